@@ -10,14 +10,17 @@ use quote::{quote, ToTokens, TokenStreamExt};
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::token::{Brace, Colon, Comma, Eq, For, Impl, Paren};
+use syn::token::{Brace, Colon, Comma, Eq, For, Impl, Paren, Sub};
 use syn::{braced, bracketed, parenthesized, parse_quote};
 use syn::{
     Attribute, ConstParam, Data, DeriveInput, Expr, Fields, FieldsNamed, FieldsUnnamed,
-    GenericParam, Generics, Ident, Index, Lifetime, LifetimeDef, Lit, Member, Token, Type,
+    GenericParam, Generics, Ident, Index, Lifetime, LifetimeDef, Lit, LitInt, Member, Token, Type,
     TypeParam, TypePath, TypeTraitObject,
 };
 
+/// Error message for a non-single layout without a `#[layout_data]` field
+pub const MISSING_LAYOUT_DATA: &str = "missing layout data field: add `#[layout_data] layout_data: <Self as kas::LayoutData>::Data,` (required for non-single layouts)";
+
 #[derive(Debug)]
 pub struct Child {
     pub ident: Member,
@@ -41,6 +44,7 @@ pub fn read_attrs(ast: &mut DeriveInput) -> Result<Args> {
             "cannot derive Widget on an enum, union or unit struct",
         ))
     };
+    let ident_span = ast.ident.span();
     let (fields, span) = match &mut ast.data {
         Data::Struct(data) => match &mut data.fields {
             Fields::Named(FieldsNamed {
@@ -148,6 +152,14 @@ pub fn read_attrs(ast: &mut DeriveInput) -> Result<Args> {
 
     let widget = widget.unwrap_or(WidgetArgs::default());
 
+    // Check this here (instead of when deriving Layout) to avoid confusing
+    // errors from other generated code.
+    if let Some(ref layout) = layout {
+        if layout.layout != LayoutType::Single && layout_data.is_none() {
+            return Err(Error::new(ident_span, MISSING_LAYOUT_DATA));
+        }
+    }
+
     if let Some(core_data) = core_data {
         Ok(Args {
             core_data,
@@ -186,6 +198,7 @@ mod kw {
     custom_keyword!(cspan);
     custom_keyword!(rspan);
     custom_keyword!(widget);
+    custom_keyword!(capture);
     custom_keyword!(handler);
     custom_keyword!(msg);
     custom_keyword!(generics);
@@ -197,6 +210,7 @@ mod kw {
     custom_keyword!(grid);
     custom_keyword!(halign);
     custom_keyword!(valign);
+    custom_keyword!(z);
     custom_keyword!(key_nav);
     custom_keyword!(hover_highlight);
     custom_keyword!(cursor_icon);
@@ -206,6 +220,9 @@ mod kw {
     custom_keyword!(noauto);
     custom_keyword!(children);
     custom_keyword!(column);
+    custom_keyword!(wrap);
+    custom_keyword!(col_gap);
+    custom_keyword!(row_gap);
 }
 
 #[derive(Debug)]
@@ -216,6 +233,7 @@ pub struct WidgetAttrArgs {
     pub rspan: Option<Lit>,
     pub halign: Option<Ident>,
     pub valign: Option<Ident>,
+    pub z: Option<i32>,
     pub handler: Option<Ident>,
 }
 
@@ -284,6 +302,7 @@ impl Parse for WidgetAttrArgs {
             rspan: None,
             halign: None,
             valign: None,
+            z: None,
             handler: None,
         };
         if input.is_empty() {
@@ -323,6 +342,13 @@ impl Parse for WidgetAttrArgs {
                 let _: kw::valign = content.parse()?;
                 let _: Eq = content.parse()?;
                 args.valign = Some(content.parse()?);
+            } else if args.z.is_none() && lookahead.peek(kw::z) {
+                let _: kw::z = content.parse()?;
+                let _: Eq = content.parse()?;
+                let neg: Option<Sub> = content.parse()?;
+                let lit: LitInt = content.parse()?;
+                let z: i32 = lit.base10_parse()?;
+                args.z = Some(if neg.is_some() { -z } else { z });
             } else if args.handler.is_none() && lookahead.peek(kw::handler) {
                 let _: kw::handler = content.parse()?;
                 let _: Eq = content.parse()?;
@@ -349,6 +375,7 @@ impl ToTokens for WidgetAttrArgs {
             || self.rspan.is_some()
             || self.halign.is_some()
             || self.valign.is_some()
+            || self.z.is_some()
             || self.handler.is_some()
         {
             let comma = TokenTree::from(Punct::new(',', Spacing::Alone));
@@ -386,6 +413,13 @@ impl ToTokens for WidgetAttrArgs {
                 }
                 args.append_all(quote! { valign = #ident });
             }
+            if let Some(z) = self.z {
+                if !args.is_empty() {
+                    args.append(comma.clone());
+                }
+                let lit = proc_macro2::Literal::i32_unsuffixed(z);
+                args.append_all(quote! { z = #lit });
+            }
             if let Some(ref ident) = self.handler {
                 if !args.is_empty() {
                     args.append(comma);
@@ -566,6 +600,29 @@ pub struct LayoutArgs {
     pub span: Span,
     pub layout: LayoutType,
     pub area: Option<Ident>,
+    pub wrap: bool,
+    pub halign: Option<Ident>,
+    pub valign: Option<Ident>,
+    pub col_gap: u32,
+    pub row_gap: u32,
+}
+
+impl LayoutArgs {
+    // Default alignment of children; these are grid-only
+    pub fn halign_toks(&self) -> Result<Option<TokenStream>> {
+        if let Some(ref ident) = self.halign {
+            Ok(Some(WidgetAttrArgs::match_align(ident, true)?))
+        } else {
+            Ok(None)
+        }
+    }
+    pub fn valign_toks(&self) -> Result<Option<TokenStream>> {
+        if let Some(ref ident) = self.valign {
+            Ok(Some(WidgetAttrArgs::match_align(ident, false)?))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 impl Parse for LayoutArgs {
@@ -619,6 +676,12 @@ impl Parse for LayoutArgs {
         }
 
         let mut area = None;
+        let mut wrap = false;
+        let mut halign = None;
+        let mut valign = None;
+        let mut col_gap = None;
+        let mut row_gap = None;
+        let is_grid = layout == LayoutType::Grid;
 
         while !content.is_empty() {
             let lookahead = content.lookahead1();
@@ -626,6 +689,27 @@ impl Parse for LayoutArgs {
                 let _: kw::area = content.parse()?;
                 let _: Eq = content.parse()?;
                 area = Some(content.parse()?);
+            } else if !wrap && is_grid && lookahead.peek(kw::wrap) {
+                let _: kw::wrap = content.parse()?;
+                wrap = true;
+            } else if halign.is_none() && is_grid && lookahead.peek(kw::halign) {
+                let _: kw::halign = content.parse()?;
+                let _: Eq = content.parse()?;
+                halign = Some(content.parse()?);
+            } else if valign.is_none() && is_grid && lookahead.peek(kw::valign) {
+                let _: kw::valign = content.parse()?;
+                let _: Eq = content.parse()?;
+                valign = Some(content.parse()?);
+            } else if col_gap.is_none() && is_grid && lookahead.peek(kw::col_gap) {
+                let _: kw::col_gap = content.parse()?;
+                let _: Eq = content.parse()?;
+                let lit: LitInt = content.parse()?;
+                col_gap = Some(lit.base10_parse()?);
+            } else if row_gap.is_none() && is_grid && lookahead.peek(kw::row_gap) {
+                let _: kw::row_gap = content.parse()?;
+                let _: Eq = content.parse()?;
+                let lit: LitInt = content.parse()?;
+                row_gap = Some(lit.base10_parse()?);
             } else {
                 return Err(lookahead.error());
             }
@@ -635,7 +719,16 @@ impl Parse for LayoutArgs {
             }
         }
 
-        Ok(LayoutArgs { span, layout, area })
+        Ok(LayoutArgs {
+            span,
+            layout,
+            area,
+            wrap,
+            halign,
+            valign,
+            col_gap: col_gap.unwrap_or(0),
+            row_gap: row_gap.unwrap_or(0),
+        })
     }
 }
 
@@ -815,6 +908,8 @@ pub enum ChildType {
 #[derive(Debug)]
 pub struct WidgetField {
     pub widget_attr: Option<WidgetAttr>,
+    // value is cloned from an external binding
+    pub capture: bool,
     pub ident: Option<Ident>,
     pub ty: ChildType,
     pub value: Expr,
@@ -907,16 +1002,24 @@ impl Parse for MakeWidget {
 
 impl Parse for WidgetField {
     fn parse(input: ParseStream) -> Result<Self> {
-        let widget_attr = if input.peek(Token![#]) {
+        let mut widget_attr = None;
+        let mut capture = false;
+        if input.peek(Token![#]) {
             let _: Token![#] = input.parse()?;
             let inner;
             let _ = bracketed!(inner in input);
-            let _: kw::widget = inner.parse()?;
-            let args = inner.parse::<WidgetAttrArgs>()?;
-            Some(WidgetAttr { args })
-        } else {
-            None
-        };
+            let lookahead = inner.lookahead1();
+            if lookahead.peek(kw::widget) {
+                let _: kw::widget = inner.parse()?;
+                let args = inner.parse::<WidgetAttrArgs>()?;
+                widget_attr = Some(WidgetAttr { args });
+            } else if lookahead.peek(kw::capture) {
+                let _: kw::capture = inner.parse()?;
+                capture = true;
+            } else {
+                return Err(lookahead.error());
+            }
+        }
 
         let ident = {
             let lookahead = input.lookahead1();
@@ -1022,6 +1125,7 @@ impl Parse for WidgetField {
 
         Ok(WidgetField {
             widget_attr,
+            capture,
             ident,
             ty,
             value,
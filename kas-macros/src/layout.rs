@@ -3,7 +3,7 @@
 // You may obtain a copy of the License in the LICENSE-APACHE file or at:
 //     https://www.apache.org/licenses/LICENSE-2.0
 
-use crate::args::{Child, LayoutArgs, LayoutType};
+use crate::args::{Child, GridPos, LayoutArgs, LayoutType, MISSING_LAYOUT_DATA};
 use proc_macro2::TokenStream;
 use quote::{quote, TokenStreamExt};
 use syn::parse::{Error, Result};
@@ -122,6 +122,69 @@ pub(crate) fn data_type(children: &Vec<Child>, layout: &LayoutArgs) -> Result<To
     })
 }
 
+fn grid_child_info(pos: &GridPos) -> TokenStream {
+    let (c0, c1) = (pos.0, pos.0 + pos.2);
+    let (r0, r1) = (pos.1, pos.1 + pos.3);
+    quote! {
+        kas::layout::GridChildInfo {
+            col: #c0,
+            col_end: #c1,
+            row: #r0,
+            row_end: #r1,
+        }
+    }
+}
+
+/// Generate grid navigation for `send`, applied to child response `r`
+///
+/// Returns `None` unless the layout is a grid.
+pub(crate) fn grid_nav(
+    children: &Vec<Child>,
+    layout: &Option<LayoutArgs>,
+    index: usize,
+) -> Result<Option<TokenStream>> {
+    let layout = match layout {
+        Some(layout) if layout.layout == LayoutType::Grid => layout,
+        _ => return Ok(None),
+    };
+
+    let mut cells = TokenStream::new();
+    for child in children.iter() {
+        let info = grid_child_info(&child.args.as_pos()?);
+        cells.append_all(quote! { #info, });
+    }
+    let len = children.len();
+    let wrap = layout.wrap;
+
+    Ok(Some(quote! {
+        let r = match r {
+            Response::Unhandled(kas::event::Event::Command(cmd, shift)) => {
+                const CELLS: [kas::layout::GridChildInfo; #len] = [#cells];
+                match mgr.grid_nav_focus(&*self, &CELLS, #index, cmd, #wrap) {
+                    Some(rect) => Response::Focus(rect),
+                    None => Response::Unhandled(kas::event::Event::Command(cmd, shift)),
+                }
+            }
+            r => r,
+        };
+    }))
+}
+
+/// Sort children by `z` for drawing: lowest first, so higher `z` is on top
+///
+/// The sort is stable: children with equal `z` remain in definition order.
+fn draw_order<T>(items: &mut [(i32, T)]) {
+    items.sort_by_key(|item| item.0);
+}
+
+/// Sort children by `z` for hit-testing (`find_id`): highest first
+///
+/// Children with equal `z` remain in definition order (as before `z` was
+/// supported).
+fn hit_test_order<T>(items: &mut [(i32, T)]) {
+    items.sort_by_key(|item| std::cmp::Reverse(item.0));
+}
+
 pub(crate) fn derive(
     children: &Vec<Child>,
     layout: &LayoutArgs,
@@ -131,10 +194,7 @@ pub(crate) fn derive(
         quote! { self.#field }
     } else {
         if layout.layout != LayoutType::Single {
-            return Err(Error::new(
-                layout.span,
-                "data field marked with #[layout_data] required when deriving Widget",
-            ));
+            return Err(Error::new(layout.span, MISSING_LAYOUT_DATA));
         }
         quote! { () }
     };
@@ -145,17 +205,26 @@ pub(crate) fn derive(
         }
     });
 
+    let (col_gap, row_gap) = (layout.col_gap, layout.row_gap);
+    let has_gaps = col_gap > 0 || row_gap > 0;
+    let default_halign = layout.halign_toks()?;
+    let default_valign = layout.valign_toks()?;
+
     let mut cols: usize = 0;
     let mut rows: usize = 0;
     let mut size = TokenStream::new();
     let mut set_rect = TokenStream::new();
-    let mut draw = TokenStream::new();
-    let mut find_id_child = TokenStream::new();
+    let mut draw_child = Vec::with_capacity(children.len());
+    let mut find_id_child = Vec::with_capacity(children.len());
 
     for child in children.iter() {
         let ident = &child.ident;
         let args = &child.args;
 
+        // Gaps are inserted before each column/row (except the first) as
+        // part of the cells of children starting there
+        let (mut hgap, mut vgap) = (false, false);
+
         let child_info = match layout.layout {
             LayoutType::Single => quote! { () },
             LayoutType::Right | LayoutType::Left => {
@@ -174,58 +243,107 @@ pub(crate) fn derive(
             }
             LayoutType::Grid => {
                 let pos = args.as_pos()?;
-                let (c0, c1) = (pos.0, pos.0 + pos.2);
-                let (r0, r1) = (pos.1, pos.1 + pos.3);
-                cols = cols.max(c1 as usize);
-                rows = rows.max(r1 as usize);
-
-                quote! {
-                    kas::layout::GridChildInfo {
-                        col: #c0,
-                        col_end: #c1,
-                        row: #r0,
-                        row_end: #r1,
-                    }
-                }
+                cols = cols.max((pos.0 + pos.2) as usize);
+                rows = rows.max((pos.1 + pos.3) as usize);
+                hgap = col_gap > 0 && pos.0 > 0;
+                vgap = row_gap > 0 && pos.1 > 0;
+                grid_child_info(&pos)
             }
         };
 
-        size.append_all(quote! {
-            let child = &mut self.#ident;
-            solver.for_child(
-                &mut #data,
-                #child_info,
-                |axis| child.size_rules(sh, axis)
-            );
-        });
+        if hgap || vgap {
+            let hgap = if hgap {
+                quote! { col_gap }
+            } else {
+                quote! { 0 }
+            };
+            let vgap = if vgap {
+                quote! { row_gap }
+            } else {
+                quote! { 0 }
+            };
+            size.append_all(quote! {
+                let child = &mut self.#ident;
+                solver.for_child(
+                    &mut #data,
+                    #child_info,
+                    |axis| {
+                        let (gap, other_gap) = match axis.is_vertical() {
+                            false => (#hgap, #vgap),
+                            true => (#vgap, #hgap),
+                        };
+                        let other = axis.other().map(|size| size - other_gap);
+                        let axis = kas::layout::AxisInfo::new(axis.is_vertical(), other);
+                        let rules = child.size_rules(sh, axis);
+                        kas::layout::SizeRules::new(
+                            rules.min_size() + gap,
+                            rules.ideal_size() + gap,
+                            rules.margins(),
+                            rules.stretch(),
+                        )
+                    }
+                );
+            });
+            set_rect.append_all(quote! {
+                let mut child_rect = setter.child_rect(&mut #data, #child_info);
+                child_rect.pos += kas::geom::Offset(#hgap, #vgap);
+                child_rect.size -= kas::geom::Size(#hgap, #vgap);
+            });
+        } else {
+            size.append_all(quote! {
+                let child = &mut self.#ident;
+                solver.for_child(
+                    &mut #data,
+                    #child_info,
+                    |axis| child.size_rules(sh, axis)
+                );
+            });
+            set_rect.append_all(quote! {
+                let child_rect = setter.child_rect(&mut #data, #child_info);
+            });
+        }
 
         set_rect.append_all(quote! { let mut align2 = align; });
-        if let Some(toks) = args.halign_toks()? {
+        if let Some(toks) = args.halign_toks()?.or_else(|| default_halign.clone()) {
             set_rect.append_all(quote! { align2.horiz = Some(#toks); });
         }
-        if let Some(toks) = args.valign_toks()? {
+        if let Some(toks) = args.valign_toks()?.or_else(|| default_valign.clone()) {
             set_rect.append_all(quote! { align2.vert = Some(#toks); });
         }
         set_rect.append_all(quote! {
-            self.#ident.set_rect(_mgr, setter.child_rect(&mut #data, #child_info), align2);
+            self.#ident.set_rect(_mgr, child_rect, align2);
         });
 
-        draw.append_all(quote! {
-            let c1 = self.#ident.rect().pos;
-            let c2 = self.#ident.rect().pos2();
-            if c1.0 <= pos2.0 && c2.0 >= pos1.0 && c1.1 <= pos2.1 && c2.1 >= pos1.1 {
-                self.#ident.draw(draw_handle, mgr, disabled);
-            }
-        });
+        let z = args.z.unwrap_or(0);
+        draw_child.push((
+            z,
+            quote! {
+                let c1 = self.#ident.rect().pos;
+                let c2 = self.#ident.rect().pos2();
+                if c1.0 <= pos2.0 && c2.0 >= pos1.0 && c1.1 <= pos2.1 && c2.1 >= pos1.1 {
+                    self.#ident.draw(draw_handle, mgr, disabled);
+                }
+            },
+        ));
 
         // TODO: more efficient search strategy?
-        find_id_child.append_all(quote! {
-            if let Some(id) = self.#ident.find_id(coord) {
-                return Some(id);
-            }
-        });
+        find_id_child.push((
+            z,
+            quote! {
+                if let Some(id) = self.#ident.find_id(coord) {
+                    return Some(id);
+                }
+            },
+        ));
     }
 
+    draw_order(&mut draw_child);
+    hit_test_order(&mut find_id_child);
+    let mut draw = TokenStream::new();
+    draw.append_all(draw_child.into_iter().map(|item| item.1));
+    let mut find_id_child_toks = TokenStream::new();
+    find_id_child_toks.append_all(find_id_child.into_iter().map(|item| item.1));
+
     let dim = match layout.layout {
         LayoutType::Single => quote! { () },
         LayoutType::Right => quote! { (kas::dir::Right, #cols) },
@@ -235,9 +353,29 @@ pub(crate) fn derive(
         LayoutType::Grid => quote! { (#cols, #rows) },
     };
 
+    let (size_gaps, set_rect_gaps) = if has_gaps {
+        let gaps = quote! {
+            let scale = sh.scale_factor();
+            let col_gap = (#col_gap as f32 * scale).round() as i32;
+            let row_gap = (#row_gap as f32 * scale).round() as i32;
+            let _ = (col_gap, row_gap);
+        };
+        (
+            gaps.clone(),
+            quote! {
+                let (col_gap, row_gap) = _mgr.size_handle(|sh| {
+                    #gaps
+                    (col_gap, row_gap)
+                });
+            },
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
     let find_id_body = find_id_area.unwrap_or_else(|| {
         quote! {
-            #find_id_child
+            #find_id_child_toks
             Some(self.id())
         }
     });
@@ -254,6 +392,7 @@ pub(crate) fn derive(
                 #dim,
                 &mut #data,
             );
+            #size_gaps
             #size
             solver.finish(&mut #data)
         }
@@ -274,6 +413,7 @@ pub(crate) fn derive(
                 align,
                 &mut #data,
             );
+            #set_rect_gaps
             #set_rect
         }
 
@@ -302,3 +442,26 @@ pub(crate) fn derive(
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn order(z: &[i32], f: fn(&mut [(i32, usize)])) -> Vec<usize> {
+        let mut items: Vec<_> = z.iter().cloned().zip(0..).collect();
+        f(&mut items);
+        items.into_iter().map(|item| item.1).collect()
+    }
+
+    #[test]
+    fn z_order() {
+        let z = [0, 2, 0, -1, 1];
+        assert_eq!(order(&z, draw_order), vec![3, 0, 2, 4, 1]);
+        assert_eq!(order(&z, hit_test_order), vec![1, 4, 0, 2, 3]);
+
+        // default z preserves definition order
+        let z = [0; 4];
+        assert_eq!(order(&z, draw_order), vec![0, 1, 2, 3]);
+        assert_eq!(order(&z, hit_test_order), vec![0, 1, 2, 3]);
+    }
+}
@@ -13,9 +13,8 @@ mod args;
 use std::collections::HashMap;
 
 use proc_macro2::{Span, TokenStream};
-use quote::{quote, ToTokens, TokenStreamExt};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use std::fmt::Write;
-#[cfg(nightly)]
 use syn::spanned::Spanned;
 use syn::Token;
 use syn::{parse_macro_input, parse_quote};
@@ -272,16 +271,25 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
         if handler.send {
             let mut ev_to_num = TokenStream::new();
-            for child in args.children.iter() {
+            for (i, child) in args.children.iter().enumerate() {
                 let ident = &child.ident;
+                let nav = layout::grid_nav(&args.children, &args.layout, i);
+                let nav = match nav {
+                    Ok(nav) => nav,
+                    Err(err) => return err.to_compile_error().into(),
+                };
                 let handler = if let Some(ref h) = child.args.handler {
                     quote! { r.try_into().unwrap_or_else(|msg| self.#h(mgr, msg)) }
                 } else {
-                    quote! { r.into() }
+                    // Span at the field such that errors point there
+                    quote_spanned! {ident.span()=>
+                        <_ as kas::macros::ConvertMsgOrAddHandler<_>>::convert(r)
+                    }
                 };
                 ev_to_num.append_all(quote! {
                     if id <= self.#ident.id() {
                         let r = self.#ident.send(mgr, id, event);
+                        #nav
                         #handler
                     } else
                 });
@@ -528,8 +536,15 @@ pub fn make_widget(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     } else {
                         args.generics.params.push(parse_quote! { #ty: kas::Widget });
                     }
+                } else if let Some(mut bound) = gen_bound {
+                    // Required by derive(Debug) and WidgetCore: Any
+                    bound.bounds.push(parse_quote! { std::fmt::Debug });
+                    bound.bounds.push(parse_quote! { 'static });
+                    args.generics.params.push(parse_quote! { #ty: #bound });
                 } else {
-                    args.generics.params.push(parse_quote! { #ty });
+                    args.generics
+                        .params
+                        .push(parse_quote! { #ty: std::fmt::Debug + 'static });
                 }
 
                 Type::Path(TypePath {
@@ -540,6 +555,11 @@ pub fn make_widget(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         };
 
         let value = &field.value;
+        let value = if field.capture {
+            quote! { ::std::clone::Clone::clone(&(#value)) }
+        } else {
+            quote! { #value }
+        };
 
         field_toks.append_all(quote! { #attr #ident: #ty, });
         field_val_toks.append_all(quote! { #ident: #value, });
@@ -9,6 +9,35 @@ use log::warn;
 
 use kas::draw::{Colour, InputState, TextClass};
 
+/// Palette colours set via [`kas::ThemeApi::set_palette_colour`]
+///
+/// These are applied over the colour scheme and re-applied when it changes.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Palette(Vec<(String, Colour)>);
+
+impl Palette {
+    /// Set a palette entry on `cols` and remember it
+    ///
+    /// Returns false if `name` is not recognised.
+    pub fn set(&mut self, cols: &mut ThemeColours, name: &str, col: Colour) -> bool {
+        if !cols.set_palette_colour(name, col) {
+            return false;
+        }
+        match self.0.iter_mut().find(|entry| entry.0 == name) {
+            Some(entry) => entry.1 = col,
+            None => self.0.push((name.to_string(), col)),
+        }
+        true
+    }
+
+    /// Apply remembered entries to `cols`
+    pub fn apply(&self, cols: &mut ThemeColours) {
+        for (name, col) in &self.0 {
+            cols.set_palette_colour(name, *col);
+        }
+    }
+}
+
 /// Provides standard theme colours
 #[derive(Clone, Debug)]
 pub struct ThemeColours {
@@ -20,25 +49,36 @@ pub struct ThemeColours {
     pub bg: Colour,
     /// Background colour of `EditBox` (disabled state)
     pub bg_disabled: Colour,
+    /// Background colour of `EditBox` (read-only state)
+    pub bg_read_only: Colour,
     /// Background colour of `EditBox` (error state)
     pub bg_error: Colour,
     /// Text colour in an `EditBox`
     pub text: Colour,
+    /// Dimmed text colour (placeholder text)
+    pub text_disabled: Colour,
     /// Selected tect colour
     pub text_sel: Colour,
     /// Selected text background colour
+    ///
+    /// Also used for selected items and link text. Palette entry
+    /// `"selection_bg"`.
     pub text_sel_bg: Colour,
     /// Text colour in a `Label`
     pub label_text: Colour,
     /// Text colour on a `TextButton`
     pub button_text: Colour,
     /// Highlight colour for keyboard navigation
+    ///
+    /// Palette entry `"focus_ring"`.
     pub nav_focus: Colour,
     /// Colour of a `TextButton`
     pub button: Colour,
     /// Colour of a `TextButton` (disabled state)
     pub button_disabled: Colour,
     /// Colour of a `TextButton` when hovered by the mouse
+    ///
+    /// Also used for menu entries. Palette entry `"hover_bg"`.
     pub button_highlighted: Colour,
     /// Colour of a `TextButton` when depressed
     pub button_depressed: Colour,
@@ -57,6 +97,7 @@ impl ThemeColours {
             "grey" => Self::grey(),
             "light" => Self::light(),
             "dark" => Self::dark(),
+            "contrast" => Self::high_contrast(),
             other => {
                 warn!("ThemeColours::open: scheme \"{}\" not found", other);
                 return None;
@@ -71,8 +112,10 @@ impl ThemeColours {
             frame: Colour::grey(0.7),
             bg: Colour::grey(1.0),
             bg_disabled: Colour::grey(0.85),
+            bg_read_only: Colour::grey(0.95),
             bg_error: Colour::new(1.0, 0.5, 0.5),
             text: Colour::grey(0.0),
+            text_disabled: Colour::grey(0.6),
             text_sel: Colour::grey(1.0),
             text_sel_bg: Colour::new(0.15, 0.525, 0.75),
             label_text: Colour::grey(0.0),
@@ -100,8 +143,10 @@ impl ThemeColours {
             frame: Colour::new(0.8, 0.8, 0.9),
             bg: Colour::grey(1.0),
             bg_disabled: Colour::grey(0.85),
+            bg_read_only: Colour::grey(0.95),
             bg_error: Colour::new(1.0, 0.5, 0.5),
             text: Colour::grey(0.0),
+            text_disabled: Colour::grey(0.55),
             text_sel: Colour::grey(0.0),
             text_sel_bg: Colour::new(0.8, 0.72, 0.24),
             label_text: Colour::grey(0.0),
@@ -122,8 +167,10 @@ impl ThemeColours {
             frame: Colour::grey(0.4),
             bg: Colour::grey(0.1),
             bg_disabled: Colour::grey(0.3),
+            bg_read_only: Colour::grey(0.15),
             bg_error: Colour::new(1.0, 0.5, 0.5),
             text: Colour::grey(1.0),
+            text_disabled: Colour::grey(0.5),
             text_sel: Colour::grey(1.0),
             text_sel_bg: Colour::new(0.6, 0.3, 0.1),
             label_text: Colour::grey(1.0),
@@ -137,12 +184,56 @@ impl ThemeColours {
         }
     }
 
+    /// High-contrast scheme
+    pub fn high_contrast() -> Self {
+        ThemeColours {
+            background: Colour::grey(0.0),
+            frame: Colour::grey(1.0),
+            bg: Colour::grey(0.0),
+            bg_disabled: Colour::grey(0.25),
+            bg_read_only: Colour::grey(0.1),
+            bg_error: Colour::new(0.6, 0.0, 0.0),
+            text: Colour::grey(1.0),
+            text_disabled: Colour::grey(0.6),
+            text_sel: Colour::grey(0.0),
+            text_sel_bg: Colour::new(0.0, 1.0, 1.0),
+            label_text: Colour::grey(1.0),
+            button_text: Colour::grey(0.0),
+            nav_focus: Colour::new(1.0, 1.0, 0.0),
+            button: Colour::grey(1.0),
+            button_disabled: Colour::grey(0.5),
+            button_highlighted: Colour::new(0.0, 1.0, 1.0),
+            button_depressed: Colour::new(1.0, 1.0, 0.0),
+            checkbox: Colour::grey(1.0),
+        }
+    }
+
+    /// Set a named palette entry
+    ///
+    /// Supported names are `"selection_bg"` (sets `text_sel_bg`), `"hover_bg"`
+    /// (sets `button_highlighted`) and `"focus_ring"` (sets `nav_focus`).
+    /// Returns false (and logs a warning) if `name` is not recognised.
+    pub fn set_palette_colour(&mut self, name: &str, col: Colour) -> bool {
+        match name {
+            "selection_bg" => self.text_sel_bg = col,
+            "hover_bg" => self.button_highlighted = col,
+            "focus_ring" => self.nav_focus = col,
+            other => {
+                warn!("ThemeColours::set_palette_colour: no entry \"{}\"", other);
+                return false;
+            }
+        }
+        true
+    }
+
     /// Get colour of a text area, depending on state
     pub fn bg_col(&self, state: InputState) -> Colour {
         if state.disabled {
             self.bg_disabled
         } else if state.error {
             self.bg_error
+        } else if state.read_only {
+            self.bg_read_only
         } else {
             self.bg
         }
@@ -212,6 +303,8 @@ impl ThemeColours {
             TextClass::Label | TextClass::LabelFixed => self.label_text,
             TextClass::Button => self.button_text,
             TextClass::Edit | TextClass::EditMulti => self.text,
+            TextClass::Placeholder => self.text_disabled,
+            TextClass::Link => self.text_sel_bg,
         }
     }
 }
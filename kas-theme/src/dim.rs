@@ -8,13 +8,14 @@
 //! Widget size and appearance can be modified through themes.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::f32;
 
 use kas::conv::{Cast, CastFloat, ConvFloat};
 use kas::draw::{self, TextClass};
 use kas::geom::{Size, Vec2};
 use kas::layout::{AxisInfo, FrameRules, Margins, SizeRules, StretchPolicy};
-use kas::text::{TextApi, TextApiExt};
+use kas::text::{Text, TextApi, TextApiExt};
 
 /// Parameterisation of [`Dimensions`]
 ///
@@ -38,6 +39,8 @@ pub struct DimensionsParams {
     pub slider_size: Vec2,
     /// Progress bar size (horizontal)
     pub progress_bar: Vec2,
+    /// Anti-aliasing feather width of rounded shapes (not rounded)
+    pub feather: f32,
 }
 
 /// Dimensions available within [`DimensionsWindow`]
@@ -59,6 +62,7 @@ pub struct Dimensions {
     pub scrollbar: Size,
     pub slider: Size,
     pub progress_bar: Size,
+    pub feather: f32,
 }
 
 impl Dimensions {
@@ -89,19 +93,30 @@ impl Dimensions {
             scrollbar: Size::from(params.scrollbar_size * scale_factor),
             slider: Size::from(params.slider_size * scale_factor),
             progress_bar: Size::from(params.progress_bar * scale_factor),
+            feather: params.feather * scale_factor,
         }
     }
 }
 
+/// Cache of text measurements, keyed by text, class, font size and wrap width
+///
+/// Font size and wrap width are stored as bit patterns of the `f32` values.
+pub type TextSizeCache = HashMap<(String, TextClass, u32, Option<u32>), Vec2>;
+
+// Limit on cache entries; beyond this the cache is cleared
+const TEXT_SIZE_CACHE_LEN: usize = 1024;
+
 /// A convenient implementation of [`crate::Window`]
 pub struct DimensionsWindow {
     pub dims: Dimensions,
+    pub text_size_cache: TextSizeCache,
 }
 
 impl DimensionsWindow {
     pub fn new(dims: DimensionsParams, pt_size: f32, scale_factor: f32) -> Self {
         DimensionsWindow {
             dims: Dimensions::new(dims, pt_size, scale_factor),
+            text_size_cache: Default::default(),
         }
     }
 }
@@ -115,12 +130,12 @@ impl crate::Window for DimensionsWindow {
     #[cfg(not(feature = "gat"))]
     unsafe fn size_handle<'a>(&'a mut self) -> Self::SizeHandle {
         // We extend lifetimes (unsafe) due to the lack of associated type generics.
-        let h: SizeHandle<'a> = SizeHandle::new(&self.dims);
+        let h: SizeHandle<'a> = SizeHandle::new(&self.dims, &mut self.text_size_cache);
         std::mem::transmute(h)
     }
     #[cfg(feature = "gat")]
     fn size_handle<'a>(&'a mut self) -> Self::SizeHandle<'a> {
-        SizeHandle::new(&self.dims)
+        SizeHandle::new(&self.dims, &mut self.text_size_cache)
     }
 
     fn as_any_mut(&mut self) -> &mut dyn Any {
@@ -130,11 +145,15 @@ impl crate::Window for DimensionsWindow {
 
 pub struct SizeHandle<'a> {
     dims: &'a Dimensions,
+    text_size_cache: &'a mut TextSizeCache,
 }
 
 impl<'a> SizeHandle<'a> {
-    pub fn new(dims: &'a Dimensions) -> Self {
-        SizeHandle { dims }
+    pub fn new(dims: &'a Dimensions, text_size_cache: &'a mut TextSizeCache) -> Self {
+        SizeHandle {
+            dims,
+            text_size_cache,
+        }
     }
 }
 
@@ -169,6 +188,26 @@ impl<'a> draw::SizeHandle for SizeHandle<'a> {
         self.dims.line_height
     }
 
+    fn text_dpem(&self, _: TextClass) -> f32 {
+        self.dims.dpp * self.dims.pt_size
+    }
+
+    fn metrics(&self) -> draw::Metrics {
+        let inner_margin = i32::from(self.dims.inner_margin);
+        let text_margin = i32::from(self.dims.text_margin);
+        let line_height = self.dims.line_height;
+        draw::Metrics {
+            gap: self.dims.outer_margin.into(),
+            inner_margin,
+            text_margin,
+            frame: self.dims.frame,
+            line_height,
+            control_height: line_height + 2 * (text_margin + inner_margin + self.dims.frame),
+            icon_size: line_height,
+            corner_radius: self.dims.button_frame as f32,
+        }
+    }
+
     fn text_bound(
         &mut self,
         text: &mut dyn TextApi,
@@ -188,7 +227,7 @@ impl<'a> draw::SizeHandle for SizeHandle<'a> {
             env.set_bounds(bounds);
 
             env.set_wrap(match class {
-                TextClass::Label | TextClass::EditMulti => true,
+                TextClass::Label | TextClass::Link | TextClass::EditMulti => true,
                 _ => false,
             });
         });
@@ -214,10 +253,11 @@ impl<'a> draw::SizeHandle for SizeHandle<'a> {
             SizeRules::new(min, ideal, margins, stretch)
         } else {
             let min = match class {
-                TextClass::Label => i32::conv_ceil(required.1),
-                TextClass::LabelFixed | TextClass::Button | TextClass::Edit => {
-                    self.dims.line_height
-                }
+                TextClass::Label | TextClass::Link => i32::conv_ceil(required.1),
+                TextClass::LabelFixed
+                | TextClass::Button
+                | TextClass::Edit
+                | TextClass::Placeholder => self.dims.line_height,
                 TextClass::EditMulti => self.dims.line_height * 3,
             };
             let ideal = i32::conv_ceil(required.1).max(min);
@@ -230,6 +270,35 @@ impl<'a> draw::SizeHandle for SizeHandle<'a> {
         }
     }
 
+    fn text_size(&mut self, text: &str, class: TextClass, wrap_width: Option<f32>) -> Vec2 {
+        let dpem = self.text_dpem(class);
+        let key = (
+            text.to_string(),
+            class,
+            dpem.to_bits(),
+            wrap_width.map(|w| w.to_bits()),
+        );
+        if let Some(size) = self.text_size_cache.get(&key) {
+            return *size;
+        }
+
+        let mut t = Text::new_multi(text);
+        let required = t.update_env(|env| {
+            env.set_dpp(self.dims.dpp);
+            env.set_pt_size(self.dims.pt_size);
+            let width = wrap_width.unwrap_or(f32::INFINITY);
+            env.set_bounds(kas::text::Vec2(width, f32::INFINITY));
+            env.set_wrap(wrap_width.is_some());
+        });
+        let size = Vec2::from(required);
+
+        if self.text_size_cache.len() >= TEXT_SIZE_CACHE_LEN {
+            self.text_size_cache.clear();
+        }
+        self.text_size_cache.insert(key, size);
+        size
+    }
+
     fn edit_marker_width(&self) -> f32 {
         self.dims.font_marker_width
     }
@@ -268,4 +337,8 @@ impl<'a> draw::SizeHandle for SizeHandle<'a> {
     fn progress_bar(&self) -> Size {
         self.dims.progress_bar
     }
+
+    fn spinner(&self) -> Size {
+        Size::splat(self.dims.line_height)
+    }
 }
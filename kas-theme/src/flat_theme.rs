@@ -10,6 +10,7 @@
 use std::f32;
 use std::ops::Range;
 
+use crate::col::Palette;
 use crate::{Dimensions, DimensionsParams, DimensionsWindow, Theme, ThemeColours, Window};
 use kas::conv::Cast;
 use kas::dir::{Direction, Directional};
@@ -26,7 +27,10 @@ use kas::{ThemeAction, ThemeApi};
 #[derive(Clone, Debug)]
 pub struct FlatTheme {
     pt_size: f32,
+    font_scale: f32,
     cols: ThemeColours,
+    palette: Palette,
+    dims: DimensionsParams,
 }
 
 impl FlatTheme {
@@ -34,7 +38,10 @@ impl FlatTheme {
     pub fn new() -> Self {
         FlatTheme {
             pt_size: 12.0,
+            font_scale: 1.0,
             cols: ThemeColours::new(),
+            palette: Default::default(),
+            dims: DIMS,
         }
     }
 
@@ -46,15 +53,49 @@ impl FlatTheme {
         self
     }
 
+    /// Set font scale
+    ///
+    /// Font sizes are multiplied by this factor (see
+    /// [`ThemeApi::set_font_scale`]). If the toolkit's configuration sets
+    /// [`kas::event::Config::font_scale`], that value is used instead.
+    pub fn with_font_scale(mut self, scale: f32) -> Self {
+        self.font_scale = scale;
+        self
+    }
+
     /// Set the colour scheme
     ///
     /// If no scheme by this name is found the scheme is left unchanged.
     pub fn with_colours(mut self, scheme: &str) -> Self {
         if let Some(scheme) = ThemeColours::open(scheme) {
             self.cols = scheme;
+            self.palette.apply(&mut self.cols);
         }
         self
     }
+
+    /// Set custom colours
+    ///
+    /// Usually one would start from a standard scheme, e.g.
+    /// `ThemeColours::open("dark")`, and adjust a few colours.
+    pub fn with_custom_colours(mut self, cols: ThemeColours) -> Self {
+        self.cols = cols;
+        self.palette.apply(&mut self.cols);
+        self
+    }
+
+    /// Get the dimension parameters used by this theme
+    ///
+    /// This may be used as a starting point for [`Self::with_dimensions`].
+    pub fn dimensions(&self) -> &DimensionsParams {
+        &self.dims
+    }
+
+    /// Set custom dimensions (margins, frame sizes, etc.)
+    pub fn with_dimensions(mut self, dims: DimensionsParams) -> Self {
+        self.dims = dims;
+        self
+    }
 }
 
 const DIMS: DimensionsParams = DimensionsParams {
@@ -66,6 +107,7 @@ const DIMS: DimensionsParams = DimensionsParams {
     scrollbar_size: Vec2::splat(8.0),
     slider_size: Vec2(12.0, 25.0),
     progress_bar: Vec2::splat(12.0),
+    feather: 1.0,
 };
 
 pub struct DrawHandle<'a, D: Draw> {
@@ -95,11 +137,19 @@ where
     }
 
     fn new_window(&self, _draw: &mut D::Draw, dpi_factor: f32) -> Self::Window {
-        DimensionsWindow::new(DIMS, self.pt_size, dpi_factor)
+        DimensionsWindow::new(
+            self.dims.clone(),
+            self.pt_size * self.font_scale,
+            dpi_factor,
+        )
     }
 
     fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
-        window.dims = Dimensions::new(DIMS, self.pt_size, dpi_factor);
+        window.dims = Dimensions::new(
+            self.dims.clone(),
+            self.pt_size * self.font_scale,
+            dpi_factor,
+        );
     }
 
     #[cfg(not(feature = "gat"))]
@@ -110,6 +160,7 @@ where
         rect: Rect,
     ) -> Self::DrawHandle {
         draw.prepare_fonts();
+        draw.set_feather(window.dims.feather);
 
         // We extend lifetimes (unsafe) due to the lack of associated type generics.
         use std::mem::transmute;
@@ -130,6 +181,7 @@ where
         rect: Rect,
     ) -> Self::DrawHandle<'a> {
         draw.prepare_fonts();
+        draw.set_feather(window.dims.feather);
 
         DrawHandle {
             draw,
@@ -152,9 +204,23 @@ impl ThemeApi for FlatTheme {
         ThemeAction::ThemeResize
     }
 
+    fn set_font_scale(&mut self, scale: f32) -> ThemeAction {
+        self.font_scale = scale;
+        ThemeAction::ThemeResize
+    }
+
     fn set_colours(&mut self, scheme: &str) -> ThemeAction {
         if let Some(scheme) = ThemeColours::open(scheme) {
             self.cols = scheme;
+            self.palette.apply(&mut self.cols);
+            ThemeAction::RedrawAll
+        } else {
+            ThemeAction::None
+        }
+    }
+
+    fn set_palette_colour(&mut self, name: &str, col: Colour) -> ThemeAction {
+        if self.palette.set(&mut self.cols, name, col) {
             ThemeAction::RedrawAll
         } else {
             ThemeAction::None
@@ -225,7 +291,7 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
     ) {
         let rect = rect + self.offset;
         let depth = self.pass.depth() + super::relative_region_depth(class);
-        let pass = self.draw.add_clip_region(rect, depth);
+        let pass = self.draw.push_clip_region(rect, depth);
         if depth < self.pass.depth() {
             // draw to depth buffer to enable correct text rendering
             self.draw
@@ -240,6 +306,7 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
             pass,
         };
         f(&mut handle);
+        self.draw.pop_clip_region();
     }
 
     fn target_rect(&self) -> Rect {
@@ -247,6 +314,41 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         self.rect - self.offset
     }
 
+    fn quad(&mut self, quad: Quad, col: Colour) {
+        let quad = quad + Vec2::from(self.offset);
+        self.draw.rect(self.pass, quad, col);
+    }
+
+    fn frame(&mut self, outer: Quad, inner: Quad, col: Colour) {
+        let offset = Vec2::from(self.offset);
+        self.draw
+            .frame(self.pass, outer + offset, inner + offset, col);
+    }
+
+    fn line(&mut self, p1: Vec2, p2: Vec2, width: f32, col: Colour) {
+        let offset = Vec2::from(self.offset);
+        self.draw
+            .rounded_line(self.pass, p1 + offset, p2 + offset, 0.5 * width, col);
+    }
+
+    fn circle(&mut self, quad: Quad, inner_radius: f32, col: Colour) {
+        let quad = quad + Vec2::from(self.offset);
+        self.draw.circle(self.pass, quad, inner_radius, col);
+    }
+
+    fn text_col(&mut self, pos: Coord, text: &dyn TextApi, col: Colour) {
+        let pos = pos + self.offset;
+        let bounds = text.env().bounds.into();
+        self.draw.text(
+            self.pass,
+            pos.into(),
+            bounds,
+            Vec2::ZERO,
+            text.display(),
+            col,
+        );
+    }
+
     fn outer_frame(&mut self, rect: Rect) {
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(self.window.dims.frame as f32);
@@ -304,6 +406,42 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         );
     }
 
+    fn text_col_spans(
+        &mut self,
+        pos: Coord,
+        offset: Offset,
+        text: &dyn TextApi,
+        effects: &[Effect<Option<Colour>>],
+        class: TextClass,
+    ) {
+        let col = self.cols.text_class(class);
+        let mut effects: Vec<_> = effects
+            .iter()
+            .map(|e| Effect {
+                start: e.start,
+                flags: e.flags,
+                aux: e.aux.unwrap_or(col),
+            })
+            .collect();
+        // The backend's default colour is not the theme's; supply our own
+        if effects.first().map(|e| e.start > 0).unwrap_or(true) {
+            let first = Effect {
+                start: 0,
+                flags: Default::default(),
+                aux: col,
+            };
+            effects.insert(0, first);
+        }
+        self.draw.text_effects(
+            self.pass,
+            (pos + self.offset).into(),
+            text.env().bounds.into(),
+            offset.into(),
+            text.display(),
+            &effects,
+        );
+    }
+
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         let pos = Vec2::from(pos + self.offset);
         let offset = Vec2::ZERO;
@@ -504,4 +642,11 @@ impl<'a, D: Draw + DrawRounded + DrawText> draw::DrawHandle for DrawHandle<'a, D
         let col = self.cols.button;
         self.draw.rect(self.pass, inner, col);
     }
+
+    fn spinner(&mut self, rect: Rect, _: InputState, phase: f32) {
+        let outer = Quad::from(rect + self.offset);
+        let min_thickness = self.window.dims.frame as f32;
+        let cols = (self.cols.frame, self.cols.button);
+        super::draw_spinner(self.draw, self.pass, outer, min_thickness, phase, cols);
+    }
 }
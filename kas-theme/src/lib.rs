@@ -29,7 +29,8 @@ mod theme_dst;
 mod traits;
 
 pub use kas;
-use kas::draw::{ClipRegion, Pass};
+use kas::draw::{ClipRegion, Colour, DrawRounded, Pass};
+use kas::geom::{Quad, Vec2};
 
 pub use col::ThemeColours;
 pub use dim::{Dimensions, DimensionsParams, DimensionsWindow};
@@ -61,3 +62,39 @@ fn relative_region_depth(class: ClipRegion) -> f32 {
         ClipRegion::Scroll => -1e-5,
     }
 }
+
+/// Draw a spinner within the centred square of `outer`
+///
+/// This is a ring of colour `cols.0` with a rotating arc of colour `cols.1`.
+/// The arc covers a third of a revolution, starting from `phase` revolutions
+/// past the top.
+fn draw_spinner<D: DrawRounded + ?Sized>(
+    draw: &mut D,
+    pass: Pass,
+    outer: Quad,
+    min_thickness: f32,
+    phase: f32,
+    cols: (Colour, Colour),
+) {
+    let side = outer.size().min_comp();
+    let quad = outer.shrink_vec((outer.size() - Vec2::splat(side)) * 0.5);
+    let thickness = (side / 8.0).max(min_thickness);
+
+    let inner_radius = 1.0 - 2.0 * thickness / side;
+    draw.circle(pass, quad, inner_radius, cols.0);
+
+    const SEGMENTS: usize = 8;
+    const SWEEP: f32 = 1.0 / 3.0;
+    let centre = (quad.a + quad.b) * 0.5;
+    let radius = 0.5 * (quad.size().min_comp() - thickness);
+    let point = |turns: f32| {
+        let angle = turns * 2.0 * std::f32::consts::PI;
+        centre + Vec2(angle.sin(), -angle.cos()) * radius
+    };
+    let mut p1 = point(phase);
+    for i in 1..=SEGMENTS {
+        let p2 = point(phase + SWEEP * (i as f32) / (SEGMENTS as f32));
+        draw.rounded_line(pass, p1, p2, 0.5 * thickness, cols.1);
+        p1 = p2;
+    }
+}
@@ -160,6 +160,14 @@ impl<Draw> ThemeApi for MultiTheme<Draw> {
         action
     }
 
+    fn set_font_scale(&mut self, scale: f32) -> ThemeAction {
+        let mut action = ThemeAction::None;
+        for theme in &mut self.themes {
+            action = action.max(theme.set_font_scale(scale));
+        }
+        action
+    }
+
     fn set_colours(&mut self, scheme: &str) -> ThemeAction {
         // Slightly inefficient, but sufficient: update all
         // (Otherwise we would have to call set_colours in set_theme too.)
@@ -170,6 +178,14 @@ impl<Draw> ThemeApi for MultiTheme<Draw> {
         action
     }
 
+    fn set_palette_colour(&mut self, name: &str, col: Colour) -> ThemeAction {
+        let mut action = ThemeAction::None;
+        for theme in &mut self.themes {
+            action = action.max(theme.set_palette_colour(name, col));
+        }
+        action
+    }
+
     fn set_theme(&mut self, theme: &str) -> ThemeAction {
         if let Some(index) = self.names.get(theme).cloned() {
             if index != self.active {
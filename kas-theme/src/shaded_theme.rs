@@ -8,6 +8,7 @@
 use std::f32;
 use std::ops::Range;
 
+use crate::col::Palette;
 use crate::{Dimensions, DimensionsParams, DimensionsWindow, Theme, ThemeColours, Window};
 use kas::dir::{Direction, Directional};
 use kas::draw::{
@@ -15,14 +16,17 @@ use kas::draw::{
     Pass, SizeHandle, TextClass,
 };
 use kas::geom::*;
-use kas::text::{AccelString, Text, TextApi, TextDisplay};
+use kas::text::{AccelString, Effect, Text, TextApi, TextDisplay};
 use kas::{ThemeAction, ThemeApi};
 
 /// A theme using simple shading to give apparent depth to elements
 #[derive(Clone, Debug)]
 pub struct ShadedTheme {
     pt_size: f32,
+    font_scale: f32,
     cols: ThemeColours,
+    palette: Palette,
+    dims: DimensionsParams,
 }
 
 impl ShadedTheme {
@@ -30,7 +34,10 @@ impl ShadedTheme {
     pub fn new() -> Self {
         ShadedTheme {
             pt_size: 12.0,
+            font_scale: 1.0,
             cols: ThemeColours::new(),
+            palette: Default::default(),
+            dims: DIMS,
         }
     }
 
@@ -42,15 +49,49 @@ impl ShadedTheme {
         self
     }
 
+    /// Set font scale
+    ///
+    /// Font sizes are multiplied by this factor (see
+    /// [`ThemeApi::set_font_scale`]). If the toolkit's configuration sets
+    /// [`kas::event::Config::font_scale`], that value is used instead.
+    pub fn with_font_scale(mut self, scale: f32) -> Self {
+        self.font_scale = scale;
+        self
+    }
+
     /// Set the colour scheme
     ///
     /// If no scheme by this name is found the scheme is left unchanged.
     pub fn with_colours(mut self, scheme: &str) -> Self {
         if let Some(scheme) = ThemeColours::open(scheme) {
             self.cols = scheme;
+            self.palette.apply(&mut self.cols);
         }
         self
     }
+
+    /// Set custom colours
+    ///
+    /// Usually one would start from a standard scheme, e.g.
+    /// `ThemeColours::open("dark")`, and adjust a few colours.
+    pub fn with_custom_colours(mut self, cols: ThemeColours) -> Self {
+        self.cols = cols;
+        self.palette.apply(&mut self.cols);
+        self
+    }
+
+    /// Get the dimension parameters used by this theme
+    ///
+    /// This may be used as a starting point for [`Self::with_dimensions`].
+    pub fn dimensions(&self) -> &DimensionsParams {
+        &self.dims
+    }
+
+    /// Set custom dimensions (margins, frame sizes, etc.)
+    pub fn with_dimensions(mut self, dims: DimensionsParams) -> Self {
+        self.dims = dims;
+        self
+    }
 }
 
 const DIMS: DimensionsParams = DimensionsParams {
@@ -62,6 +103,7 @@ const DIMS: DimensionsParams = DimensionsParams {
     scrollbar_size: Vec2::splat(8.0),
     slider_size: Vec2(12.0, 25.0),
     progress_bar: Vec2::splat(12.0),
+    feather: 1.0,
 };
 
 pub struct DrawHandle<'a, D: Draw> {
@@ -91,11 +133,19 @@ where
     }
 
     fn new_window(&self, _draw: &mut D::Draw, dpi_factor: f32) -> Self::Window {
-        DimensionsWindow::new(DIMS, self.pt_size, dpi_factor)
+        DimensionsWindow::new(
+            self.dims.clone(),
+            self.pt_size * self.font_scale,
+            dpi_factor,
+        )
     }
 
     fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
-        window.dims = Dimensions::new(DIMS, self.pt_size, dpi_factor);
+        window.dims = Dimensions::new(
+            self.dims.clone(),
+            self.pt_size * self.font_scale,
+            dpi_factor,
+        );
     }
 
     #[cfg(not(feature = "gat"))]
@@ -106,6 +156,7 @@ where
         rect: Rect,
     ) -> Self::DrawHandle {
         draw.prepare_fonts();
+        draw.set_feather(window.dims.feather);
 
         // We extend lifetimes (unsafe) due to the lack of associated type generics.
         use std::mem::transmute;
@@ -126,6 +177,7 @@ where
         rect: Rect,
     ) -> Self::DrawHandle<'a> {
         draw.prepare_fonts();
+        draw.set_feather(window.dims.feather);
 
         DrawHandle {
             draw,
@@ -148,9 +200,23 @@ impl ThemeApi for ShadedTheme {
         ThemeAction::ThemeResize
     }
 
+    fn set_font_scale(&mut self, scale: f32) -> ThemeAction {
+        self.font_scale = scale;
+        ThemeAction::ThemeResize
+    }
+
     fn set_colours(&mut self, scheme: &str) -> ThemeAction {
         if let Some(scheme) = ThemeColours::open(scheme) {
             self.cols = scheme;
+            self.palette.apply(&mut self.cols);
+            ThemeAction::RedrawAll
+        } else {
+            ThemeAction::None
+        }
+    }
+
+    fn set_palette_colour(&mut self, name: &str, col: Colour) -> ThemeAction {
+        if self.palette.set(&mut self.cols, name, col) {
             ThemeAction::RedrawAll
         } else {
             ThemeAction::None
@@ -239,7 +305,7 @@ where
     ) {
         let rect = rect + self.offset;
         let depth = self.pass.depth() + super::relative_region_depth(class);
-        let pass = self.draw.add_clip_region(rect, depth);
+        let pass = self.draw.push_clip_region(rect, depth);
         if depth < self.pass.depth() {
             // draw to depth buffer to enable correct text rendering
             self.draw
@@ -254,6 +320,7 @@ where
             pass,
         };
         f(&mut handle);
+        self.draw.pop_clip_region();
     }
 
     fn target_rect(&self) -> Rect {
@@ -261,6 +328,41 @@ where
         self.rect - self.offset
     }
 
+    fn quad(&mut self, quad: Quad, col: Colour) {
+        let quad = quad + Vec2::from(self.offset);
+        self.draw.rect(self.pass, quad, col);
+    }
+
+    fn frame(&mut self, outer: Quad, inner: Quad, col: Colour) {
+        let offset = Vec2::from(self.offset);
+        self.draw
+            .frame(self.pass, outer + offset, inner + offset, col);
+    }
+
+    fn line(&mut self, p1: Vec2, p2: Vec2, width: f32, col: Colour) {
+        let offset = Vec2::from(self.offset);
+        self.draw
+            .rounded_line(self.pass, p1 + offset, p2 + offset, 0.5 * width, col);
+    }
+
+    fn circle(&mut self, quad: Quad, inner_radius: f32, col: Colour) {
+        let quad = quad + Vec2::from(self.offset);
+        self.draw.circle(self.pass, quad, inner_radius, col);
+    }
+
+    fn text_col(&mut self, pos: Coord, text: &dyn TextApi, col: Colour) {
+        let pos = pos + self.offset;
+        let bounds = text.env().bounds.into();
+        self.draw.text(
+            self.pass,
+            pos.into(),
+            bounds,
+            Vec2::ZERO,
+            text.display(),
+            col,
+        );
+    }
+
     fn outer_frame(&mut self, rect: Rect) {
         let outer = Quad::from(rect + self.offset);
         let inner = outer.shrink(self.window.dims.frame as f32);
@@ -308,6 +410,18 @@ where
         self.as_flat().text_effects(pos, offset, text, class);
     }
 
+    fn text_col_spans(
+        &mut self,
+        pos: Coord,
+        offset: Offset,
+        text: &dyn TextApi,
+        effects: &[Effect<Option<Colour>>],
+        class: TextClass,
+    ) {
+        self.as_flat()
+            .text_col_spans(pos, offset, text, effects, class);
+    }
+
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         self.as_flat().text_accel(pos, text, state, class);
     }
@@ -433,4 +547,11 @@ where
         self.draw
             .shaded_round_frame(self.pass, outer, inner, (0.0, 0.6), col);
     }
+
+    fn spinner(&mut self, rect: Rect, _: InputState, phase: f32) {
+        let outer = Quad::from(rect + self.offset);
+        let min_thickness = self.window.dims.frame as f32;
+        let cols = (self.cols.frame, self.cols.button);
+        super::draw_spinner(self.draw, self.pass, outer, min_thickness, phase, cols);
+    }
 }
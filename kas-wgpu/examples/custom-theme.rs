@@ -100,9 +100,17 @@ impl ThemeApi for CustomTheme {
         ThemeApi::set_font_size(&mut self.inner, size)
     }
 
+    fn set_font_scale(&mut self, scale: f32) -> ThemeAction {
+        ThemeApi::set_font_scale(&mut self.inner, scale)
+    }
+
     fn set_colours(&mut self, scheme: &str) -> ThemeAction {
         ThemeApi::set_colours(&mut self.inner, scheme)
     }
+
+    fn set_palette_colour(&mut self, name: &str, col: Colour) -> ThemeAction {
+        ThemeApi::set_palette_colour(&mut self.inner, name, col)
+    }
 }
 
 #[derive(Clone, Debug, VoidMsg)]
@@ -24,8 +24,9 @@ pub trait CustomPipeBuilder {
 
     /// Build a pipe
     ///
-    /// The given texture format and depth format should be used to construct a
-    /// compatible [`wgpu::RenderPipeline`].
+    /// The given texture format and depth format (always
+    /// [`super::DEPTH_FORMAT`]) should be used to construct a compatible
+    /// [`wgpu::RenderPipeline`].
     fn build(
         &mut self,
         device: &wgpu::Device,
@@ -46,6 +47,47 @@ pub trait CustomPipeBuilder {
 /// Note that `kas-wgpu` accepts only a single custom pipe. To use more than
 /// one, you will have to implement your own multiplexer (presumably using an
 /// enum for the `Param` type).
+///
+/// # Frame lifecycle
+///
+/// Each frame of each window is drawn as follows:
+///
+/// 1.  Widgets are drawn, calling [`CustomWindow::invoke`] via
+///     [`DrawCustom::custom`] to batch custom draw data
+/// 2.  [`CustomPipe::prepare`] is called: data may be uploaded via the queue
+/// 3.  A command encoder is created and [`CustomPipe::update`] called
+/// 4.  For each clip region, a render pass is started and
+///     [`CustomPipe::render_pass`] called
+/// 5.  [`CustomPipe::render_final`] is called, followed by text rendering
+/// 6.  Commands are submitted to the queue
+///
+/// Data uploaded in steps 2 or 3 is therefore available to all render steps.
+/// Application code wishing to upload data outside of this cycle (e.g. from
+/// another thread) may use the device and queue returned by
+/// [`crate::Toolkit::device`] and [`crate::Toolkit::queue`].
+///
+/// # Depth buffer
+///
+/// Each window has a single depth buffer of format [`super::DEPTH_FORMAT`],
+/// shared by all pipes. It is bound to the render pass given to
+/// [`CustomPipe::render_pass`] and passed to [`CustomPipe::render_final`].
+///
+/// Depth does not represent distance from the viewer; instead each
+/// [`Pass`] has a depth value ([`Pass::depth`]) which increases for regions
+/// drawn over others (e.g. pop-ups). The buffer is cleared to the depth of
+/// the window's first pass and KAS geometry is drawn with
+/// `depth_compare: wgpu::CompareFunction::Always`, writing the depth of its
+/// pass, thus afterwards each pixel holds the depth of the top-most region
+/// covering it. Text is drawn last with `CompareFunction::GreaterEqual`, and
+/// thus is hidden where a higher region (such as a pop-up) covers it.
+///
+/// Custom content should write `pass.depth()` (the value of the [`Pass`]
+/// given to [`DrawCustom::custom`]) as its depth. 3D content may use depth
+/// values in a small range above this, provided these remain below the depth
+/// of any region drawn over it. Within [`CustomPipe::render_pass`] a scissor
+/// rect is applied, so `Always` is sufficient; in
+/// [`CustomPipe::render_final`] use `GreaterEqual` to avoid drawing over
+/// pop-ups and outside of scroll regions.
 pub trait CustomPipe {
     /// Associated per-window state for the custom pipe
     type Window: CustomWindow + 'static;
@@ -62,6 +104,16 @@ pub trait CustomPipe {
         size: Size,
     );
 
+    /// Per-frame preparation
+    ///
+    /// This is called once per frame, before [`CustomPipe::update`], and may
+    /// be used to write buffers and textures directly via `queue` (e.g. to
+    /// stream a video frame).
+    ///
+    /// This method is optional; by default it does nothing.
+    #[allow(unused)]
+    fn prepare(&self, window: &mut Self::Window, device: &wgpu::Device, queue: &wgpu::Queue) {}
+
     /// Per-frame updates
     ///
     /// This is called once per frame before rendering operations, and may for
@@ -106,9 +158,11 @@ pub trait CustomPipe {
     /// rendering. Depending on the application, it may make more sense to draw
     /// in [`CustomPipe::render_pass`] or in this method.
     ///
-    /// A depth buffer is available and may be used with
+    /// The window's depth buffer is available via
+    /// `depth_stencil_attachment.attachment` and may be used with
     /// `depth_compare: wgpu::CompareFunction::GreaterEqual` to avoid drawing
-    /// over pop-up elements and outside of scroll regions.
+    /// over pop-up elements and outside of scroll regions (see
+    /// [depth buffer](CustomPipe#depth-buffer)).
     ///
     /// This method is optional; by default it does nothing.
     #[allow(unused)]
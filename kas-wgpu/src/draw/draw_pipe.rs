@@ -102,11 +102,13 @@ impl<C: CustomPipe> DrawPipe<C> {
         DrawWindow {
             depth: make_depth_texture(device, size),
             clip_regions: vec![rect],
+            clip_stack: vec![],
             shaded_square,
             shaded_round,
             flat_round,
             custom,
             glyph_brush,
+            preloaded_glyphs: Default::default(),
             dur_text: Default::default(),
         }
     }
@@ -132,14 +134,19 @@ impl<C: CustomPipe> DrawPipe<C> {
     }
 
     /// Render batched draw instructions via `rpass`
+    ///
+    /// The target is cleared to `clear_color` if given, otherwise drawing is
+    /// over existing contents.
     pub fn render(
         &mut self,
         window: &mut DrawWindow<C::Window>,
-        device: &mut wgpu::Device,
-        queue: &mut wgpu::Queue,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
         frame_view: &wgpu::TextureView,
-        clear_color: wgpu::Color,
+        clear_color: Option<wgpu::Color>,
     ) {
+        self.custom.prepare(&mut window.custom, device, queue);
+
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("render"),
         });
@@ -150,7 +157,10 @@ impl<C: CustomPipe> DrawPipe<C> {
             attachment: frame_view,
             resolve_target: None,
             ops: wgpu::Operations {
-                load: wgpu::LoadOp::Clear(clear_color),
+                load: match clear_color {
+                    Some(color) => wgpu::LoadOp::Clear(color),
+                    None => wgpu::LoadOp::Load,
+                },
                 store: true,
             },
         }];
@@ -184,6 +194,7 @@ impl<C: CustomPipe> DrawPipe<C> {
                     color_attachments: &color_attachments,
                     depth_stencil_attachment: Some(depth_stencil_attachment.clone()),
                 });
+                // Clip regions are in physical pixels, as required here
                 rpass.set_scissor_rect(
                     rect.pos.0.cast(),
                     rect.pos.1.cast(),
@@ -236,6 +247,8 @@ impl<C: CustomPipe> DrawPipe<C> {
 
         // Keep only first clip region (which is the entire window)
         window.clip_regions.truncate(1);
+        debug_assert!(window.clip_stack.is_empty(), "unbalanced push_clip_region");
+        window.clip_stack.clear();
 
         self.staging_belt.finish();
         queue.submit(std::iter::once(encoder.finish()));
@@ -271,6 +284,22 @@ impl<CW: CustomWindow + 'static> Draw for DrawWindow<CW> {
         Pass::new_pass_with_depth(pass, depth)
     }
 
+    fn push_clip_region(&mut self, rect: Rect, depth: f32) -> Pass {
+        let parent = self.clip_regions[self.clip_stack.last().cloned().unwrap_or(0)];
+        let rect = rect
+            .intersection(&parent)
+            .unwrap_or(Rect::new(rect.pos, Size::ZERO));
+        let index = self.clip_regions.len();
+        self.clip_regions.push(rect);
+        self.clip_stack.push(index);
+        Pass::new_pass_with_depth(index.cast(), depth)
+    }
+
+    fn pop_clip_region(&mut self) {
+        let popped = self.clip_stack.pop();
+        debug_assert!(popped.is_some(), "pop_clip_region: stack is empty");
+    }
+
     #[inline]
     fn rect(&mut self, pass: Pass, rect: Quad, col: Colour) {
         self.shaded_square.rect(pass, rect, col);
@@ -283,6 +312,11 @@ impl<CW: CustomWindow + 'static> Draw for DrawWindow<CW> {
 }
 
 impl<CW: CustomWindow + 'static> DrawRounded for DrawWindow<CW> {
+    fn set_feather(&mut self, feather: f32) {
+        self.flat_round.set_feather(feather);
+        self.shaded_round.set_feather(feather);
+    }
+
     #[inline]
     fn rounded_line(&mut self, pass: Pass, p1: Vec2, p2: Vec2, radius: f32, col: Colour) {
         self.flat_round.line(pass, p1, p2, radius, col);
@@ -5,7 +5,8 @@
 
 //! Text drawing API for `kas_wgpu`
 
-use wgpu_glyph::{ab_glyph, Extra, SectionGlyph};
+use wgpu_glyph::ab_glyph::{self, Font};
+use wgpu_glyph::{Extra, SectionGlyph};
 
 use super::{CustomWindow, DrawWindow};
 use kas::draw::{Colour, Draw, DrawText, Pass};
@@ -35,9 +36,61 @@ impl<CW: CustomWindow + 'static> DrawText for DrawWindow<CW> {
                 let id = self.glyph_brush.add_font(font);
                 assert_eq!(id.0, i);
             }
+            // Glyphs may now resolve to a different (fallback) font
+            self.preloaded_glyphs.clear();
         }
     }
 
+    fn preload_glyphs(&mut self, text: &str, scale: f32) -> bool {
+        self.prepare_fonts();
+
+        // Glyphs are positioned outside of an empty bounding rect: they are
+        // cached when the queue is processed but yield no visible output.
+        let position = ab_glyph::point(-1e6, -1e6);
+        let mut grew = false;
+        let mut glyphs = vec![];
+        for c in text.chars() {
+            let fonts = self.glyph_brush.fonts();
+            let found = fonts.iter().enumerate().find_map(|(i, font)| {
+                let id = font.glyph_id(c);
+                if id.0 != 0 {
+                    Some((i, id))
+                } else {
+                    None
+                }
+            });
+            let (font_id, id) = match found {
+                Some(found) => found,
+                None => continue,
+            };
+            if self
+                .preloaded_glyphs
+                .insert((font_id, id.0, scale.to_bits()))
+            {
+                grew = true;
+                glyphs.push(SectionGlyph {
+                    section_index: 0,
+                    byte_index: 0,
+                    glyph: id.with_scale_and_position(scale, position),
+                    font_id: wgpu_glyph::FontId(font_id),
+                });
+            }
+        }
+
+        if grew {
+            let bounds = ab_glyph::Rect {
+                min: position,
+                max: position,
+            };
+            let extra = vec![Extra {
+                color: [0.0; 4],
+                z: 0.0,
+            }];
+            self.glyph_brush.queue_pre_positioned(glyphs, extra, bounds);
+        }
+        grew
+    }
+
     fn text(
         &mut self,
         pass: Pass,
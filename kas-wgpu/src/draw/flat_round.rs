@@ -14,7 +14,7 @@ use kas::draw::{Colour, Pass};
 use kas::geom::{Quad, Size, Vec2, Vec3};
 
 /// Offset relative to the size of a pixel used by the fragment shader to
-/// implement multi-sampling.
+/// implement multi-sampling, for a feather width of one pixel.
 const OFFSET: f32 = 0.125;
 
 #[repr(C)]
@@ -41,6 +41,7 @@ pub struct Window {
     bind_group: wgpu::BindGroup,
     scale_buf: wgpu::Buffer,
     passes: Vec<Vec<Vertex>>,
+    offset: f32,
 }
 
 /// Buffer used during render pass
@@ -173,6 +174,7 @@ impl Pipeline {
             bind_group,
             scale_buf,
             passes: vec![],
+            offset: OFFSET,
         }
     }
 
@@ -204,6 +206,11 @@ impl Pipeline {
 }
 
 impl Window {
+    /// Set the anti-aliasing feather width, in pixels
+    pub fn set_feather(&mut self, feather: f32) {
+        self.offset = OFFSET * feather.max(0.0);
+    }
+
     pub fn resize(
         &mut self,
         device: &wgpu::Device,
@@ -241,7 +248,7 @@ impl Window {
         let na = -nb;
 
         // Since we take the mid-point, all offsets are uniform
-        let p = Vec2::splat(OFFSET / radius);
+        let p = Vec2::splat(self.offset / radius);
 
         let depth = pass.depth();
         let p1my = p1 - vy;
@@ -300,7 +307,7 @@ impl Window {
         let nba = Vec2(nb.0, na.1);
 
         // Since we take the mid-point, all offsets are uniform
-        let p = nb / (bb - mid) * OFFSET;
+        let p = nb / (bb - mid) * self.offset;
         let depth = pass.depth();
 
         let aa = Vertex::new2(aa, depth, col, inner, na, p);
@@ -365,10 +372,10 @@ impl Window {
         let n0a = Vec2(0.0, na.1);
         let n0b = Vec2(0.0, nb.1);
 
-        let paa = na / (aa - cc) * OFFSET;
-        let pab = nab / (ab - cd) * OFFSET;
-        let pba = nba / (ba - dc) * OFFSET;
-        let pbb = nb / (bb - dd) * OFFSET;
+        let paa = na / (aa - cc) * self.offset;
+        let pab = nab / (ab - cd) * self.offset;
+        let pba = nba / (ba - dc) * self.offset;
+        let pbb = nb / (bb - dd) * self.offset;
         let depth = pass.depth();
 
         // We must add corners separately to ensure correct interpolation of dir
@@ -16,6 +16,7 @@ mod shaded_square;
 mod shaders;
 
 use kas::geom::Rect;
+use std::collections::HashSet;
 use wgpu::{CompareFunction, DepthStencilState, TextureFormat};
 use wgpu_glyph::ab_glyph::FontRef;
 
@@ -23,8 +24,15 @@ pub(crate) use shaders::ShaderManager;
 
 pub use custom::{CustomPipe, CustomPipeBuilder, CustomWindow, DrawCustom};
 
-const DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
-pub(crate) const TEX_FORMAT: TextureFormat = TextureFormat::Bgra8UnormSrgb;
+/// Format of the depth buffer
+///
+/// Custom pipes drawing to a window share its depth buffer; see
+/// [`CustomPipe`](CustomPipe#depth-buffer).
+pub const DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+/// Format of render targets
+///
+/// Targets passed to [`crate::Embedded::render`] must use this format.
+pub const TEX_FORMAT: TextureFormat = TextureFormat::Bgra8UnormSrgb;
 
 const fn new_depth_desc(depth_compare: CompareFunction) -> DepthStencilState {
     DepthStencilState {
@@ -83,10 +91,13 @@ type GlyphBrush = wgpu_glyph::GlyphBrush<DepthStencilState, FontRef<'static>>;
 pub struct DrawWindow<CW: CustomWindow> {
     depth: Option<wgpu::TextureView>,
     clip_regions: Vec<Rect>,
+    clip_stack: Vec<usize>,
     shaded_square: shaded_square::Window,
     shaded_round: shaded_round::Window,
     flat_round: flat_round::Window,
     custom: CW,
     glyph_brush: GlyphBrush, // TODO: should be in DrawPipe
+    // Glyphs queued by preload_glyphs: (font, glyph, scale bits)
+    pub(crate) preloaded_glyphs: HashSet<(usize, u16, u32)>,
     pub(crate) dur_text: std::time::Duration,
 }
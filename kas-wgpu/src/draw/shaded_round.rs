@@ -15,7 +15,7 @@ use kas::draw::{Colour, Pass};
 use kas::geom::{Quad, Size, Vec2, Vec3};
 
 /// Offset relative to the size of a pixel used by the fragment shader to
-/// implement multi-sampling.
+/// implement multi-sampling, for a feather width of one pixel.
 const OFFSET: f32 = 0.125;
 
 #[repr(C)]
@@ -42,6 +42,7 @@ pub struct Window {
     bind_group: wgpu::BindGroup,
     scale_buf: wgpu::Buffer,
     passes: Vec<Vec<Vertex>>,
+    offset: f32,
 }
 
 /// Buffer used during render pass
@@ -201,6 +202,7 @@ impl Pipeline {
             bind_group,
             scale_buf,
             passes: vec![],
+            offset: OFFSET,
         }
     }
 
@@ -232,6 +234,11 @@ impl Pipeline {
 }
 
 impl Window {
+    /// Set the anti-aliasing feather width, in pixels
+    pub fn set_feather(&mut self, feather: f32) {
+        self.offset = OFFSET * feather.max(0.0);
+    }
+
     pub fn resize(
         &mut self,
         device: &wgpu::Device,
@@ -277,7 +284,7 @@ impl Window {
         let nba = Vec2(nbb.0, naa.1);
 
         // Since we take the mid-point, all offsets are uniform
-        let p = nbb / (bb - mid) * OFFSET;
+        let p = nbb / (bb - mid) * self.offset;
         let depth = pass.depth();
 
         let aa = Vertex::new2(aa, depth, col, naa, adjust, p);
@@ -344,10 +351,10 @@ impl Window {
         let n0a = Vec2(0.0, naa.1);
         let n0b = Vec2(0.0, nbb.1);
 
-        let paa = naa / (aa - cc) * OFFSET;
-        let pab = nab / (ab - cd) * OFFSET;
-        let pba = nba / (ba - dc) * OFFSET;
-        let pbb = nbb / (bb - dd) * OFFSET;
+        let paa = naa / (aa - cc) * self.offset;
+        let pab = nab / (ab - cd) * self.offset;
+        let pba = nba / (ba - dc) * self.offset;
+        let pbb = nbb / (bb - dd) * self.offset;
         let depth = pass.depth();
 
         // We must add corners separately to ensure correct interpolation of dir
@@ -0,0 +1,437 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Embedding in an existing wgpu application
+
+use log::{debug, warn};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use kas::draw::SizeHandle;
+use kas::event::{Config, CursorIcon, ManagerState, UpdateHandle, UpdateQueue};
+use kas::geom::{Coord, Rect, Size};
+use kas::layout::SolveCache;
+use kas::{ThemeAction, ThemeApi, TkAction, WindowId};
+use kas_theme::Theme;
+use winit::event::WindowEvent;
+
+use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe, DrawWindow};
+use crate::shared::{PendingAction, SharedState};
+use crate::window::to_wgpu_color;
+
+macro_rules! shell {
+    ($self:ident) => {
+        EmbedShell {
+            shared: &mut $self.shared,
+            theme_window: &mut $self.theme_window,
+            cursor_icon: &mut $self.cursor_icon,
+            popups: &mut $self.popups,
+        }
+    };
+}
+
+/// A KAS window rendered to a user-supplied texture
+///
+/// This allows a KAS UI to be composited over (or into) the output of an
+/// application which owns its own `wgpu` device, surfaces and event loop.
+/// The device and queue are shared with the caller, who is responsible for:
+///
+/// -   calling [`Embedded::resize`] with the target size, initially and
+///     whenever this changes
+/// -   forwarding window events to [`Embedded::handle_event`], then calling
+///     [`Embedded::update`] after each batch of events
+/// -   calling [`Embedded::update_timer`] once [`Embedded::next_resume`] is
+///     reached
+/// -   calling [`Embedded::render`] with a view of the target texture, whose
+///     format must be [`TEX_FORMAT`](crate::draw::TEX_FORMAT)
+/// -   applying [`Embedded::cursor_icon`] to the host window, if desired
+///
+/// Pop-ups (e.g. menus) are supported. Requests to open new windows are
+/// ignored.
+pub struct Embedded<C: CustomPipe, T: Theme<DrawPipe<C>>>
+where
+    T::Window: kas_theme::Window,
+{
+    shared: SharedState<C, T>,
+    window_id: WindowId,
+    widget: Box<dyn kas::Window>,
+    mgr: ManagerState,
+    solve_cache: SolveCache,
+    draw: DrawWindow<C::Window>,
+    theme_window: T::Window,
+    size: Size,
+    scale_factor: f64,
+    cursor_icon: CursorIcon,
+    popups: Vec<(WindowId, kas::Popup)>,
+    redraw: bool,
+}
+
+impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Embedded<C, T>
+where
+    T::Window: kas_theme::Window,
+{
+    /// Construct over an existing `device` and `queue`
+    ///
+    /// The `custom` parameter accepts a custom draw pipe (see
+    /// [`CustomPipeBuilder`]); pass `()` if you don't have one.
+    ///
+    /// Nothing is drawn until [`Embedded::resize`] is called.
+    pub fn new<CB: CustomPipeBuilder<Pipe = C>>(
+        custom: CB,
+        theme: T,
+        config: Rc<RefCell<Config>>,
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        mut widget: Box<dyn kas::Window>,
+    ) -> Self {
+        use kas_theme::Window;
+
+        let mut shared = SharedState::with_device(custom, theme, config, 1.0, device, queue);
+        let window_id = shared.next_window_id();
+
+        let scale_factor = shared.theme_scale_factor(shared.scale_factor);
+        let mut draw = shared.draw.new_window(&shared.device, Size::ZERO);
+        let mut theme_window = shared.theme.new_window(&mut draw, scale_factor);
+
+        let mut size_handle = unsafe { theme_window.size_handle() };
+        let solve_cache = SolveCache::find_constraints(widget.as_widget_mut(), &mut size_handle);
+        drop(size_handle);
+
+        let mgr = ManagerState::new(shared.config.clone());
+
+        let mut r = Embedded {
+            shared,
+            window_id,
+            widget,
+            mgr,
+            solve_cache,
+            draw,
+            theme_window,
+            size: Size::ZERO,
+            scale_factor: 1.0,
+            cursor_icon: CursorIcon::Default,
+            popups: vec![],
+            redraw: true,
+        };
+        let mut shell = shell!(r);
+        r.mgr.configure(&mut shell, &mut *r.widget);
+        r
+    }
+
+    /// Access the widget
+    #[inline]
+    pub fn widget(&self) -> &dyn kas::Window {
+        &*self.widget
+    }
+
+    /// The cursor icon requested by the UI
+    #[inline]
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+
+    /// True when the UI should be redrawn
+    ///
+    /// This is always true while widgets request updates on each frame (see
+    /// [`kas::event::Manager::update_on_frame`]).
+    #[inline]
+    pub fn needs_redraw(&self) -> bool {
+        self.redraw || self.mgr.continuous_redraw(&*self.widget)
+    }
+
+    /// Ideal size of the UI, in physical pixels
+    #[inline]
+    pub fn ideal_size(&self) -> Size {
+        self.solve_cache.ideal(true)
+    }
+
+    /// Set the size of the render target, in physical pixels
+    pub fn resize(&mut self, size: Size) {
+        if size == self.size {
+            return;
+        }
+        self.size = size;
+        if size.0 == 0 || size.1 == 0 {
+            return;
+        }
+
+        let buf = self
+            .shared
+            .draw
+            .resize(&mut self.draw, &self.shared.device, size);
+        self.shared.queue.submit(std::iter::once(buf));
+        self.apply_size();
+    }
+
+    /// Set the scale factor (ratio of physical pixels to logical pixels)
+    ///
+    /// This is also updated by [`Embedded::handle_event`].
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.shared.scale_factor = scale_factor;
+        self.theme_resize();
+    }
+
+    /// Handle an input event
+    ///
+    /// Coordinates are taken to be relative to the render target.
+    /// `Resized` events are ignored: call [`Embedded::resize`] instead.
+    pub fn handle_event(&mut self, event: WindowEvent) {
+        match event {
+            WindowEvent::Resized(_) | WindowEvent::Destroyed => (),
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.set_scale_factor(scale_factor);
+            }
+            event => {
+                let mut shell = shell!(self);
+                let widget = &mut *self.widget;
+                self.mgr.with(&mut shell, |mgr| {
+                    mgr.handle_winit(widget, event);
+                });
+            }
+        }
+    }
+
+    /// Update, after handling a batch of events
+    ///
+    /// The returned action may be checked for [`TkAction::CLOSE`] and
+    /// [`TkAction::EXIT`]; other actions are handled internally.
+    pub fn update(&mut self) -> TkAction {
+        let mut shell = shell!(self);
+        let action = self.mgr.update(&mut shell, &mut *self.widget);
+        self.handle_pending();
+
+        if action.intersects(TkAction::CLOSE | TkAction::EXIT) {
+            return action;
+        }
+        if action.contains(TkAction::RECONFIGURE) {
+            let mut shell = shell!(self);
+            self.mgr.configure(&mut shell, &mut *self.widget);
+            self.solve_cache.invalidate_rule_cache();
+            self.apply_size();
+        } else if action.contains(TkAction::RESIZE) {
+            self.solve_cache.invalidate_rule_cache();
+            self.apply_size();
+        } else if action.contains(TkAction::SET_SIZE) {
+            self.apply_size();
+        }
+        if action.contains(TkAction::REGION_MOVED) {
+            let mut shell = shell!(self);
+            self.mgr.region_moved(&mut shell, &mut *self.widget);
+            self.redraw = true;
+        } else if action.contains(TkAction::REDRAW) {
+            self.redraw = true;
+        }
+        action
+    }
+
+    /// Time at which [`Embedded::update_timer`] should next be called
+    #[inline]
+    pub fn next_resume(&self) -> Option<Instant> {
+        self.mgr.next_resume()
+    }
+
+    /// Handle timer updates
+    ///
+    /// Call [`Embedded::update`] afterwards.
+    pub fn update_timer(&mut self) {
+        let mut shell = shell!(self);
+        let widget = &mut *self.widget;
+        self.mgr.with(&mut shell, |mgr| {
+            mgr.update_timer(widget);
+        });
+    }
+
+    /// Draw the UI to `view`
+    ///
+    /// The UI is drawn over the existing contents of `view`, which must have
+    /// the size last passed to [`Embedded::resize`].
+    pub fn render(&mut self, view: &wgpu::TextureView) {
+        self.render_impl(view, false);
+    }
+}
+
+// Internal functions
+impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Embedded<C, T>
+where
+    T::Window: kas_theme::Window,
+{
+    /// Draw the UI to `view`, optionally clearing with the theme's colour
+    pub(crate) fn render_impl(&mut self, view: &wgpu::TextureView, clear: bool) {
+        if self.size.0 == 0 || self.size.1 == 0 {
+            return;
+        }
+
+        self.mgr.begin_frame();
+        if self.mgr.continuous_redraw(&*self.widget) {
+            let mut shell = shell!(self);
+            let widget = &mut *self.widget;
+            self.mgr.with(&mut shell, |mgr| mgr.update_frame(widget));
+        }
+        let rect = Rect::new(Coord::ZERO, self.size);
+        unsafe {
+            // Safety: we must drop draw_handle after draw call (wrong lifetime)
+            let mut draw_handle =
+                self.shared
+                    .theme
+                    .draw_handle(&mut self.draw, &mut self.theme_window, rect);
+            self.widget.draw(&mut draw_handle, &self.mgr, false);
+        }
+
+        let clear_color = if clear {
+            Some(to_wgpu_color(self.shared.theme.clear_color()))
+        } else {
+            None
+        };
+        self.shared.render(&mut self.draw, view, clear_color);
+        self.draw.dur_text = Default::default();
+        self.redraw = false;
+    }
+
+    fn theme_resize(&mut self) {
+        debug!("Embedded::theme_resize");
+        let scale_factor = self.shared.theme_scale_factor(self.scale_factor);
+        self.shared
+            .theme
+            .update_window(&mut self.theme_window, scale_factor);
+        self.solve_cache.invalidate_rule_cache();
+        self.apply_size();
+    }
+
+    fn apply_size(&mut self) {
+        if self.size.0 == 0 || self.size.1 == 0 {
+            return;
+        }
+
+        let rect = Rect::new(Coord::ZERO, self.size);
+        let mut shell = shell!(self);
+        let solve_cache = &mut self.solve_cache;
+        let widget = &mut self.widget;
+        self.mgr.with(&mut shell, |mgr| {
+            solve_cache.apply_rect(widget.as_widget_mut(), mgr, rect, true);
+            widget.resize_popups(mgr);
+        });
+        self.redraw = true;
+    }
+
+    fn handle_pending(&mut self) {
+        for (id, popup) in std::mem::take(&mut self.popups) {
+            let mut shell = shell!(self);
+            let widget = &mut *self.widget;
+            self.mgr.with(&mut shell, |mut mgr| {
+                kas::Window::add_popup(widget, &mut mgr, id, popup);
+            });
+        }
+
+        let mut updates = UpdateQueue::new();
+        loop {
+            while let Some(pending) = self.shared.pending.pop_front() {
+                match pending {
+                    PendingAction::CloseWindow(id) if id == self.window_id => {
+                        self.mgr.send_action(TkAction::CLOSE);
+                    }
+                    PendingAction::CloseWindow(id) => {
+                        let mut shell = shell!(self);
+                        let widget = &mut *self.widget;
+                        self.mgr.with(&mut shell, |mut mgr| {
+                            widget.remove_popup(&mut mgr, id);
+                        });
+                    }
+                    PendingAction::ThemeResize => self.theme_resize(),
+                    PendingAction::RedrawAll => self.redraw = true,
+                    PendingAction::Update(handle, payload) => {
+                        updates.push(handle, payload);
+                    }
+                    // Not generated by EmbedShell
+                    PendingAction::AddPopup(..)
+                    | PendingAction::AddWindow(..)
+                    | PendingAction::Message(..) => (),
+                }
+            }
+
+            // Dispatch updates once per handle. This may queue further actions.
+            if updates.is_empty() {
+                break;
+            }
+            let mut shell = shell!(self);
+            let widget = &mut *self.widget;
+            self.mgr.with(&mut shell, |mgr| {
+                mgr.update_handles(widget, &updates);
+            });
+            updates = UpdateQueue::new();
+        }
+    }
+}
+
+struct EmbedShell<'a, C: CustomPipe, T: Theme<DrawPipe<C>>>
+where
+    T::Window: kas_theme::Window,
+{
+    shared: &'a mut SharedState<C, T>,
+    theme_window: &'a mut T::Window,
+    cursor_icon: &'a mut CursorIcon,
+    popups: &'a mut Vec<(WindowId, kas::Popup)>,
+}
+
+impl<'a, C, T> kas::ShellWindow for EmbedShell<'a, C, T>
+where
+    C: CustomPipe,
+    T: Theme<DrawPipe<C>>,
+    T::Window: kas_theme::Window,
+{
+    fn add_popup(&mut self, popup: kas::Popup) -> WindowId {
+        let id = self.shared.next_window_id();
+        self.popups.push((id, popup));
+        id
+    }
+
+    fn add_window(&mut self, _: Box<dyn kas::Window>) -> WindowId {
+        warn!("Embedded: unable to add a window; ignoring");
+        self.shared.next_window_id()
+    }
+
+    fn close_window(&mut self, id: WindowId) {
+        self.shared
+            .pending
+            .push_back(PendingAction::CloseWindow(id));
+    }
+
+    fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
+        self.shared
+            .pending
+            .push_back(PendingAction::Update(handle, payload));
+    }
+
+    #[inline]
+    fn get_clipboard(&mut self) -> Option<String> {
+        self.shared.get_clipboard()
+    }
+
+    #[inline]
+    fn set_clipboard<'c>(&mut self, content: std::borrow::Cow<'c, str>) {
+        self.shared.set_clipboard(content);
+    }
+
+    fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {
+        match f(&mut self.shared.theme) {
+            ThemeAction::None => (),
+            ThemeAction::RedrawAll => self.shared.pending.push_back(PendingAction::RedrawAll),
+            ThemeAction::ThemeResize => self.shared.pending.push_back(PendingAction::ThemeResize),
+        }
+    }
+
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        use kas_theme::Window;
+        let mut size_handle = unsafe { self.theme_window.size_handle() };
+        f(&mut size_handle);
+    }
+
+    #[inline]
+    fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        *self.cursor_icon = icon;
+    }
+}
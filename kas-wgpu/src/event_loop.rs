@@ -14,6 +14,7 @@ use winit::event::{Event, StartCause};
 use winit::event_loop::{ControlFlow, EventLoopWindowTarget};
 use winit::window as ww;
 
+use kas::event::UpdateQueue;
 use kas::TkAction;
 use kas_theme::Theme;
 
@@ -71,7 +72,17 @@ where
                 }
             }
 
-            DeviceEvent { .. } => return, // windows handle local input; we do not handle global input
+            DeviceEvent {
+                event: winit::event::DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                // Windows handle local input; this is only used to continue
+                // mouse grabs outside the window
+                for window in self.windows.values_mut() {
+                    window.handle_mouse_motion(&mut self.shared, delta);
+                }
+            }
+            DeviceEvent { .. } => return, // we do not handle other global input
             UserEvent(action) => match action {
                 ProxyAction::Close(id) => {
                     if let Some(id) = self.id_map.get(&id) {
@@ -80,6 +91,12 @@ where
                         }
                     }
                 }
+                ProxyAction::AddWindow(id, f) => {
+                    let widget = f();
+                    self.shared
+                        .pending
+                        .push_back(PendingAction::AddWindow(id, widget));
+                }
                 ProxyAction::CloseAll => {
                     for window in self.windows.values_mut() {
                         window.send_action(TkAction::CLOSE);
@@ -88,7 +105,18 @@ where
                 ProxyAction::Update(handle, payload) => {
                     self.shared
                         .pending
-                        .push(PendingAction::Update(handle, payload));
+                        .push_back(PendingAction::Update(handle, payload));
+                }
+                ProxyAction::SendTo(id, msg) => {
+                    self.shared
+                        .pending
+                        .push_back(PendingAction::Message(id, msg));
+                }
+                ProxyAction::Run(f) => {
+                    let window = self.windows.values_mut().min_by_key(|w| w.window_id);
+                    if let Some(window) = window {
+                        window.run_closure(&mut self.shared, f);
+                    }
                 }
             },
 
@@ -102,31 +130,32 @@ where
                     } => {
                         debug!("Wakeup: timer (requested: {:?})", requested_resume);
 
-                        let item = self
-                            .resumes
-                            .first()
-                            .cloned()
-                            .unwrap_or_else(|| panic!("timer wakeup without resume"));
-                        assert_eq!(item.0, requested_resume);
-
-                        let resume = if let Some(w) = self.windows.get_mut(&item.1) {
-                            w.update_timer(&mut self.shared)
-                        } else {
-                            // presumably, some window with active timers was removed
-                            None
-                        };
+                        // Otherwise this is the next frame of a continuously
+                        // redrawn window, which is handled by MainEventsCleared
+                        let item = self.resumes.first().cloned();
+                        if let Some(item) = item.filter(|item| item.0 == requested_resume) {
+                            let resume = if let Some(w) = self.windows.get_mut(&item.1) {
+                                w.update_timer(&mut self.shared)
+                            } else {
+                                // presumably, some window with active timers was removed
+                                None
+                            };
 
-                        if let Some(instant) = resume {
-                            self.resumes[0].0 = instant;
-                        } else {
-                            self.resumes.remove(0);
+                            if let Some(instant) = resume {
+                                self.resumes[0].0 = instant;
+                            } else {
+                                self.resumes.remove(0);
+                            }
                         }
                     }
                     StartCause::WaitCancelled { .. } => {
                         // This event serves no purpose?
                         // debug!("Wakeup: WaitCancelled (ignoring)");
                     }
-                    StartCause::Poll => (),
+                    StartCause::Poll => {
+                        // Resume times are not reported while polling
+                        self.update_timers(Instant::now());
+                    }
                     StartCause::Init => {
                         debug!("Wakeup: init");
                     }
@@ -136,8 +165,12 @@ where
             MainEventsCleared => {
                 let mut close_all = false;
                 let mut to_close = SmallVec::<[ww::WindowId; 4]>::new();
+                let mut next_frame: Option<Instant> = None;
                 for (window_id, window) in self.windows.iter_mut() {
                     let (action, resume) = window.update(&mut self.shared);
+                    if let Some(time) = window.next_frame() {
+                        next_frame = Some(next_frame.map(|t| t.min(time)).unwrap_or(time));
+                    }
                     if action.contains(TkAction::EXIT) {
                         close_all = true;
                     } else if action.contains(TkAction::CLOSE) {
@@ -182,10 +215,12 @@ where
                     ControlFlow::Exit
                 } else if *control_flow == ControlFlow::Poll {
                     ControlFlow::Poll
-                } else if let Some((instant, _)) = self.resumes.first() {
-                    ControlFlow::WaitUntil(*instant)
                 } else {
-                    ControlFlow::Wait
+                    let resume = self.resumes.first().map(|item| item.0);
+                    match resume.into_iter().chain(next_frame).min() {
+                        Some(instant) => ControlFlow::WaitUntil(instant),
+                        None => ControlFlow::Wait,
+                    }
                 };
             }
 
@@ -198,56 +233,110 @@ where
             RedrawEventsCleared | LoopDestroyed | Suspended | Resumed => return,
         };
 
-        // Create and init() any new windows.
-        while let Some(pending) = self.shared.pending.pop() {
-            match pending {
-                PendingAction::AddPopup(parent_id, id, popup) => {
-                    debug!("Adding overlay");
-                    // TODO: support pop-ups as a special window, where available
-                    self.windows.get_mut(&parent_id).unwrap().add_popup(
-                        &mut self.shared,
-                        id,
-                        popup,
-                    );
-                    self.id_map.insert(id, parent_id);
-                }
-                PendingAction::AddWindow(id, widget) => {
-                    debug!("Adding window {}", widget.title());
-                    match Window::new(&mut self.shared, elwt, id, widget) {
-                        Ok(window) => {
-                            let wid = window.window.id();
-                            self.id_map.insert(id, wid);
-                            self.windows.insert(wid, window);
-                        }
-                        Err(e) => {
-                            error!("Unable to create window: {}", e);
+        let mut updates = UpdateQueue::new();
+        loop {
+            // Create and init() any new windows.
+            while let Some(pending) = self.shared.pending.pop_front() {
+                match pending {
+                    PendingAction::AddPopup(parent_id, id, popup) => {
+                        debug!("Adding overlay");
+                        // TODO: support pop-ups as a special window, where available
+                        self.windows.get_mut(&parent_id).unwrap().add_popup(
+                            &mut self.shared,
+                            id,
+                            popup,
+                        );
+                        self.id_map.insert(id, parent_id);
+                    }
+                    PendingAction::AddWindow(id, widget) => {
+                        debug!("Adding window {}", widget.title());
+                        match Window::new(&mut self.shared, elwt, id, widget) {
+                            Ok(window) => {
+                                let wid = window.window.id();
+                                self.id_map.insert(id, wid);
+                                self.windows.insert(wid, window);
+                            }
+                            Err(e) => {
+                                error!("Unable to create window: {}", e);
+                            }
+                        };
+                    }
+                    PendingAction::CloseWindow(id) => {
+                        if let Some(wwid) = self.id_map.get(&id) {
+                            if let Some(window) = self.windows.get_mut(&wwid) {
+                                window.send_close(&mut self.shared, id);
+                            }
+                            self.id_map.remove(&id);
                         }
-                    };
-                }
-                PendingAction::CloseWindow(id) => {
-                    if let Some(wwid) = self.id_map.get(&id) {
-                        if let Some(window) = self.windows.get_mut(&wwid) {
-                            window.send_close(&mut self.shared, id);
+                    }
+                    PendingAction::ThemeResize => {
+                        for (_, window) in self.windows.iter_mut() {
+                            window.theme_resize(&mut self.shared);
                         }
-                        self.id_map.remove(&id);
                     }
-                }
-                PendingAction::ThemeResize => {
-                    for (_, window) in self.windows.iter_mut() {
-                        window.theme_resize(&mut self.shared);
+                    PendingAction::RedrawAll => {
+                        for (_, window) in self.windows.iter_mut() {
+                            window.request_redraw();
+                        }
                     }
-                }
-                PendingAction::RedrawAll => {
-                    for (_, window) in self.windows.iter_mut() {
-                        window.window.request_redraw();
+                    PendingAction::Update(handle, payload) => {
+                        updates.push(handle, payload);
                     }
-                }
-                PendingAction::Update(handle, payload) => {
-                    for window in self.windows.values_mut() {
-                        window.update_handle(&mut self.shared, handle, payload);
+                    PendingAction::Message(id, msg) => {
+                        // Popups share their parent's id_map entry; deliver
+                        // only to the window itself
+                        if let Some(wwid) = self.id_map.get(&id) {
+                            if let Some(window) = self.windows.get_mut(wwid) {
+                                if window.window_id == id {
+                                    window.send_message(&mut self.shared, msg);
+                                }
+                            }
+                        }
                     }
                 }
             }
+
+            // Dispatch updates once per handle. This may queue further actions.
+            if updates.is_empty() {
+                break;
+            }
+            let queue = std::mem::take(&mut updates);
+            for window in self.windows.values_mut() {
+                window.update_handles(&mut self.shared, &queue);
+            }
+        }
+    }
+
+    /// Update timers for all windows with a resume time no later than `now`
+    ///
+    /// Used while polling, when the event loop does not report resume times.
+    fn update_timers(&mut self, now: Instant) {
+        // Each window is updated at most once, even if it requests a resume
+        // time which has already passed.
+        let due: SmallVec<[ww::WindowId; 4]> = self
+            .resumes
+            .iter()
+            .take_while(|item| item.0 <= now)
+            .map(|item| item.1)
+            .collect();
+
+        for window_id in due {
+            let resume = if let Some(w) = self.windows.get_mut(&window_id) {
+                w.update_timer(&mut self.shared)
+            } else {
+                // presumably, some window with active timers was removed
+                None
+            };
+
+            let i = self.resumes.iter().position(|item| item.1 == window_id);
+            let i = i.expect("missing resume");
+            if let Some(instant) = resume {
+                self.resumes[i].0 = instant;
+            } else {
+                self.resumes.remove(i);
+            }
         }
+
+        self.resumes.sort_by_key(|item| item.0);
     }
 }
@@ -11,6 +11,13 @@
 //! Windowing is provided by [winit].
 //! Clipboard functionality is (currently) provided by the [clipboard] crate.
 //!
+//! Usually the UI is run via a [`Toolkit`]. Alternatively, [`Embedded`] allows
+//! drawing a UI within an existing `wgpu` application, and
+//! [`offscreen::render_to_image`] draws a single frame to an image.
+//!
+//! Windows are passed to the toolkit as `Box<dyn kas::Window>`; the generic
+//! [`Toolkit::add`] and [`Toolkit::with`] methods merely box their argument.
+//!
 //! [WebGPU]: https://github.com/gfx-rs/wgpu-rs
 //! [winit]: https://github.com/rust-windowing/winit
 //! [clipboard]: https://crates.io/crates/clipboard
@@ -18,17 +25,22 @@
 #![cfg_attr(feature = "gat", feature(generic_associated_types))]
 
 pub mod draw;
+mod embed;
 mod event_loop;
+pub mod offscreen;
 pub mod options;
 mod shared;
 mod window;
 
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::atomic::AtomicU32;
+use std::sync::Arc;
 use thiserror::Error;
 
-use kas::event::UpdateHandle;
-use kas::WindowId;
+use kas::event::{Manager, UpdateHandle};
+use kas::{ColourScheme, ThemeAction, ThemeApi, WindowId};
 use kas_theme::Theme;
 use winit::error::OsError;
 use winit::event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget};
@@ -37,6 +49,7 @@ use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe};
 use crate::shared::SharedState;
 use window::Window;
 
+pub use embed::Embedded;
 pub use options::Options;
 
 pub use kas;
@@ -55,8 +68,20 @@ pub enum Error {
     ///
     /// This can be a driver/configuration issue or hardware limitation. Note
     /// that for now, `wgpu` only supports DX11, DX12, Vulkan and Metal.
-    #[error("no graphics adapter found")]
-    NoAdapter,
+    ///
+    /// The payload is the set of backends tried (see [`Options::backends`]).
+    #[error("no graphics adapter found for backend(s) {0:?}")]
+    NoAdapter(wgpu::BackendBit),
+    /// Failed to open a graphics device on the chosen adapter
+    ///
+    /// This is usually caused by a driver issue.
+    #[error("failed to open graphics device")]
+    RequestDevice(#[from] wgpu::RequestDeviceError),
+    /// Failed to read back a rendered image
+    ///
+    /// See [`offscreen::render_to_image`].
+    #[error("failed to read back rendered image")]
+    ReadBack,
     /// Config load/save error
     #[error("config load/save error")]
     Config(#[from] kas::event::ConfigError),
@@ -66,12 +91,6 @@ pub enum Error {
     Window(#[from] OsError),
 }
 
-impl From<wgpu::RequestDeviceError> for Error {
-    fn from(_: wgpu::RequestDeviceError) -> Self {
-        Error::NoAdapter
-    }
-}
-
 /// A toolkit over winit and WebGPU
 ///
 /// All KAS shells are expected to provide a similar `Toolkit` type and API.
@@ -147,7 +166,8 @@ where
 
     /// Assume ownership of and display a window
     ///
-    /// This is a convenience wrapper around [`Toolkit::add_boxed`].
+    /// This is a convenience wrapper around [`Toolkit::add_boxed`]. Only the
+    /// boxing is generic over `W`, thus the cost of monomorphisation is small.
     ///
     /// Note: typically, one should have `W: Clone`, enabling multiple usage.
     #[inline]
@@ -167,6 +187,11 @@ where
     }
 
     /// Add a boxed window directly
+    ///
+    /// This is the non-generic method used by all other methods adding
+    /// windows. Applications constructing many distinct window types (e.g.
+    /// from plugins) may prefer to construct each as a `Box<dyn kas::Window>`
+    /// in a non-generic function, as in the `stopwatch` example.
     pub fn add_boxed(&mut self, widget: Box<dyn kas::Window>) -> Result<WindowId, Error> {
         let id = self.shared.next_window_id();
         let win = Window::new(&mut self.shared, &self.el, id, widget)?;
@@ -175,16 +200,93 @@ where
     }
 
     /// Add a boxed window directly, inline
+    ///
+    /// See [`Toolkit::add_boxed`].
     #[inline]
     pub fn with_boxed(mut self, widget: Box<dyn kas::Window>) -> Result<Self, Error> {
         self.add_boxed(widget)?;
         Ok(self)
     }
 
+    /// Adjust the theme
+    ///
+    /// This may be called before or between adding windows; any existing
+    /// windows are updated as required. Once the UI is running, use
+    /// [`kas::event::Manager::adjust_theme`] instead.
+    pub fn adjust_theme<F: FnOnce(&mut T) -> ThemeAction>(&mut self, f: F) {
+        match f(&mut self.shared.theme) {
+            ThemeAction::None | ThemeAction::RedrawAll => (),
+            ThemeAction::ThemeResize => {
+                for window in &mut self.windows {
+                    window.theme_resize(&mut self.shared);
+                }
+            }
+        }
+    }
+
+    /// Change to a standard colour scheme
+    ///
+    /// This is a convenience wrapper around [`Toolkit::adjust_theme`].
+    /// At run-time, the scheme may be changed via
+    /// [`kas::event::Manager::adjust_theme`] and
+    /// [`ThemeApi::set_colour_scheme`].
+    #[inline]
+    pub fn set_colour_scheme(&mut self, scheme: ColourScheme) {
+        self.adjust_theme(|theme| theme.set_colour_scheme(scheme));
+    }
+
+    /// Set the UI scale factor
+    ///
+    /// This scales theme dimensions and fonts by `factor` relative to the
+    /// monitor's scale factor, e.g. `1.25` for larger UI elements. The value
+    /// is stored in [`kas::event::Config::ui_scale`], hence is persisted
+    /// with the config. Existing windows are resized.
+    ///
+    /// Panics if `factor` is not a positive, finite number.
+    pub fn set_ui_scale(&mut self, factor: f32) {
+        assert!(factor > 0.0 && factor < f32::INFINITY, "invalid ui_scale");
+        self.shared.config.borrow_mut().ui_scale = factor;
+        for window in &mut self.windows {
+            window.theme_resize(&mut self.shared);
+        }
+    }
+
+    /// Access the graphics device
+    ///
+    /// This may be used to create GPU resources (e.g. for a custom pipe)
+    /// outside of the draw cycle; see [`CustomPipe`].
+    #[inline]
+    pub fn device(&self) -> Arc<wgpu::Device> {
+        self.shared.device.clone()
+    }
+
+    /// Access the command queue
+    ///
+    /// This may be used to upload data (e.g. via `wgpu::Queue::write_texture`)
+    /// outside of the draw cycle; see [`CustomPipe`].
+    #[inline]
+    pub fn queue(&self) -> Arc<wgpu::Queue> {
+        self.shared.queue.clone()
+    }
+
+    /// Set the font scale factor
+    ///
+    /// This scales font sizes by `factor`, without affecting other theme
+    /// dimensions. The value is stored in [`kas::event::Config::font_scale`],
+    /// hence is persisted with the config. Existing windows are resized.
+    ///
+    /// At run-time, use [`kas::event::Manager::adjust_theme`] with
+    /// [`ThemeApi::set_font_scale`].
+    pub fn set_font_scale(&mut self, factor: f32) {
+        self.shared.config.borrow_mut().font_scale = Some(factor);
+        self.adjust_theme(|theme| theme.set_font_scale(factor));
+    }
+
     /// Create a proxy which can be used to update the UI from another thread
     pub fn create_proxy(&self) -> ToolkitProxy {
         ToolkitProxy {
             proxy: self.el.create_proxy(),
+            window_id: self.shared.window_id.clone(),
         }
     }
 
@@ -212,6 +314,7 @@ fn find_scale_factor<T>(el: &EventLoopWindowTarget<T>) -> f64 {
 /// Created by [`Toolkit::create_proxy`].
 pub struct ToolkitProxy {
     proxy: EventLoopProxy<ProxyAction>,
+    window_id: Arc<AtomicU32>,
 }
 
 /// Error type returned by [`ToolkitProxy`] functions.
@@ -234,17 +337,85 @@ impl ToolkitProxy {
             .map_err(|_| ClosedError)
     }
 
+    /// Add a new window
+    ///
+    /// Windows are usually not `Send`, thus the constructor `f` is sent
+    /// instead; it is called on the UI thread and the window opened from the
+    /// event loop. The identifier is allocated immediately and may be used
+    /// (e.g. with [`ToolkitProxy::close`]) even before the window opens.
+    ///
+    /// If the window cannot be created, an error is logged.
+    pub fn add_window(
+        &self,
+        f: Box<dyn FnOnce() -> Box<dyn kas::Window> + Send>,
+    ) -> Result<WindowId, ClosedError> {
+        let id = shared::next_window_id(&self.window_id);
+        self.proxy
+            .send_event(ProxyAction::AddWindow(id, f))
+            .map_err(|_| ClosedError)?;
+        Ok(id)
+    }
+
     /// Trigger an update handle
     pub fn trigger_update(&self, handle: UpdateHandle, payload: u64) -> Result<(), ClosedError> {
         self.proxy
             .send_event(ProxyAction::Update(handle, payload))
             .map_err(|_| ClosedError)
     }
+
+    /// Send a message to a window
+    ///
+    /// The message is sent to the UI thread and delivered to the root widget
+    /// of window `id` as [`kas::event::Event::Message`] (with
+    /// [`kas::widget::Window`], to its content widget); the recipient may
+    /// extract it with [`kas::event::Payload::take`]. Since the message is
+    /// constructed on this thread, it must be `Send`.
+    ///
+    /// If no window `id` is open when the message is received, it is dropped.
+    pub fn send_to(&self, id: WindowId, msg: Box<dyn Any + Send>) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SendTo(id, msg))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Run a closure on the UI thread
+    ///
+    /// The closure is sent to the UI thread and called from the event loop
+    /// with the [`Manager`] of the first open window (that added first). It
+    /// may for example trigger updates via [`Manager::trigger_update`], or
+    /// send actions. The closure must be `Send` since it is moved from the
+    /// calling thread to the UI thread (`ToolkitProxy` is usually used from
+    /// other threads). It should return promptly to avoid blocking the UI.
+    ///
+    /// If no window is open when the closure is received, it is dropped
+    /// without being called.
+    pub fn run(&self, f: Box<dyn FnOnce(&mut Manager) + Send>) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::Run(f))
+            .map_err(|_| ClosedError)
+    }
 }
 
-#[derive(Debug)]
 enum ProxyAction {
     CloseAll,
     Close(WindowId),
+    AddWindow(WindowId, Box<dyn FnOnce() -> Box<dyn kas::Window> + Send>),
     Update(UpdateHandle, u64),
+    SendTo(WindowId, Box<dyn Any + Send>),
+    Run(Box<dyn FnOnce(&mut Manager) + Send>),
+}
+
+impl std::fmt::Debug for ProxyAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ProxyAction::CloseAll => write!(f, "CloseAll"),
+            ProxyAction::Close(id) => write!(f, "Close({:?})", id),
+            ProxyAction::AddWindow(id, _) => write!(f, "AddWindow({:?}, ..)", id),
+            ProxyAction::Update(handle, payload) => {
+                write!(f, "Update({:?}, {:?})", handle, payload)
+            }
+            ProxyAction::SendTo(id, _) => write!(f, "SendTo({:?}, ..)", id),
+            ProxyAction::Run(_) => write!(f, "Run(..)"),
+        }
+    }
 }
@@ -0,0 +1,121 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Offscreen rendering
+//!
+//! This allows a window to be drawn to an image without ever opening an OS
+//! window, for example for screenshot tests.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use kas::conv::{Cast, Conv};
+use kas::event::Config;
+use kas::geom::Size;
+use kas_theme::Theme;
+
+use crate::draw::{DrawPipe, TEX_FORMAT};
+use crate::shared::request_device;
+use crate::{Embedded, Error, Options};
+
+/// Render a window to an RGBA image
+///
+/// The window is sized to `size` (in physical pixels), drawn once using
+/// `theme` at scale factor 1 and read back from the GPU. The result contains
+/// `size.0 * size.1` pixels of 8-bit sRGB RGBA data in row-major order, or
+/// is empty if either dimension of `size` is zero.
+///
+/// The graphics adapter is selected via [`Options::from_env`]. Default KAS
+/// config is used (i.e. `KAS_CONFIG` is ignored), thus output is
+/// deterministic given the same theme, size and adapter.
+pub fn render_to_image<T>(
+    window: Box<dyn kas::Window>,
+    size: Size,
+    theme: T,
+) -> Result<Vec<u8>, Error>
+where
+    T: Theme<DrawPipe<()>>,
+    T::Window: kas_theme::Window,
+{
+    if size.0 <= 0 || size.1 <= 0 {
+        return Ok(vec![]);
+    }
+
+    let options = Options::from_env();
+    let (_instance, device, queue) = request_device(&options)?;
+    let device = Arc::new(device);
+    let queue = Arc::new(queue);
+    let config = Rc::new(RefCell::new(Config::default()));
+
+    let mut ui = Embedded::new((), theme, config, device.clone(), queue.clone(), window);
+    ui.resize(size);
+    ui.update();
+
+    let (width, height): (u32, u32) = (size.0.cast(), size.1.cast());
+    let extent = wgpu::Extent3d {
+        width,
+        height,
+        depth: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("offscreen target"),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TEX_FORMAT,
+        usage: wgpu::TextureUsage::RENDER_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+    });
+    let view = texture.create_view(&Default::default());
+    ui.render_impl(&view, true);
+
+    // Rows of the copy must be aligned
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let bytes_per_row = (4 * width + align - 1) / align * align;
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("offscreen read-back"),
+        size: u64::from(bytes_per_row * height),
+        usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("offscreen read-back"),
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TextureCopyView {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+        },
+        wgpu::BufferCopyView {
+            buffer: &buffer,
+            layout: wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row,
+                rows_per_image: height,
+            },
+        },
+        extent,
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let req = slice.map_async(wgpu::MapMode::Read);
+    device.poll(wgpu::Maintain::Wait);
+    futures::executor::block_on(req).map_err(|_| Error::ReadBack)?;
+
+    let data = slice.get_mapped_range();
+    let row_len = 4 * usize::conv(width);
+    let mut image = Vec::with_capacity(row_len * usize::conv(height));
+    for row in data.chunks(bytes_per_row.cast()) {
+        // TEX_FORMAT is BGRA
+        for px in row[..row_len].chunks_exact(4) {
+            image.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+        }
+    }
+    Ok(image)
+}
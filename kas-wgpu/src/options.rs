@@ -9,7 +9,7 @@ use super::Error;
 use log::warn;
 use std::env::var;
 use std::path::PathBuf;
-pub use wgpu::{BackendBit, PowerPreference};
+pub use wgpu::{BackendBit, PowerPreference, PresentMode};
 
 /// Config mode
 ///
@@ -29,10 +29,14 @@ pub struct Options {
     pub config_path: PathBuf,
     /// Config mode. Default: Read.
     pub config_mode: ConfigMode,
-    /// Adapter power preference. Default value: low power.
+    /// Adapter power preference. Default value: high performance.
     pub power_preference: PowerPreference,
     /// Adapter backend. Default value: PRIMARY (Vulkan/Metal/DX12).
     pub backends: BackendBit,
+    /// Swap chain present mode. Default value: Fifo (VSync).
+    pub present_mode: PresentMode,
+    /// Layout debugging. Default value: false.
+    pub debug_layout: bool,
 }
 
 impl Default for Options {
@@ -40,8 +44,10 @@ impl Default for Options {
         Options {
             config_path: PathBuf::new(),
             config_mode: ConfigMode::Read,
-            power_preference: PowerPreference::LowPower,
+            power_preference: PowerPreference::HighPerformance,
             backends: BackendBit::PRIMARY,
+            present_mode: PresentMode::Fifo,
+            debug_layout: false,
         }
     }
 }
@@ -75,13 +81,14 @@ impl Options {
     ///
     /// The `KAS_POWER_PREFERENCE` variable supports:
     ///
-    /// -   `Default`
+    /// -   `Default`: same as `HighPerformance`
     /// -   `LowPower`
     /// -   `HighPerformance`
     ///
     /// ### Backend
     ///
-    /// The `KAS_BACKENDS` variable supports:
+    /// The `KAS_BACKENDS` variable takes a comma-separated list of backends
+    /// (e.g. `vulkan,metal`), each of which may be:
     ///
     /// -   `Vulkan`
     /// -   `GL`
@@ -90,6 +97,22 @@ impl Options {
     /// -   `DX12`
     /// -   `PRIMARY`: any of Vulkan, Metal or DX12
     /// -   `SECONDARY`: any of GL or DX11
+    ///
+    /// ### Present mode
+    ///
+    /// The `KAS_PRESENT_MODE` variable supports:
+    ///
+    /// -   `Fifo` (default): wait for vertical blank; no tearing
+    /// -   `Mailbox`: low latency without tearing
+    /// -   `Immediate`: no waiting; may tear
+    ///
+    /// If the surface does not support the chosen mode, `Fifo` is used.
+    ///
+    /// ### Layout debugging
+    ///
+    /// If `KAS_DEBUG_LAYOUT` is `1` (or `true`), an outline is drawn around
+    /// each widget and the size rules of the hovered widget are logged (at
+    /// `info` level). `0` and `false` disable this mode.
     pub fn from_env() -> Self {
         let mut options = Options::default();
 
@@ -112,8 +135,8 @@ impl Options {
         if let Ok(mut v) = var("KAS_POWER_PREFERENCE") {
             v.make_ascii_uppercase();
             options.power_preference = match v.as_str() {
-                "DEFAULT" | "LOWPOWER" => PowerPreference::LowPower,
-                "HIGHPERFORMANCE" => PowerPreference::HighPerformance,
+                "LOWPOWER" => PowerPreference::LowPower,
+                "DEFAULT" | "HIGHPERFORMANCE" => PowerPreference::HighPerformance,
                 other => {
                     warn!(
                         "Unexpected environment value: KAS_POWER_PREFERENCE={}",
@@ -126,17 +149,48 @@ impl Options {
 
         if let Ok(mut v) = var("KAS_BACKENDS") {
             v.make_ascii_uppercase();
-            options.backends = match v.as_str() {
-                "VULKAN" => BackendBit::VULKAN,
-                "GL" => BackendBit::GL,
-                "METAL" => BackendBit::METAL,
-                "DX11" => BackendBit::DX11,
-                "DX12" => BackendBit::DX12,
-                "PRIMARY" => BackendBit::PRIMARY,
-                "SECONDARY" => BackendBit::SECONDARY,
+            let mut backends = BackendBit::empty();
+            for token in v.split(',').map(|s| s.trim()) {
+                backends |= match token {
+                    "VULKAN" => BackendBit::VULKAN,
+                    "GL" => BackendBit::GL,
+                    "METAL" => BackendBit::METAL,
+                    "DX11" => BackendBit::DX11,
+                    "DX12" => BackendBit::DX12,
+                    "PRIMARY" => BackendBit::PRIMARY,
+                    "SECONDARY" => BackendBit::SECONDARY,
+                    other => {
+                        warn!("Unexpected environment value: KAS_BACKENDS={}", other);
+                        BackendBit::empty()
+                    }
+                };
+            }
+            if !backends.is_empty() {
+                options.backends = backends;
+            }
+        }
+
+        if let Ok(mut v) = var("KAS_PRESENT_MODE") {
+            v.make_ascii_uppercase();
+            options.present_mode = match v.as_str() {
+                "FIFO" => PresentMode::Fifo,
+                "MAILBOX" => PresentMode::Mailbox,
+                "IMMEDIATE" => PresentMode::Immediate,
                 other => {
-                    warn!("Unexpected environment value: KAS_BACKENDS={}", other);
-                    options.backends
+                    warn!("Unexpected environment value: KAS_PRESENT_MODE={}", other);
+                    options.present_mode
+                }
+            }
+        }
+
+        if let Ok(mut v) = var("KAS_DEBUG_LAYOUT") {
+            v.make_ascii_uppercase();
+            options.debug_layout = match v.as_str() {
+                "1" | "TRUE" => true,
+                "0" | "FALSE" => false,
+                other => {
+                    warn!("Unexpected environment value: KAS_DEBUG_LAYOUT={}", other);
+                    options.debug_layout
                 }
             }
         }
@@ -144,6 +198,28 @@ impl Options {
         options
     }
 
+    /// Set the power preference used when selecting a graphics adapter
+    pub fn with_power_preference(mut self, power_preference: PowerPreference) -> Self {
+        self.power_preference = power_preference;
+        self
+    }
+
+    /// Set the backends which may be used
+    ///
+    /// For example, `BackendBit::VULKAN` forces use of Vulkan.
+    pub fn with_backends(mut self, backends: BackendBit) -> Self {
+        self.backends = backends;
+        self
+    }
+
+    /// Set the swap chain present mode
+    ///
+    /// If the surface does not support this mode, `Fifo` is used.
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
     pub(crate) fn adapter_options(&self) -> wgpu::RequestAdapterOptions {
         wgpu::RequestAdapterOptions {
             power_preference: self.power_preference,
@@ -151,10 +227,6 @@ impl Options {
         }
     }
 
-    pub(crate) fn backend(&self) -> BackendBit {
-        self.backends
-    }
-
     /// Load KAS config
     pub fn config(&self) -> Result<kas::event::Config, Error> {
         if !self.config_path.as_os_str().is_empty() {
@@ -7,8 +7,11 @@
 
 use log::{info, warn};
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 
 use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe, DrawWindow, ShaderManager};
 use crate::{Error, Options, WindowId};
@@ -21,18 +24,55 @@ use clipboard::{ClipboardContext, ClipboardProvider};
 pub struct SharedState<C: CustomPipe, T> {
     #[cfg(feature = "clipboard")]
     clipboard: Option<ClipboardContext>,
-    pub instance: wgpu::Instance,
-    pub device: wgpu::Device,
-    pub queue: wgpu::Queue,
+    /// Used to create surfaces; `None` when the device is supplied by the user
+    pub instance: Option<wgpu::Instance>,
+    pub device: Arc<wgpu::Device>,
+    pub queue: Arc<wgpu::Queue>,
     pub shaders: ShaderManager,
     pub draw: DrawPipe<C>,
     pub theme: T,
     pub config: Rc<RefCell<kas::event::Config>>,
-    pub pending: Vec<PendingAction>,
+    pub pending: VecDeque<PendingAction>,
     /// Newly created windows need to know the scale_factor *before* they are
     /// created. This is used to estimate ideal window size.
     pub scale_factor: f64,
-    window_id: u32,
+    /// Draw widget outlines and report size rules on hover
+    pub debug_layout: bool,
+    /// Swap chain present mode
+    pub present_mode: wgpu::PresentMode,
+    /// Window identifier counter, shared with [`crate::ToolkitProxy`]
+    pub window_id: Arc<AtomicU32>,
+}
+
+/// Allocate a new window identifier from a shared counter
+pub(crate) fn next_window_id(counter: &AtomicU32) -> WindowId {
+    let id = counter.fetch_add(1, Ordering::Relaxed) + 1;
+    WindowId::new(NonZeroU32::new(id).unwrap())
+}
+
+/// Request a graphics adapter and device, as configured by `options`
+pub(crate) fn request_device(
+    options: &Options,
+) -> Result<(wgpu::Instance, wgpu::Device, wgpu::Queue), Error> {
+    let backends = options.backends;
+    let instance = wgpu::Instance::new(backends);
+    let adapter_options = options.adapter_options();
+    let req = instance.request_adapter(&adapter_options);
+    let adapter = match futures::executor::block_on(req) {
+        Some(a) => a,
+        None => return Err(Error::NoAdapter(backends)),
+    };
+    info!("Using graphics adapter: {}", adapter.get_info().name);
+
+    let desc = wgpu::DeviceDescriptor {
+        label: None,
+        features: Default::default(),
+        limits: Default::default(),
+    };
+    let req = adapter.request_device(&desc, None);
+    let (device, queue) = futures::executor::block_on(req)?;
+
+    Ok((instance, device, queue))
 }
 
 impl<C: CustomPipe, T: Theme<DrawPipe<C>>> SharedState<C, T>
@@ -42,11 +82,39 @@ where
     /// Construct
     pub fn new<CB: CustomPipeBuilder<Pipe = C>>(
         custom: CB,
-        mut theme: T,
+        theme: T,
         options: Options,
         config: Rc<RefCell<kas::event::Config>>,
         scale_factor: f64,
     ) -> Result<Self, Error> {
+        let (instance, device, queue) = request_device(&options)?;
+
+        let mut shared = Self::with_device(
+            custom,
+            theme,
+            config,
+            scale_factor,
+            Arc::new(device),
+            Arc::new(queue),
+        );
+        shared.instance = Some(instance);
+        shared.debug_layout = options.debug_layout;
+        shared.present_mode = options.present_mode;
+        Ok(shared)
+    }
+
+    /// Construct over an existing device
+    ///
+    /// No `wgpu::Instance` is available, thus surfaces (windows) cannot be
+    /// created from the result.
+    pub fn with_device<CB: CustomPipeBuilder<Pipe = C>>(
+        custom: CB,
+        mut theme: T,
+        config: Rc<RefCell<kas::event::Config>>,
+        scale_factor: f64,
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+    ) -> Self {
         #[cfg(feature = "clipboard")]
         let clipboard = match ClipboardContext::new() {
             Ok(cb) => Some(cb),
@@ -56,62 +124,50 @@ where
             }
         };
 
-        let instance = wgpu::Instance::new(options.backend());
-        let adapter_options = options.adapter_options();
-        let req = instance.request_adapter(&adapter_options);
-        let adapter = match futures::executor::block_on(req) {
-            Some(a) => a,
-            None => return Err(Error::NoAdapter),
-        };
-        info!("Using graphics adapter: {}", adapter.get_info().name);
-
-        let desc = wgpu::DeviceDescriptor {
-            label: None,
-            features: Default::default(),
-            limits: Default::default(),
-        };
-        let req = adapter.request_device(&desc, None);
-        let (device, queue) = futures::executor::block_on(req)?;
-
         let shaders = ShaderManager::new(&device);
         let mut draw = DrawPipe::new(custom, &device, &shaders);
 
         theme.init(&mut draw);
+        // An explicitly configured font scale overrides the theme's value
+        if let Some(scale) = config.borrow().font_scale {
+            let _ = theme.set_font_scale(scale);
+        }
 
-        Ok(SharedState {
+        SharedState {
             #[cfg(feature = "clipboard")]
             clipboard,
-            instance,
+            instance: None,
             device,
             queue,
             shaders,
             draw,
             theme,
             config,
-            pending: vec![],
+            pending: Default::default(),
             scale_factor,
-            window_id: 0,
-        })
+            debug_layout: false,
+            present_mode: wgpu::PresentMode::Fifo,
+            window_id: Default::default(),
+        }
+    }
+
+    /// Scale factor used by the theme, given the window's scale factor
+    pub fn theme_scale_factor(&self, scale_factor: f64) -> f32 {
+        scale_factor as f32 * self.config.borrow().ui_scale
     }
 
     pub fn next_window_id(&mut self) -> WindowId {
-        self.window_id += 1;
-        WindowId::new(NonZeroU32::new(self.window_id).unwrap())
+        next_window_id(&self.window_id)
     }
 
     pub fn render(
         &mut self,
         window: &mut DrawWindow<C::Window>,
         frame_view: &wgpu::TextureView,
-        clear_color: wgpu::Color,
+        clear_color: Option<wgpu::Color>,
     ) {
-        self.draw.render(
-            window,
-            &mut self.device,
-            &mut self.queue,
-            frame_view,
-            clear_color,
-        );
+        self.draw
+            .render(window, &self.device, &self.queue, frame_view, clear_color);
     }
 
     #[cfg(not(feature = "clipboard"))]
@@ -153,4 +209,5 @@ pub enum PendingAction {
     ThemeResize,
     RedrawAll,
     Update(kas::event::UpdateHandle, u64),
+    Message(WindowId, Box<dyn std::any::Any + Send>),
 }
@@ -5,26 +5,30 @@
 
 //! `Window` and `WindowList` types
 
-use log::{debug, error, info, trace};
-use std::time::Instant;
+use log::{debug, error, info, trace, warn};
+use std::time::{Duration, Instant};
 
 use kas::conv::Cast;
-use kas::draw::SizeHandle;
-use kas::event::{CursorIcon, ManagerState, UpdateHandle};
-use kas::geom::{Coord, Rect, Size};
-use kas::layout::SolveCache;
-use kas::{ThemeAction, ThemeApi, TkAction, WindowId};
+use kas::draw::{Colour, Draw, DrawHandle, Pass, SizeHandle};
+use kas::event::{CursorIcon, ManagerState, UpdateHandle, UpdateQueue};
+use kas::geom::{Coord, DVec2, Offset, Quad, Rect, Size};
+use kas::layout::{debug_size_rules, SolveCache};
+use kas::{ThemeAction, ThemeApi, TkAction, WidgetConfig, WidgetId, WindowId, WindowPosition};
 use kas_theme::Theme;
-use winit::dpi::PhysicalSize;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::error::OsError;
 use winit::event::WindowEvent;
 use winit::event_loop::EventLoopWindowTarget;
+use winit::monitor::MonitorHandle;
 use winit::window::WindowBuilder;
 
 use crate::draw::{CustomPipe, CustomWindow, DrawPipe, DrawWindow, TEX_FORMAT};
 use crate::shared::{PendingAction, SharedState};
 use crate::ProxyAction;
 
+/// Minimum time between frames while redrawing continuously
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
 /// Per-window data
 pub(crate) struct Window<CW: CustomWindow, TW> {
     pub(crate) widget: Box<dyn kas::Window>,
@@ -38,6 +42,12 @@ pub(crate) struct Window<CW: CustomWindow, TW> {
     swap_chain: wgpu::SwapChain,
     draw: DrawWindow<CW>,
     theme_window: TW,
+    /// True while minimised: drawing is suspended
+    hidden: bool,
+    /// Earliest time of the next frame while redrawing continuously
+    next_frame: Instant,
+    /// Last hovered widget, when debugging layout
+    debug_hover: Option<WidgetId>,
 }
 
 // Public functions, for use by the toolkit
@@ -60,12 +70,13 @@ where
         let time = Instant::now();
 
         // Create draw immediately (with Size::ZERO) to find ideal window size
-        let scale_factor = shared.scale_factor as f32;
-        let mut draw = shared.draw.new_window(&mut shared.device, Size::ZERO);
+        let scale_factor = shared.theme_scale_factor(shared.scale_factor);
+        let mut draw = shared.draw.new_window(&shared.device, Size::ZERO);
         let mut theme_window = shared.theme.new_window(&mut draw, scale_factor);
 
         let mut size_handle = unsafe { theme_window.size_handle() };
-        let solve_cache = SolveCache::find_constraints(widget.as_widget_mut(), &mut size_handle);
+        let mut solve_cache =
+            SolveCache::find_constraints(widget.as_widget_mut(), &mut size_handle);
         // Opening a zero-size window causes a crash, so force at least 1x1:
         let ideal = solve_cache.ideal(true).max(Size(1, 1));
         drop(size_handle);
@@ -78,9 +89,63 @@ where
         if restrict_dimensions.1 {
             builder = builder.with_max_inner_size(ideal);
         }
+        let size_to_content = widget.size_to_content();
+        let position = widget.position();
+        if let WindowPosition::At(coord) = position {
+            builder = builder.with_position(PhysicalPosition::<i32>::from(coord));
+        }
+        let show_later = match position {
+            WindowPosition::Centered | WindowPosition::OnMonitor(_) => true,
+            _ => size_to_content,
+        };
+        if show_later {
+            // The scale factor and final size are not known until the window
+            // exists; we show it only once correctly sized and positioned.
+            builder = builder.with_visible(false);
+        }
         let window = builder.with_title(widget.title()).build(elwt)?;
 
         let scale_factor = window.scale_factor();
+        if size_to_content {
+            if scale_factor != shared.scale_factor {
+                let factor = shared.theme_scale_factor(scale_factor);
+                shared.theme.update_window(&mut theme_window, factor);
+                let mut size_handle = unsafe { theme_window.size_handle() };
+                solve_cache =
+                    SolveCache::find_constraints(widget.as_widget_mut(), &mut size_handle);
+            }
+            let min = solve_cache.min(true).max(Size(1, 1));
+            let mut ideal = solve_cache.ideal(true).max(min);
+            if let Some(monitor) = window.current_monitor() {
+                let bounds: Size = monitor.size().into();
+                ideal = ideal.min(bounds).max(min);
+            }
+            window.set_inner_size(ideal);
+            if restrict_dimensions.0 {
+                window.set_min_inner_size(Some(min));
+            }
+            if restrict_dimensions.1 {
+                window.set_max_inner_size(Some(ideal));
+            }
+        }
+        match position {
+            WindowPosition::Centered => {
+                if let Some(monitor) = window.current_monitor() {
+                    center_on(&window, &monitor);
+                }
+            }
+            WindowPosition::OnMonitor(index) => {
+                if let Some(monitor) = elwt.available_monitors().nth(index) {
+                    center_on(&window, &monitor);
+                } else {
+                    warn!("WindowPosition::OnMonitor({}): no such monitor", index);
+                }
+            }
+            _ => (),
+        }
+        if show_later {
+            window.set_visible(true);
+        }
         shared.scale_factor = scale_factor;
         let size: Size = window.inner_size().into();
         info!("Constucted new window with size {:?}", size);
@@ -89,13 +154,15 @@ where
         let buf = shared.draw.resize(&mut draw, &shared.device, size);
         shared.queue.submit(std::iter::once(buf));
 
-        let surface = unsafe { shared.instance.create_surface(&window) };
+        let instance = shared.instance.as_ref().expect("no wgpu::Instance");
+        let surface = unsafe { instance.create_surface(&window) };
         let sc_desc = wgpu::SwapChainDescriptor {
             usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
             format: TEX_FORMAT,
             width: size.0.cast(),
             height: size.1.cast(),
-            present_mode: wgpu::PresentMode::Fifo,
+            // wgpu falls back to Fifo if this mode is unsupported
+            present_mode: shared.present_mode,
         };
         let swap_chain = shared.device.create_swap_chain(&surface, &sc_desc);
 
@@ -114,6 +181,9 @@ where
             swap_chain,
             draw,
             theme_window,
+            hidden: false,
+            next_frame: Instant::now(),
+            debug_hover: None,
         };
         r.apply_size(shared);
 
@@ -127,10 +197,12 @@ where
         T: Theme<DrawPipe<C>, Window = TW>,
     {
         debug!("Window::theme_resize");
-        let scale_factor = self.window.scale_factor() as f32;
+        let scale_factor = shared.theme_scale_factor(self.window.scale_factor());
         shared
             .theme
             .update_window(&mut self.theme_window, scale_factor);
+        // The theme's fonts or font sizes may have changed
+        self.draw.preloaded_glyphs.clear();
         self.solve_cache.invalidate_rule_cache();
         self.apply_size(shared);
     }
@@ -144,25 +216,53 @@ where
         // Note: resize must be handled here to update self.swap_chain.
         match event {
             WindowEvent::Destroyed => (),
-            WindowEvent::Resized(size) => self.do_resize(shared, size),
+            WindowEvent::Resized(size) if size.width == 0 || size.height == 0 => {
+                // Some platforms report minimisation as a resize to zero.
+                // Winit does not (yet) report occlusion, so this is our only
+                // indication that drawing may be skipped.
+                debug!("Window hidden: suspending drawing");
+                self.hidden = true;
+            }
+            WindowEvent::Resized(size) => {
+                if self.hidden {
+                    debug!("Window shown: resuming drawing");
+                    self.hidden = false;
+                    self.window.request_redraw();
+                }
+                self.do_resize(shared, size)
+            }
             WindowEvent::ScaleFactorChanged {
                 scale_factor,
                 new_inner_size,
             } => {
                 // Note: API allows us to set new window size here.
                 shared.scale_factor = scale_factor;
+                let scale_factor = shared.theme_scale_factor(scale_factor);
                 shared
                     .theme
-                    .update_window(&mut self.theme_window, scale_factor as f32);
+                    .update_window(&mut self.theme_window, scale_factor);
+                // Text is now drawn at a different scale
+                self.draw.preloaded_glyphs.clear();
                 self.solve_cache.invalidate_rule_cache();
                 self.do_resize(shared, *new_inner_size);
             }
             event @ _ => {
+                let debug_coord = match event {
+                    WindowEvent::CursorMoved { position, .. } if shared.debug_layout => {
+                        Some(position.into())
+                    }
+                    _ => None,
+                };
+
                 let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
                 let widget = &mut *self.widget;
                 self.mgr.with(&mut tkw, |mgr| {
                     mgr.handle_winit(widget, event);
                 });
+
+                if let Some(coord) = debug_coord {
+                    self.debug_hover(shared, coord);
+                }
             }
         }
     }
@@ -198,14 +298,34 @@ where
         if action.contains(TkAction::REGION_MOVED) {
             let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
             self.mgr.region_moved(&mut tkw, &mut *self.widget);
-            self.window.request_redraw();
-        } else if action.contains(TkAction::REDRAW) {
-            self.window.request_redraw();
+            self.request_redraw();
+        } else if action.contains(TkAction::REDRAW) || self.frame_due(Instant::now()) {
+            self.request_redraw();
         }
 
         (action, self.mgr.next_resume())
     }
 
+    /// Whether this window is currently redrawn continuously
+    pub fn continuous_redraw(&self) -> bool {
+        !self.hidden && self.mgr.continuous_redraw(&*self.widget)
+    }
+
+    /// Time at which the next frame should be drawn, if redrawing continuously
+    ///
+    /// The event loop should wake at this time (see [`Self::update`]).
+    pub fn next_frame(&self) -> Option<Instant> {
+        if self.continuous_redraw() {
+            Some(self.next_frame)
+        } else {
+            None
+        }
+    }
+
+    fn frame_due(&self, now: Instant) -> bool {
+        self.next_frame().map(|t| t <= now).unwrap_or(false)
+    }
+
     pub fn handle_closure<C, T>(mut self, shared: &mut SharedState<C, T>) -> TkAction
     where
         C: CustomPipe<Window = CW>,
@@ -232,11 +352,48 @@ where
         self.mgr.next_resume()
     }
 
-    pub fn update_handle<C, T>(
+    pub fn update_handles<C, T>(&mut self, shared: &mut SharedState<C, T>, updates: &UpdateQueue)
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
+        let widget = &mut *self.widget;
+        self.mgr.with(&mut tkw, |mgr| {
+            mgr.update_handles(widget, updates);
+        });
+    }
+
+    /// Handle raw mouse motion (only used during a grab)
+    pub fn handle_mouse_motion<C, T>(&mut self, shared: &mut SharedState<C, T>, delta: (f64, f64))
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        let delta = DVec2::from(delta);
+        let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
+        let widget = &mut *self.widget;
+        self.mgr.with(&mut tkw, |mgr| {
+            mgr.handle_mouse_motion(widget, delta);
+        });
+    }
+
+    pub fn run_closure<C, T>(
+        &mut self,
+        shared: &mut SharedState<C, T>,
+        f: Box<dyn FnOnce(&mut kas::event::Manager) + Send>,
+    ) where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
+        self.mgr.with(&mut tkw, |mgr| f(mgr));
+    }
+
+    pub fn send_message<C, T>(
         &mut self,
         shared: &mut SharedState<C, T>,
-        handle: UpdateHandle,
-        payload: u64,
+        msg: Box<dyn std::any::Any + Send>,
     ) where
         C: CustomPipe<Window = CW>,
         T: Theme<DrawPipe<C>, Window = TW>,
@@ -244,7 +401,7 @@ where
         let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
         let widget = &mut *self.widget;
         self.mgr.with(&mut tkw, |mgr| {
-            mgr.update_handle(widget, handle, payload);
+            mgr.send_message(widget, msg);
         });
     }
 
@@ -264,6 +421,16 @@ where
         });
     }
 
+    /// Request a redraw, unless hidden
+    ///
+    /// Input and timers are still processed while hidden; the window is
+    /// redrawn in full when shown again.
+    pub fn request_redraw(&mut self) {
+        if !self.hidden {
+            self.window.request_redraw();
+        }
+    }
+
     pub fn send_action(&mut self, action: TkAction) {
         self.mgr.send_action(action);
     }
@@ -339,7 +506,7 @@ where
                 .set_max_inner_size(Some(self.solve_cache.ideal(true)));
         };
 
-        self.window.request_redraw();
+        self.request_redraw();
         trace!("apply_size completed in {}µs", time.elapsed().as_micros());
     }
 
@@ -373,11 +540,58 @@ where
         );
     }
 
+    // Log size rules of the hovered widget, if changed
+    //
+    // Querying size rules modifies layout state (see debug_size_rules), so we
+    // restore this with a full re-solve of the window layout.
+    fn debug_hover<C, T>(&mut self, shared: &mut SharedState<C, T>, coord: Coord)
+    where
+        C: CustomPipe<Window = CW>,
+        T: Theme<DrawPipe<C>, Window = TW>,
+    {
+        use kas_theme::Window;
+
+        let id = self.widget.find_id(coord);
+        if id == self.debug_hover {
+            return;
+        }
+        self.debug_hover = id;
+
+        if let Some(widget) = id.and_then(|id| self.widget.find_leaf_mut(id)) {
+            let mut size_handle = unsafe { self.theme_window.size_handle() };
+            let (w, h) = debug_size_rules(widget, &mut size_handle);
+            info!(
+                "Hovered {} {}: rect={:?}, horiz={:?}, vert={:?}",
+                widget.widget_name(),
+                widget.id(),
+                widget.rect(),
+                w,
+                h,
+            );
+            drop(size_handle);
+
+            self.solve_cache.invalidate_rule_cache();
+            self.apply_size(shared);
+        }
+    }
+
     pub(crate) fn do_draw<C, T>(&mut self, shared: &mut SharedState<C, T>)
     where
         C: CustomPipe<Window = CW>,
         T: Theme<DrawPipe<C>, Window = TW>,
     {
+        if self.hidden {
+            return;
+        }
+
+        self.mgr.begin_frame();
+        self.next_frame = Instant::now() + FRAME_INTERVAL;
+        if self.continuous_redraw() {
+            let mut tkw = TkWindow::new(shared, &self.window, &mut self.theme_window);
+            let widget = &mut *self.widget;
+            self.mgr.with(&mut tkw, |mgr| mgr.update_frame(widget));
+        }
+
         let time = Instant::now();
         let rect = Rect::new(Coord::ZERO, self.sc_size());
 
@@ -388,6 +602,10 @@ where
                     .theme
                     .draw_handle(&mut self.draw, &mut self.theme_window, rect);
             self.widget.draw(&mut draw_handle, &self.mgr, false);
+            if shared.debug_layout {
+                let (pass, offset, draw) = draw_handle.draw_device();
+                draw_outlines(draw, pass, self.widget.as_widget(), offset);
+            }
         }
 
         let time2 = Instant::now();
@@ -402,7 +620,7 @@ where
         let time3 = Instant::now();
         // TODO: check frame.optimal ?
         let clear_color = to_wgpu_color(shared.theme.clear_color());
-        shared.render(&mut self.draw, &frame.output.view, clear_color);
+        shared.render(&mut self.draw, &frame.output.view, Some(clear_color));
 
         let end = Instant::now();
         // Explanation: 'text' is the time to prepare positioned glyphs, 'frame-
@@ -418,7 +636,19 @@ where
     }
 }
 
-fn to_wgpu_color(c: kas::draw::Colour) -> wgpu::Color {
+// Outline each widget's rect, translating into child coordinate spaces
+fn draw_outlines(draw: &mut dyn Draw, pass: Pass, widget: &dyn WidgetConfig, offset: Offset) {
+    let outer = Quad::from(widget.rect() + offset);
+    let col = Colour::new(1.0, 0.0, 1.0);
+    draw.frame(pass, outer, outer.shrink(1.0), col);
+    for index in 0..widget.num_children() {
+        if let Some(child) = widget.get_child(index) {
+            draw_outlines(draw, pass, child, offset - widget.translation(index));
+        }
+    }
+}
+
+pub(crate) fn to_wgpu_color(c: kas::draw::Colour) -> wgpu::Color {
     wgpu::Color {
         r: c.r as f64,
         g: c.g as f64,
@@ -464,7 +694,7 @@ where
         let parent_id = self.window.id();
         self.shared
             .pending
-            .push(PendingAction::AddPopup(parent_id, id, popup));
+            .push_back(PendingAction::AddPopup(parent_id, id, popup));
         id
     }
 
@@ -478,18 +708,20 @@ where
         let id = self.shared.next_window_id();
         self.shared
             .pending
-            .push(PendingAction::AddWindow(id, widget));
+            .push_back(PendingAction::AddWindow(id, widget));
         id
     }
 
     fn close_window(&mut self, id: WindowId) {
-        self.shared.pending.push(PendingAction::CloseWindow(id));
+        self.shared
+            .pending
+            .push_back(PendingAction::CloseWindow(id));
     }
 
     fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
         self.shared
             .pending
-            .push(PendingAction::Update(handle, payload));
+            .push_back(PendingAction::Update(handle, payload));
     }
 
     #[inline]
@@ -505,8 +737,8 @@ where
     fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {
         match f(&mut self.shared.theme) {
             ThemeAction::None => (),
-            ThemeAction::RedrawAll => self.shared.pending.push(PendingAction::RedrawAll),
-            ThemeAction::ThemeResize => self.shared.pending.push(PendingAction::ThemeResize),
+            ThemeAction::RedrawAll => self.shared.pending.push_back(PendingAction::RedrawAll),
+            ThemeAction::ThemeResize => self.shared.pending.push_back(PendingAction::ThemeResize),
         }
     }
 
@@ -521,3 +753,17 @@ where
         self.window.set_cursor_icon(icon);
     }
 }
+
+/// Centre `window` on `monitor`
+///
+/// Winit does not report the monitor's work area (excluding panels), thus
+/// this uses the full monitor area. The window's size is not adjusted for the
+/// monitor's scale factor (see [`WindowPosition::OnMonitor`]).
+fn center_on(window: &winit::window::Window, monitor: &MonitorHandle) {
+    let area = Rect::new(monitor.position().into(), monitor.size().into());
+    let size: Size = window.outer_size().into();
+    let pos = area.pos + (Offset::from(area.size) - Offset::from(size)) / 2;
+    // Keep the title bar on-screen when the window is larger than the monitor
+    let pos = pos.max(area.pos);
+    window.set_outer_position(PhysicalPosition::<i32>::from(pos));
+}
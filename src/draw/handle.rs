@@ -9,10 +9,10 @@ use std::convert::AsRef;
 use std::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
 
 use kas::dir::Direction;
-use kas::draw::{Draw, Pass};
-use kas::geom::{Coord, Offset, Rect, Size, Vec2};
+use kas::draw::{Colour, Draw, Pass};
+use kas::geom::{Coord, Offset, Quad, Rect, Size, Vec2};
 use kas::layout::{AxisInfo, FrameRules, Margins, SizeRules};
-use kas::text::{format::FormattableText, AccelString, Text, TextApi, TextDisplay};
+use kas::text::{format::FormattableText, AccelString, Effect, Text, TextApi, TextDisplay};
 
 // for doc use
 #[allow(unused)]
@@ -37,6 +37,9 @@ pub struct InputState {
     pub disabled: bool,
     /// Some widgets, such as `EditBox`, use a red background on error
     pub error: bool,
+    /// Read-only widgets may be focussed and have text selected, but not
+    /// edited. Themes should distinguish these (subtly) from disabled widgets.
+    pub read_only: bool,
     /// "Hover" is true if the mouse is over this element
     pub hover: bool,
     /// Elements such as buttons, handles and menu entries may be depressed
@@ -61,6 +64,7 @@ impl std::ops::BitOr for InputState {
         InputState {
             disabled: self.disabled || rhs.disabled,
             error: self.error || rhs.error,
+            read_only: self.read_only || rhs.read_only,
             hover: self.hover || rhs.hover,
             depress: self.depress || rhs.depress,
             nav_focus: self.nav_focus || rhs.nav_focus,
@@ -70,6 +74,36 @@ impl std::ops::BitOr for InputState {
     }
 }
 
+/// Standard spacing and sizes used by the theme
+///
+/// Returned by [`SizeHandle::metrics`]. All values are scaled (i.e. in
+/// physical pixels) and reflect the theme's current configuration; they should
+/// be re-queried after a theme resize (e.g. from [`Layout::size_rules`]).
+///
+/// These allow custom widgets to match the look of standard widgets without
+/// hard-coding sizes.
+///
+/// [`Layout::size_rules`]: crate::Layout::size_rules
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Metrics {
+    /// Standard gap between widgets (the outer margin)
+    pub gap: i32,
+    /// Margin between a widget's frame and its content
+    pub inner_margin: i32,
+    /// Margin around text
+    pub text_margin: i32,
+    /// Width of a standard frame
+    pub frame: i32,
+    /// Height of a line of standard text
+    pub line_height: i32,
+    /// Height of a standard control such as a button (including its frame)
+    pub control_height: i32,
+    /// Size of a standard (square) icon
+    pub icon_size: i32,
+    /// Radius of rounded corners, or zero if the theme uses square corners
+    pub corner_radius: f32,
+}
+
 /// Class of text drawn
 ///
 /// Themes choose font, font size, colour, and alignment based on this.
@@ -85,12 +119,19 @@ pub enum TextClass {
     Edit,
     /// Class of text drawn in a multi-line edit box
     EditMulti,
+    /// Placeholder text shown in an empty edit box (usually dimmed)
+    Placeholder,
+    /// Hyperlink text (usually drawn in an accent colour)
+    Link,
 }
 
 impl TextClass {
     /// True if text should be automatically line-wrapped
     pub fn line_wrap(self) -> bool {
-        self == TextClass::Label || self == TextClass::EditMulti
+        matches!(
+            self,
+            TextClass::Label | TextClass::Link | TextClass::EditMulti
+        )
     }
 }
 
@@ -122,6 +163,10 @@ pub trait SizeHandle {
     /// This value may change during a program's execution (e.g. when a window
     /// is moved to a different monitor); in this case all widgets will be
     /// resized via [`kas::Layout::size_rules`].
+    ///
+    /// This is the only conversion from logical to physical pixels: all
+    /// [`SizeRules`] and [`kas::geom`] values are physical (see
+    /// [pixel units](kas::geom#pixel-units)).
     fn scale_factor(&self) -> f32;
 
     /// Size of a frame around child widget(s)
@@ -151,6 +196,43 @@ pub trait SizeHandle {
     /// The height of a line of text
     fn line_height(&self, class: TextClass) -> i32;
 
+    /// Font size of text, in pixels per Em
+    ///
+    /// This is the product of the theme's font size for `class` (in points)
+    /// and the scale factor; it changes when either does. Widgets caching
+    /// text measurements may use this as part of their cache key.
+    ///
+    /// The default implementation returns [`SizeHandle::line_height`], which
+    /// is proportional to the font size for most themes.
+    fn text_dpem(&self, class: TextClass) -> f32 {
+        self.line_height(class) as f32
+    }
+
+    /// Standard theme metrics
+    ///
+    /// See [`Metrics`].
+    ///
+    /// The default implementation derives these values from other methods of
+    /// this trait; themes should override this where more accurate values
+    /// are known (e.g. `text_margin` and `corner_radius`).
+    fn metrics(&self) -> Metrics {
+        let line_height = self.line_height(TextClass::Label);
+        let inner_margin = self.inner_margin().0;
+        let (_, frame, _) = self.frame(true).surround(SizeRules::EMPTY);
+        let content = SizeRules::fixed(line_height, (0, 0));
+        let (button, _, _) = self.button_surround(true).surround(content);
+        Metrics {
+            gap: self.outer_margins().vert.0.into(),
+            inner_margin,
+            text_margin: inner_margin,
+            frame,
+            line_height,
+            control_height: button.min_size(),
+            icon_size: line_height,
+            corner_radius: 0.0,
+        }
+    }
+
     /// Update a [`Text`] and get a size bound
     ///
     /// First, this method updates the text's [`Environment`]: `bounds`, `dpp`
@@ -167,6 +249,32 @@ pub trait SizeHandle {
     fn text_bound(&mut self, text: &mut dyn TextApi, class: TextClass, axis: AxisInfo)
         -> SizeRules;
 
+    /// Measure a string
+    ///
+    /// This allows widgets to size themselves to text content during
+    /// [`Layout::size_rules`] without keeping a [`Text`] object. Returns the
+    /// size (in pixels) required to display `text` with the font used for
+    /// `class`; if `wrap_width` is given, lines are wrapped at this width.
+    /// Margins are not included.
+    ///
+    /// Themes may cache results; repeated measurement of the same text at the
+    /// same scale is cheap.
+    ///
+    /// The default implementation prepares a new [`Text`] on each call, using
+    /// [`SizeHandle::text_bound`] to set the font size and scale.
+    ///
+    /// [`Layout::size_rules`]: kas::Layout::size_rules
+    fn text_size(&mut self, text: &str, class: TextClass, wrap_width: Option<f32>) -> Vec2 {
+        let mut text = Text::new_multi(text);
+        let _ = self.text_bound(&mut text, class, AxisInfo::new(false, None));
+        let required = text.update_env(|env| {
+            let width = wrap_width.unwrap_or(f32::INFINITY);
+            env.set_bounds(kas::text::Vec2(width, f32::INFINITY));
+            env.set_wrap(wrap_width.is_some());
+        });
+        Vec2::from(required)
+    }
+
     /// Width of an edit marker
     fn edit_marker_width(&self) -> f32;
 
@@ -213,6 +321,16 @@ pub trait SizeHandle {
     /// that the width is adjustable while the height is (preferably) not.
     /// For a vertical bar, the values are swapped.
     fn progress_bar(&self) -> Size;
+
+    /// Dimensions for a spinner (busy indicator)
+    ///
+    /// Returns the ideal size; the spinner is drawn within a centred square.
+    ///
+    /// The default implementation returns a square with side
+    /// [`SizeHandle::line_height`] of [`TextClass::Label`].
+    fn spinner(&self) -> Size {
+        Size::splat(self.line_height(TextClass::Label))
+    }
 }
 
 /// Handle passed to objects during draw operations
@@ -277,6 +395,53 @@ pub trait DrawHandle {
     /// that method; otherwise this returns the window's `rect`.
     fn target_rect(&self) -> Rect;
 
+    /// Draw a rectangle of uniform colour
+    ///
+    /// This and the following primitives ([`DrawHandle::frame`],
+    /// [`DrawHandle::line`], [`DrawHandle::circle`] and
+    /// [`DrawHandle::text_col`]) allow widgets to draw custom content without
+    /// a custom pipe. Unlike the themed methods, colours are specified
+    /// directly.
+    ///
+    /// Coordinates are those of the widget's [`Rect`] (converted to `f32`):
+    /// the draw offset (see [`DrawHandle::draw_device`]) is applied by the
+    /// implementation. To restrict drawing to some region, use
+    /// [`DrawHandleExt::clip`].
+    ///
+    /// The default implementations of these primitives draw via
+    /// [`DrawHandle::draw_device`]; since [`Draw`] has no rounded shapes or
+    /// text, `line`, `circle` and `text_col` only approximate the result.
+    fn quad(&mut self, quad: Quad, col: Colour) {
+        let (pass, offset, draw) = self.draw_device();
+        draw.rect(pass, quad + Vec2::from(offset), col);
+    }
+
+    /// Draw a frame of uniform colour
+    ///
+    /// The frame covers the area inside `outer` and not inside `inner`.
+    fn frame(&mut self, outer: Quad, inner: Quad, col: Colour) {
+        let (pass, offset, draw) = self.draw_device();
+        let offset = Vec2::from(offset);
+        draw.frame(pass, outer + offset, inner + offset, col);
+    }
+
+    /// Draw a line with rounded ends
+    ///
+    /// The line runs from `p1` to `p2` and is `width` wide.
+    fn line(&mut self, p1: Vec2, p2: Vec2, width: f32, col: Colour);
+
+    /// Draw a circle or oval filling `quad`
+    ///
+    /// If `inner_radius` is greater than zero, a ring is drawn instead; this is
+    /// relative to the outer radius and should be in the range `0.0..1.0`.
+    fn circle(&mut self, quad: Quad, inner_radius: f32, col: Colour);
+
+    /// Draw text with a given colour
+    ///
+    /// The `text` is drawn within the rect from `pos` to `text.env().bounds`.
+    /// Unlike [`DrawHandleExt::text`], font colour is not chosen by the theme.
+    fn text_col(&mut self, pos: Coord, text: &dyn TextApi, col: Colour);
+
     /// Draw a frame inside the given `rect`
     ///
     /// The frame dimensions equal those of [`SizeHandle::frame`] on each side.
@@ -319,6 +484,20 @@ pub trait DrawHandle {
     /// strikethrough effects.
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass);
 
+    /// Draw text with effects and per-span colours
+    ///
+    /// This is like [`DrawHandle::text_effects`], but uses the given `effects`
+    /// list instead of the text's own. An effect with colour `None` uses the
+    /// theme's colour for `class`.
+    fn text_col_spans(
+        &mut self,
+        pos: Coord,
+        offset: Offset,
+        text: &dyn TextApi,
+        effects: &[Effect<Option<Colour>>],
+        class: TextClass,
+    );
+
     /// Draw an `AccelString` text
     ///
     /// The `text` is drawn within the rect from `pos` to `text.env().bounds`.
@@ -393,6 +572,17 @@ pub trait DrawHandle {
     /// -   `state`: highlighting information
     /// -   `value`: progress value, between 0.0 and 1.0
     fn progress_bar(&mut self, rect: Rect, dir: Direction, state: InputState, value: f32);
+
+    /// Draw UI element: spinner (busy indicator)
+    ///
+    /// -   `rect`: area of whole widget
+    /// -   `state`: highlighting information
+    /// -   `phase`: animation phase, where one revolution is `0.0..1.0`
+    ///
+    /// The default implementation draws a progress bar with value `phase`.
+    fn spinner(&mut self, rect: Rect, state: InputState, phase: f32) {
+        self.progress_bar(rect, Direction::Right, state, phase);
+    }
 }
 
 /// Extension trait over [`DrawHandle`]
@@ -424,6 +614,14 @@ pub trait DrawHandleExt: DrawHandle {
         self.text_offset(pos, bounds, Offset::ZERO, text.as_ref(), class);
     }
 
+    /// Draw content clipped to the given `rect`
+    ///
+    /// This is a convenience wrapper around [`DrawHandle::clip_region`] which
+    /// does not change the coordinate space.
+    fn clip<F: FnMut(&mut dyn DrawHandle)>(&mut self, rect: Rect, mut f: F) {
+        self.clip_region(rect, Offset::ZERO, ClipRegion::Scroll, &mut f);
+    }
+
     /// Draw some text using the standard font, with a subset selected
     ///
     /// Other than visually highlighting the selection, this method behaves
@@ -479,6 +677,12 @@ impl<S: SizeHandle> SizeHandle for Box<S> {
     fn line_height(&self, class: TextClass) -> i32 {
         self.deref().line_height(class)
     }
+    fn text_dpem(&self, class: TextClass) -> f32 {
+        self.deref().text_dpem(class)
+    }
+    fn metrics(&self) -> Metrics {
+        self.deref().metrics()
+    }
     fn text_bound(
         &mut self,
         text: &mut dyn TextApi,
@@ -487,6 +691,9 @@ impl<S: SizeHandle> SizeHandle for Box<S> {
     ) -> SizeRules {
         self.deref_mut().text_bound(text, class, axis)
     }
+    fn text_size(&mut self, text: &str, class: TextClass, wrap_width: Option<f32>) -> Vec2 {
+        self.deref_mut().text_size(text, class, wrap_width)
+    }
     fn edit_marker_width(&self) -> f32 {
         self.deref().edit_marker_width()
     }
@@ -513,6 +720,9 @@ impl<S: SizeHandle> SizeHandle for Box<S> {
     fn progress_bar(&self) -> Size {
         self.deref().progress_bar()
     }
+    fn spinner(&self) -> Size {
+        self.deref().spinner()
+    }
 }
 
 #[cfg(feature = "stack_dst")]
@@ -543,6 +753,12 @@ where
     fn line_height(&self, class: TextClass) -> i32 {
         self.deref().line_height(class)
     }
+    fn text_dpem(&self, class: TextClass) -> f32 {
+        self.deref().text_dpem(class)
+    }
+    fn metrics(&self) -> Metrics {
+        self.deref().metrics()
+    }
     fn text_bound(
         &mut self,
         text: &mut dyn TextApi,
@@ -551,6 +767,9 @@ where
     ) -> SizeRules {
         self.deref_mut().text_bound(text, class, axis)
     }
+    fn text_size(&mut self, text: &str, class: TextClass, wrap_width: Option<f32>) -> Vec2 {
+        self.deref_mut().text_size(text, class, wrap_width)
+    }
     fn edit_marker_width(&self) -> f32 {
         self.deref().edit_marker_width()
     }
@@ -577,6 +796,9 @@ where
     fn progress_bar(&self) -> Size {
         self.deref().progress_bar()
     }
+    fn spinner(&self) -> Size {
+        self.deref().spinner()
+    }
 }
 
 impl<H: DrawHandle> DrawHandle for Box<H> {
@@ -598,6 +820,21 @@ impl<H: DrawHandle> DrawHandle for Box<H> {
     fn target_rect(&self) -> Rect {
         self.deref().target_rect()
     }
+    fn quad(&mut self, quad: Quad, col: Colour) {
+        self.deref_mut().quad(quad, col);
+    }
+    fn frame(&mut self, outer: Quad, inner: Quad, col: Colour) {
+        self.deref_mut().frame(outer, inner, col);
+    }
+    fn line(&mut self, p1: Vec2, p2: Vec2, width: f32, col: Colour) {
+        self.deref_mut().line(p1, p2, width, col);
+    }
+    fn circle(&mut self, quad: Quad, inner_radius: f32, col: Colour) {
+        self.deref_mut().circle(quad, inner_radius, col);
+    }
+    fn text_col(&mut self, pos: Coord, text: &dyn TextApi, col: Colour) {
+        self.deref_mut().text_col(pos, text, col);
+    }
     fn outer_frame(&mut self, rect: Rect) {
         self.deref_mut().outer_frame(rect);
     }
@@ -624,6 +861,17 @@ impl<H: DrawHandle> DrawHandle for Box<H> {
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass) {
         self.deref_mut().text_effects(pos, offset, text, class);
     }
+    fn text_col_spans(
+        &mut self,
+        pos: Coord,
+        offset: Offset,
+        text: &dyn TextApi,
+        effects: &[Effect<Option<Colour>>],
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_col_spans(pos, offset, text, effects, class);
+    }
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         self.deref_mut().text_accel(pos, text, state, class);
     }
@@ -675,6 +923,9 @@ impl<H: DrawHandle> DrawHandle for Box<H> {
     fn progress_bar(&mut self, rect: Rect, dir: Direction, state: InputState, value: f32) {
         self.deref_mut().progress_bar(rect, dir, state, value);
     }
+    fn spinner(&mut self, rect: Rect, state: InputState, phase: f32) {
+        self.deref_mut().spinner(rect, state, phase);
+    }
 }
 
 #[cfg(feature = "stack_dst")]
@@ -700,6 +951,21 @@ where
     fn target_rect(&self) -> Rect {
         self.deref().target_rect()
     }
+    fn quad(&mut self, quad: Quad, col: Colour) {
+        self.deref_mut().quad(quad, col);
+    }
+    fn frame(&mut self, outer: Quad, inner: Quad, col: Colour) {
+        self.deref_mut().frame(outer, inner, col);
+    }
+    fn line(&mut self, p1: Vec2, p2: Vec2, width: f32, col: Colour) {
+        self.deref_mut().line(p1, p2, width, col);
+    }
+    fn circle(&mut self, quad: Quad, inner_radius: f32, col: Colour) {
+        self.deref_mut().circle(quad, inner_radius, col);
+    }
+    fn text_col(&mut self, pos: Coord, text: &dyn TextApi, col: Colour) {
+        self.deref_mut().text_col(pos, text, col);
+    }
     fn outer_frame(&mut self, rect: Rect) {
         self.deref_mut().outer_frame(rect);
     }
@@ -726,6 +992,17 @@ where
     fn text_effects(&mut self, pos: Coord, offset: Offset, text: &dyn TextApi, class: TextClass) {
         self.deref_mut().text_effects(pos, offset, text, class);
     }
+    fn text_col_spans(
+        &mut self,
+        pos: Coord,
+        offset: Offset,
+        text: &dyn TextApi,
+        effects: &[Effect<Option<Colour>>],
+        class: TextClass,
+    ) {
+        self.deref_mut()
+            .text_col_spans(pos, offset, text, effects, class);
+    }
     fn text_accel(&mut self, pos: Coord, text: &Text<AccelString>, state: bool, class: TextClass) {
         self.deref_mut().text_accel(pos, text, state, class);
     }
@@ -777,6 +1054,9 @@ where
     fn progress_bar(&mut self, rect: Rect, dir: Direction, state: InputState, value: f32) {
         self.deref_mut().progress_bar(rect, dir, state, value);
     }
+    fn spinner(&mut self, rect: Rect, state: InputState, phase: f32) {
+        self.deref_mut().spinner(rect, state, phase);
+    }
 }
 
 #[cfg(test)]
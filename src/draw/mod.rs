@@ -98,7 +98,8 @@ pub trait DrawShared {
 ///
 /// Draw operations take place over multiple render passes, identified by a
 /// handle of type [`Pass`]. In general the user only needs to pass this value
-/// into methods as required. [`Draw::add_clip_region`] creates a new [`Pass`].
+/// into methods as required. [`Draw::add_clip_region`] and
+/// [`Draw::push_clip_region`] create a new [`Pass`].
 ///
 /// Each [`Pass`] has an associated depth value which may be used to determine
 /// the result of overlapping draw commands.
@@ -114,8 +115,31 @@ pub trait Draw: Any {
     /// Clip regions are cleared each frame and so must be recreated on demand.
     /// Each region has an associated depth value. The theme is responsible for
     /// assigning depth values.
+    ///
+    /// The `rect` is in physical pixels, as are all [`kas::geom`] types.
+    ///
+    /// The region is clipped only to the window; see also
+    /// [`Draw::push_clip_region`].
     fn add_clip_region(&mut self, rect: Rect, depth: f32) -> Pass;
 
+    /// Push a nested clip region
+    ///
+    /// This is like [`Draw::add_clip_region`], except that `rect` is first
+    /// intersected with the region on top of the clip stack (if any), and the
+    /// new region is pushed onto this stack. Each call must be matched by a
+    /// call to [`Draw::pop_clip_region`] once drawing to the region is done.
+    ///
+    /// The default implementation does not nest regions: it simply calls
+    /// [`Draw::add_clip_region`].
+    fn push_clip_region(&mut self, rect: Rect, depth: f32) -> Pass {
+        self.add_clip_region(rect, depth)
+    }
+
+    /// Pop a clip region pushed by [`Draw::push_clip_region`]
+    ///
+    /// The default implementation does nothing.
+    fn pop_clip_region(&mut self) {}
+
     /// Draw a rectangle of uniform colour
     fn rect(&mut self, pass: Pass, rect: Quad, col: Colour);
 
@@ -133,6 +157,18 @@ pub trait Draw: Any {
 /// If the implementation buffers draw commands, it should draw these
 /// primitives after solid primitives.
 pub trait DrawRounded: Draw {
+    /// Set the anti-aliasing feather width
+    ///
+    /// Edges of rounded shapes are blended over approximately this width, in
+    /// physical pixels; where supported this also applies to shaded shapes.
+    /// A value of `1.0` suits a scale factor of 1; larger values look softer.
+    /// This affects subsequent draw commands.
+    ///
+    /// The default implementation does nothing.
+    fn set_feather(&mut self, feather: f32) {
+        let _ = feather;
+    }
+
     /// Draw a line with rounded ends and uniform colour
     ///
     /// This command draws a line segment between the points `p1` and `p2`.
@@ -220,6 +256,23 @@ pub trait DrawText {
     /// Load resources needed for the next frame
     fn prepare_fonts(&mut self);
 
+    /// Rasterize glyphs ahead of time
+    ///
+    /// Glyphs are usually rasterized and uploaded to the glyph cache the first
+    /// time they are drawn, which may cause a stutter (e.g. when first
+    /// opening a dialog during an animation). This method queues all glyphs
+    /// in `text` at the given `scale` (font height in pixels) for upload with
+    /// the next frame, without drawing anything.
+    ///
+    /// Returns true if any glyphs were queued which had not previously been
+    /// preloaded.
+    ///
+    /// The default implementation does nothing and returns false.
+    fn preload_glyphs(&mut self, text: &str, scale: f32) -> bool {
+        let _ = (text, scale);
+        false
+    }
+
     /// Draw text with a colour
     fn text(
         &mut self,
@@ -6,6 +6,9 @@
 //! Event handling configuration
 
 use super::shortcuts::Shortcuts;
+use super::ScrollDelta;
+use crate::conv::ConvFloat;
+use crate::geom::Offset;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -23,6 +26,8 @@ pub enum ConfigError {
     IoError(#[from] std::io::Error),
     #[error("format not supported: {0}")]
     UnsupportedFormat(ConfigFormat),
+    #[error("invalid value for config field: {0}")]
+    InvalidValue(&'static str),
 }
 
 /// Serialisation formats
@@ -57,17 +62,151 @@ impl Default for ConfigFormat {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Config {
     pub shortcuts: Shortcuts,
+    /// User-adjustable UI scale factor
+    ///
+    /// Theme dimensions and font sizes are multiplied by this factor in
+    /// addition to the monitor's scale factor. Default value: 1.
+    #[cfg_attr(feature = "serde", serde(default = "default_ui_scale"))]
+    pub ui_scale: f32,
+    /// User-adjustable font scale factor
+    ///
+    /// Font sizes are multiplied by this factor (in addition to
+    /// [`Config::ui_scale`]) while other theme dimensions are not, allowing
+    /// larger text without larger controls.
+    ///
+    /// If `None` (the default), the theme's own font scale (e.g. as set by
+    /// `with_font_scale`) is used. If set, this value replaces the theme's
+    /// font scale on start-up.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub font_scale: Option<f32>,
+    /// Distance a press must move before movement is reported
+    ///
+    /// Units are logical pixels. Until a grabbed mouse or touch press moves
+    /// this far from its start position, no [`Event::PressMove`] is sent; this
+    /// prevents small jitters during a click from being treated as a drag.
+    /// Default value: 4.
+    ///
+    /// [`Event::PressMove`]: super::Event::PressMove
+    #[cfg_attr(feature = "serde", serde(default = "default_drag_threshold"))]
+    pub drag_threshold: f32,
+    /// Delay before a held navigation key starts repeating (milliseconds)
+    ///
+    /// Navigation keys (arrows, Page Up/Down, Home/End and Tab) are repeated by
+    /// KAS itself, ignoring the OS's key-repeat, for consistent behaviour
+    /// across platforms. Default value: 500.
+    #[cfg_attr(feature = "serde", serde(default = "default_key_repeat_delay"))]
+    pub key_repeat_delay: u32,
+    /// Interval between repeats of a held navigation key (milliseconds)
+    ///
+    /// If zero, navigation keys do not repeat. Default value: 40.
+    #[cfg_attr(feature = "serde", serde(default = "default_key_repeat_interval"))]
+    pub key_repeat_interval: u32,
+    /// Duration a touch must be held to count as a long-press (milliseconds)
+    ///
+    /// Widgets may treat a long-press like a secondary (right) click, e.g. to
+    /// open a context menu. Default value: 500.
+    #[cfg_attr(feature = "serde", serde(default = "default_long_press_delay"))]
+    pub long_press_delay: u32,
+    /// Timeout for type-ahead find (milliseconds)
+    ///
+    /// Lists and combo boxes select the first item starting with the text
+    /// typed; the text is reset if no key is typed within this time.
+    /// Default value: 1000.
+    #[cfg_attr(feature = "serde", serde(default = "default_type_ahead_timeout"))]
+    pub type_ahead_timeout: u32,
+    /// Speed multiplier for line-based scrolling (mouse wheels)
+    ///
+    /// Default value: 1.
+    #[cfg_attr(feature = "serde", serde(default = "default_scroll_speed"))]
+    pub wheel_scroll_speed: f32,
+    /// Speed multiplier for pixel-based scrolling (touchpads)
+    ///
+    /// Default value: 1.
+    #[cfg_attr(feature = "serde", serde(default = "default_scroll_speed"))]
+    pub touchpad_scroll_speed: f32,
+    /// Use "natural" scrolling (content follows the fingers)
+    ///
+    /// If `None`, scroll deltas are used as reported by the platform, which
+    /// may already apply the user's system preference (as on macOS). If set,
+    /// deltas are reversed whenever this differs from the platform default
+    /// (natural on macOS, traditional elsewhere). Default value: `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub natural_scroll: Option<bool>,
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+fn default_drag_threshold() -> f32 {
+    4.0
+}
+
+fn default_key_repeat_delay() -> u32 {
+    500
+}
+
+fn default_key_repeat_interval() -> u32 {
+    40
+}
+
+fn default_long_press_delay() -> u32 {
+    500
+}
+
+fn default_type_ahead_timeout() -> u32 {
+    1000
+}
+
+fn default_scroll_speed() -> f32 {
+    1.0
 }
 
 impl Default for Config {
     fn default() -> Self {
         let mut shortcuts = Shortcuts::new();
         shortcuts.load_platform_defaults();
-        Config { shortcuts }
+        Config {
+            shortcuts,
+            ui_scale: default_ui_scale(),
+            font_scale: None,
+            drag_threshold: default_drag_threshold(),
+            key_repeat_delay: default_key_repeat_delay(),
+            key_repeat_interval: default_key_repeat_interval(),
+            long_press_delay: default_long_press_delay(),
+            type_ahead_timeout: default_type_ahead_timeout(),
+            wheel_scroll_speed: default_scroll_speed(),
+            touchpad_scroll_speed: default_scroll_speed(),
+            natural_scroll: None,
+        }
     }
 }
 
 impl Config {
+    /// Adjust a scroll delta as reported by the platform
+    ///
+    /// Applies the speed multiplier for the input type (wheel or touchpad)
+    /// and the scroll direction preference.
+    pub fn adjust_scroll(&self, delta: ScrollDelta) -> ScrollDelta {
+        let reverse = match self.natural_scroll {
+            Some(natural) => natural != cfg!(target_os = "macos"),
+            None => false,
+        };
+        let sign = if reverse { -1.0 } else { 1.0 };
+        match delta {
+            ScrollDelta::LineDelta(x, y) => {
+                let f = sign * self.wheel_scroll_speed;
+                ScrollDelta::LineDelta(f * x, f * y)
+            }
+            ScrollDelta::PixelDelta(d) => {
+                let f = sign * self.touchpad_scroll_speed;
+                let x = i32::conv_nearest(f * d.0 as f32);
+                let y = i32::conv_nearest(f * d.1 as f32);
+                ScrollDelta::PixelDelta(Offset(x, y))
+            }
+        }
+    }
+
     fn guess_format(path: &Path) -> ConfigFormat {
         // use == since there is no OsStr literal
         if let Some(ext) = path.extension() {
@@ -86,12 +225,15 @@ impl Config {
     }
 
     /// Read from a path
+    ///
+    /// Fails with [`ConfigError::InvalidValue`] if [`Config::ui_scale`] or
+    /// [`Config::font_scale`] is not a positive, finite number.
     pub fn from_path(path: &Path, mut format: ConfigFormat) -> Result<Self, ConfigError> {
         if format == ConfigFormat::None {
             format = Self::guess_format(path);
         }
 
-        match format {
+        let result: Result<Self, ConfigError> = match format {
             #[cfg(feature = "json")]
             ConfigFormat::Json => {
                 let r = std::io::BufReader::new(std::fs::File::open(path)?);
@@ -103,7 +245,20 @@ impl Config {
                 Ok(serde_yaml::from_reader(r)?)
             }
             _ => Err(ConfigError::UnsupportedFormat(format)),
+        };
+        result.and_then(Config::validate)
+    }
+
+    fn validate(self) -> Result<Self, ConfigError> {
+        // Note: NaN fails both comparisons
+        let is_valid = |x: f32| x > 0.0 && x < f32::INFINITY;
+        if !is_valid(self.ui_scale) {
+            return Err(ConfigError::InvalidValue("ui_scale"));
+        }
+        if !self.font_scale.map(is_valid).unwrap_or(true) {
+            return Err(ConfigError::InvalidValue("font_scale"));
         }
+        Ok(self)
     }
 
     /// Write to a path
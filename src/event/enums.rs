@@ -81,6 +81,14 @@ pub enum MouseButton {
     Other(u8),
 }
 
+/// Describes the input state of a key.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ElementState {
+    Pressed,
+    Released,
+}
+
 impl ModifiersState {
     /// Returns `true` if the shift key is pressed.
     pub fn shift(&self) -> bool {
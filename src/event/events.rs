@@ -8,12 +8,16 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use super::{ElementState, ModifiersState, MouseButton, UpdateHandle, VirtualKeyCode};
 #[allow(unused)]
 use super::{GrabMode, Manager, Response}; // for doc-links
-use super::{MouseButton, UpdateHandle, VirtualKeyCode};
 
 use crate::geom::{Coord, DVec2, Offset};
 use crate::{WidgetId, WindowId};
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
 
 /// Events addressed to a widget
 #[non_exhaustive]
@@ -47,7 +51,38 @@ pub enum Event {
     /// widget has already received [`Event::LostCharFocus`].
     LostSelFocus,
     /// Widget receives a character of text input
+    ///
+    /// This is sent to the widget with character focus. When no widget has
+    /// character focus, it is instead sent (like [`Event::Command`]) to the
+    /// widget with navigation focus or else to the fallback, unless Ctrl or
+    /// Alt is held; this enables type-ahead find in lists.
     ReceivedCharacter(char),
+    /// Raw key press or release
+    ///
+    /// This is sent to the widget with char focus (if any; see
+    /// [`Manager::request_char_focus`]) for each key press and release, before
+    /// any other processing. If the widget handles this event (i.e. does not
+    /// return [`Response::Unhandled`]), a key press is not processed further:
+    /// no [`Event::Command`] is generated. Text input is still delivered
+    /// separately via [`Event::ReceivedCharacter`].
+    ///
+    /// Most widgets should ignore this event; it is intended for things like
+    /// hotkey-capture fields which need the physical key and full modifier
+    /// state.
+    Key {
+        /// Physical key (platform-specific scancode)
+        ///
+        /// This is independent of keyboard layout and modifiers.
+        physical: u32,
+        /// Logical key, if known
+        ///
+        /// This depends on the keyboard layout.
+        logical: Option<VirtualKeyCode>,
+        /// Modifier state at the time of the event
+        modifiers: ModifiersState,
+        /// Whether the key was pressed or released
+        state: ElementState,
+    },
     /// A mouse or touchpad scroll event
     Scroll(ScrollDelta),
     /// A mouse or touch-screen move/zoom/rotate event
@@ -143,6 +178,14 @@ pub enum Event {
     /// A user-defined payload is passed. Interpretation of this payload is
     /// user-defined and unfortunately not type safe.
     HandleUpdate { handle: UpdateHandle, payload: u64 },
+    /// A message sent from another thread
+    ///
+    /// This event is sent to a window's root widget when a message is pushed
+    /// via the toolkit's proxy (e.g. `kas_wgpu::ToolkitProxy::send_to`).
+    /// [`kas::widget::Window`] forwards the event to its content widget. The
+    /// recipient may extract the message with [`Payload::take`]; if it does
+    /// not, it should forward the event to the relevant child.
+    Message(Payload),
     /// Notification that a new popup has been created
     ///
     /// This is sent to the parent of each open popup when a new popup is
@@ -159,6 +202,83 @@ pub enum Event {
     ///
     /// The widget should reply with [`Response::Focus`].
     NavFocus,
+    /// The system appearance changed between light and dark
+    ///
+    /// This is sent to the window's root widget when the OS reports a change,
+    /// where supported by the platform (currently only on Windows); elsewhere
+    /// it is never sent. [`kas::widget::Window`] forwards this event to its
+    /// content. Apps wishing to follow the system may react by changing the
+    /// colour scheme via [`Manager::adjust_theme`].
+    ///
+    /// Other widgets may query [`ManagerState::dark_appearance`] when
+    /// drawing; a redraw is scheduled automatically on change.
+    ///
+    /// [`ManagerState::dark_appearance`]: super::ManagerState::dark_appearance
+    AppearanceChanged { dark: bool },
+    /// The window gained (`true`) or lost (`false`) OS focus
+    ///
+    /// This is sent to the window's root widget only. Other widgets may query
+    /// [`ManagerState::window_has_focus`] when drawing; a redraw is scheduled
+    /// automatically when the focus changes.
+    ///
+    /// [`ManagerState::window_has_focus`]: super::ManagerState::window_has_focus
+    WindowFocus(bool),
+    /// Modifier keys (Shift, Ctrl, Alt, Logo) changed
+    ///
+    /// This is sent to the widgets with selection focus and navigation focus
+    /// as well as to any widget holding a press grab, allowing e.g. a drag
+    /// operation to react to Shift being pressed mid-drag. It is not sent
+    /// to other widgets; these may read the current state via
+    /// [`Manager::modifiers`].
+    ///
+    /// [`Manager::modifiers`]: super::Manager::modifiers
+    ModifiersChanged(ModifiersState),
+}
+
+/// Payload of [`Event::Message`]
+///
+/// This wraps a boxed value of any type. Clones share the same value, which
+/// may be taken (once) by a recipient knowing its type.
+#[derive(Clone)]
+pub struct Payload(Rc<RefCell<Option<Box<dyn Any + Send>>>>);
+
+impl Payload {
+    /// Construct
+    pub fn new(msg: Box<dyn Any + Send>) -> Self {
+        Payload(Rc::new(RefCell::new(Some(msg))))
+    }
+
+    /// True if the payload is present and of type `T`
+    pub fn is<T: Any>(&self) -> bool {
+        match self.0.borrow().as_ref() {
+            Some(msg) => msg.is::<T>(),
+            None => false,
+        }
+    }
+
+    /// Take the payload, if present and of type `T`
+    ///
+    /// On success, the payload is removed (from all clones).
+    pub fn take<T: Any>(&self) -> Option<T> {
+        if !self.is::<T>() {
+            return None;
+        }
+        let msg = self.0.borrow_mut().take().unwrap();
+        msg.downcast::<T>().ok().map(|msg| *msg)
+    }
+}
+
+impl fmt::Debug for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Payload(..)")
+    }
+}
+
+/// Payloads compare equal only if they share the same value
+impl PartialEq for Payload {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 /// Command input ([`Event::Command`])
@@ -360,6 +480,18 @@ impl Command {
             _ => return None,
         })
     }
+
+    /// True for caret/selection movement commands
+    ///
+    /// These commands may be combined with Shift to extend a selection.
+    pub fn is_movement(self) -> bool {
+        use Command::*;
+        match self {
+            Left | Right | Up | Down | WordLeft | WordRight | Home | End | DocHome | DocEnd
+            | PageUp | PageDown => true,
+            _ => false,
+        }
+    }
 }
 
 /// Source of `EventChild::Press`
@@ -416,6 +548,7 @@ impl PressSource {
 
 /// Type used by [`Event::Scroll`]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ScrollDelta {
     /// Scroll a given number of lines
     LineDelta(f32, f32),
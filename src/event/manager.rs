@@ -12,16 +12,15 @@ use linear_map::{set::LinearSet, LinearMap};
 use log::trace;
 use smallvec::SmallVec;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::u16;
 
 use super::*;
 use crate::conv::Cast;
-use crate::geom::Coord;
-#[allow(unused)]
-use crate::WidgetConfig; // for doc-links
+use crate::geom::{Coord, DVec2, Rect};
+use crate::{Layout, WidgetChildren, WidgetConfig, WidgetCore};
 use crate::{ShellWindow, TkAction, Widget, WidgetId, WindowId};
 
 mod mgr_pub;
@@ -50,6 +49,7 @@ struct MouseGrab {
     depress: Option<WidgetId>,
     mode: GrabMode,
     pan_grab: (u16, u16),
+    drag_start: Option<(Coord, i32)>,
 }
 
 #[derive(Clone, Debug)]
@@ -60,6 +60,19 @@ struct TouchGrab {
     coord: Coord,
     mode: GrabMode,
     pan_grab: (u16, u16),
+    drag_start: Option<(Coord, i32)>,
+}
+
+// Start coord and threshold (in physical pixels) of a press which has not yet
+// moved beyond the drag threshold
+fn within_drag_threshold(drag_start: Option<(Coord, i32)>, coord: Coord) -> bool {
+    match drag_start {
+        Some((start, threshold)) => {
+            let d = coord - start;
+            d.0.abs().max(d.1.abs()) <= threshold
+        }
+        None => false,
+    }
 }
 
 const MAX_PAN_GRABS: usize = 2;
@@ -77,8 +90,35 @@ struct PanGrab {
 enum Pending {
     LostCharFocus(WidgetId),
     LostSelFocus(WidgetId),
+    NavFocus(WidgetId),
+}
+
+/// Observer of events delivered to widgets
+///
+/// See [`ManagerState::set_event_observer`]. The observer is passed the root
+/// widget, the target's identifier, the event and the last known mouse
+/// coordinate.
+pub struct EventObserver(Box<dyn FnMut(&dyn WidgetConfig, WidgetId, &Event, Coord)>);
+
+impl EventObserver {
+    /// Construct from a closure
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnMut(&dyn WidgetConfig, WidgetId, &Event, Coord) + 'static,
+    {
+        EventObserver(Box::new(f))
+    }
+}
+
+impl std::fmt::Debug for EventObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EventObserver")
+    }
 }
 
+// Alt-bypass flag and widgets claiming each key (usually only one)
+type AccelLayer = (bool, HashMap<VirtualKeyCode, SmallVec<[WidgetId; 1]>>);
+
 /// Event manager state
 ///
 /// This struct encapsulates window-specific event-handling state and handling.
@@ -106,30 +146,49 @@ pub struct ManagerState {
     sel_focus: Option<WidgetId>,
     nav_focus: Option<WidgetId>,
     nav_fallback: Option<WidgetId>,
+    default_widget: Option<WidgetId>,
+    focus_initialised: bool,
     nav_stack: SmallVec<[u32; 16]>,
     hover: Option<WidgetId>,
     hover_icon: CursorIcon,
     key_depress: LinearMap<u32, WidgetId>,
+    // Held navigation key: scancode, key and time of next repeat
+    key_repeat: Option<(u32, VirtualKeyCode, Instant)>,
     last_mouse_coord: Coord,
+    /// Estimated cursor position, tracked via device motion while the cursor
+    /// is outside the window during a mouse grab. `last_mouse_coord` remains
+    /// the last position reported by the window.
+    mouse_outside: Option<DVec2>,
     last_click_button: MouseButton,
     last_click_repetitions: u32,
     last_click_timeout: Instant,
     mouse_grab: Option<MouseGrab>,
     touch_grab: LinearMap<u64, TouchGrab>,
     pan_grab: SmallVec<[PanGrab; 4]>,
-    accel_stack: Vec<(bool, HashMap<VirtualKeyCode, WidgetId>)>,
-    accel_layers: HashMap<WidgetId, (bool, HashMap<VirtualKeyCode, WidgetId>)>,
+    accel_stack: Vec<AccelLayer>,
+    accel_layers: HashMap<WidgetId, AccelLayer>,
     popups: SmallVec<[(WindowId, kas::Popup); 16]>,
     new_popups: SmallVec<[WidgetId; 16]>,
     popup_removed: SmallVec<[(WidgetId, WindowId); 16]>,
+    widget_errors: HashMap<WidgetId, String>,
+    rects: BTreeMap<WidgetId, Rect>,
+    dark_appearance: Option<bool>,
+    window_focus: bool,
 
     time_start: Instant,
     time_updates: Vec<(Instant, WidgetId)>,
+    frame_updates: Vec<WidgetId>,
+    // Whether a TimerUpdate was sent since the last frame began
+    timer_since_frame: bool,
+    animation_frame: bool,
+    frame_time: Instant,
+    frame_delta: Duration,
     // TODO(opt): consider other containers, e.g. C++ multimap
     // or sorted Vec with binary search yielding a range
     handle_updates: HashMap<UpdateHandle, LinearSet<WidgetId>>,
     pending: SmallVec<[Pending; 8]>,
     action: TkAction,
+    event_observer: Option<EventObserver>,
 }
 
 /// internals
@@ -233,6 +292,21 @@ pub struct Manager<'a> {
     action: TkAction,
 }
 
+// True if `id` is `widget` or a descendant which is currently part of the
+// layout (see Layout::is_child_active)
+fn is_active(mut widget: &dyn WidgetConfig, id: WidgetId) -> bool {
+    while widget.id() != id {
+        match widget.find_child(id) {
+            Some(index) if widget.is_child_active(index) => match widget.get_child(index) {
+                Some(child) => widget = child,
+                None => return false,
+            },
+            _ => return false,
+        }
+    }
+    true
+}
+
 /// Internal methods
 impl<'a> Manager<'a> {
     fn set_hover<W: Widget + ?Sized>(&mut self, widget: &W, w_id: Option<WidgetId>) {
@@ -257,28 +331,41 @@ impl<'a> Manager<'a> {
                 }
             }
             self.state.hover = w_id;
+            self.update_hover_icon(widget);
+        }
+    }
 
-            if let Some(id) = w_id {
-                let mut icon = widget.cursor_icon();
-                let mut widget = widget.as_widget();
-                while let Some(child) = widget.find_child(id) {
-                    widget = widget.get_child(child).unwrap();
+    // Recompute the cursor icon for the hovered widget at the last known
+    // mouse coordinate. Children take precedence over their parents.
+    fn update_hover_icon<W: Widget + ?Sized>(&mut self, widget: &W) {
+        if let Some(id) = self.state.hover {
+            let mut coord = self.state.last_mouse_coord;
+            let mut icon = widget
+                .cursor_icon_at(coord)
+                .unwrap_or_else(|| widget.cursor_icon());
+            let mut widget = widget.as_widget();
+            while let Some(index) = widget.find_child(id) {
+                coord = coord + widget.translation(index);
+                widget = widget.get_child(index).unwrap();
+                if let Some(child_icon) = widget.cursor_icon_at(coord) {
+                    icon = child_icon;
+                } else {
                     let child_icon = widget.cursor_icon();
                     if child_icon != CursorIcon::Default {
                         icon = child_icon;
                     }
                 }
-                if icon != self.state.hover_icon {
-                    self.state.hover_icon = icon;
-                    if self.state.mouse_grab.is_none() {
-                        self.shell.set_cursor_icon(icon);
-                    }
+            }
+            if icon != self.state.hover_icon {
+                self.state.hover_icon = icon;
+                if self.state.mouse_grab.is_none() {
+                    self.shell.set_cursor_icon(icon);
                 }
             }
         }
     }
 
-    fn start_key_event<W>(&mut self, widget: &mut W, vkey: VirtualKeyCode, scancode: u32)
+    pub(crate) fn start_key_event<W>(&mut self, widget: &mut W, vkey: VirtualKeyCode, scancode: u32)
     where
         W: Widget<Msg = VoidMsg> + ?Sized,
     {
@@ -288,6 +375,30 @@ impl<'a> Manager<'a> {
         drop(config);
         let shift = self.state.modifiers.shift();
 
+        if !self.state.modifiers.alt() && self.state.popups.is_empty() {
+            // Dialog keys go to the window's default and cancel actions before
+            // normal focus routing, unless a widget with char focus uses the
+            // Return key itself (e.g. a multi-line edit box).
+            match opt_command {
+                Some(Command::Return) if !self.char_focus_uses_return(widget) => {
+                    if self.send_to_root(widget, Event::Command(Command::Return, shift)) {
+                        return;
+                    }
+                    // Next priority goes to the default widget
+                    if let Some(id) = self.state.default_widget {
+                        self.send_key_action(widget, id, Event::Activate, vkey, scancode);
+                        return;
+                    }
+                }
+                Some(Command::Escape) => {
+                    if self.send_to_root(widget, Event::Command(Command::Escape, shift)) {
+                        return;
+                    }
+                }
+                _ => (),
+            }
+        }
+
         if self.state.char_focus {
             if let Some(id) = self.state.sel_focus {
                 if let Some(cmd) = opt_command {
@@ -343,6 +454,7 @@ impl<'a> Manager<'a> {
         if id_action.is_none() {
             // Next priority goes to accelerator keys when Alt is held or alt_bypass is true
             let mut n = 0;
+            let mut nav_target = None;
             for (i, id) in (self.state.popups.iter().rev())
                 .map(|(_, popup)| popup.parent)
                 .chain(std::iter::once(widget.id()))
@@ -351,14 +463,34 @@ impl<'a> Manager<'a> {
                 if let Some(layer) = self.state.accel_layers.get(&id) {
                     // but only when Alt is held or alt-bypass is enabled:
                     if self.state.modifiers.alt() || layer.0 {
-                        if let Some(id) = layer.1.get(&vkey).cloned() {
-                            id_action = Some((id, Event::Activate));
+                        if let Some(ids) = layer.1.get(&vkey) {
+                            // Skip widgets not currently part of the layout
+                            // (e.g. on an inactive page of a TabView)
+                            let root = widget.as_widget();
+                            let ids: SmallVec<[WidgetId; 4]> = (ids.iter().cloned())
+                                .filter(|id| is_active(root, *id))
+                                .collect();
+                            if ids.len() == 1 {
+                                id_action = Some((ids[0], Event::Activate));
+                            } else if !ids.is_empty() {
+                                // Several widgets claim this key: instead of
+                                // activating, cycle nav focus between them
+                                let nav_focus = self.state.nav_focus;
+                                let index = ids.iter().position(|id| Some(*id) == nav_focus);
+                                let index = index.map(|i| (i + 1) % ids.len()).unwrap_or(0);
+                                nav_target = Some(ids[index]);
+                            } else {
+                                continue;
+                            }
                             n = i;
                             break;
                         }
                     }
                 }
             }
+            if let Some(id) = nav_target {
+                self.set_nav_focus(id);
+            }
 
             // If we had to look below the top pop-up, we should close it
             if n > 0 {
@@ -371,35 +503,115 @@ impl<'a> Manager<'a> {
         }
 
         if let Some((id, event)) = id_action {
-            let is_activate = event == Event::Activate;
-            trace!("Send to {}: {:?}", id, event);
-            match widget.send(self, id, event) {
-                Response::Unhandled(_) if vkey == VK::Escape => {
-                    // When unhandled, the Escape key causes other actions
-                    if let Some(id) = self.state.popups.last().map(|(id, _)| *id) {
-                        self.close_window(id);
-                    } else if self.nav_focus().is_some() {
-                        self.clear_nav_focus();
-                    }
+            self.send_key_action(widget, id, event, vkey, scancode);
+        }
+    }
+
+    // Send the event resulting from a key press
+    fn send_key_action<W>(
+        &mut self,
+        widget: &mut W,
+        id: WidgetId,
+        event: Event,
+        vkey: VirtualKeyCode,
+        scancode: u32,
+    ) where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        let is_activate = event == Event::Activate;
+        trace!("Send to {}: {:?}", id, event);
+        self.observe_event(widget, id, &event);
+        match widget.send(self, id, event) {
+            Response::Unhandled(_) if vkey == VirtualKeyCode::Escape => {
+                // When unhandled, the Escape key causes other actions
+                if let Some(id) = self.state.popups.last().map(|(id, _)| *id) {
+                    self.close_window(id);
+                } else if self.nav_focus().is_some() {
+                    self.clear_nav_focus();
                 }
-                _ => (),
             }
+            _ => (),
+        }
 
-            // Event::Activate causes buttons to be visually depressed
-            if is_activate {
-                for press_id in self.state.key_depress.values().cloned() {
-                    if press_id == id {
-                        return;
-                    }
+        // Event::Activate causes buttons to be visually depressed
+        if is_activate {
+            for press_id in self.state.key_depress.values().cloned() {
+                if press_id == id {
+                    return;
                 }
+            }
+
+            self.state.key_depress.insert(scancode, id);
+            self.redraw(id);
+        }
+    }
+
+    // Start a key press, with synthetic repeats for navigation keys
+    fn start_key_press<W>(&mut self, widget: &mut W, vkey: VirtualKeyCode, scancode: u32)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        if self.state.key_repeat.map(|r| r.0) == Some(scancode) {
+            // OS auto-repeat of a key we repeat ourselves
+            return;
+        }
+        self.start_key_event(widget, vkey, scancode);
 
-                self.state.key_depress.insert(scancode, id);
-                self.redraw(id);
+        use VirtualKeyCode as VK;
+        let config = self.state.config.borrow();
+        let delay = Duration::from_millis(config.key_repeat_delay.into());
+        let enabled = config.key_repeat_interval > 0;
+        drop(config);
+        self.state.key_repeat = match vkey {
+            VK::Left
+            | VK::Right
+            | VK::Up
+            | VK::Down
+            | VK::PageUp
+            | VK::PageDown
+            | VK::Home
+            | VK::End
+            | VK::Tab
+                if enabled =>
+            {
+                Some((scancode, vkey, Instant::now() + delay))
             }
+            _ => None,
+        };
+    }
+
+    // Send Event::Key to the widget with char focus; true if handled
+    fn send_key_event<W>(
+        &mut self,
+        widget: &mut W,
+        physical: u32,
+        logical: Option<VirtualKeyCode>,
+        state: ElementState,
+    ) -> bool
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        let id = match (self.state.char_focus, self.state.sel_focus) {
+            (true, Some(id)) => id,
+            _ => return false,
+        };
+        let event = Event::Key {
+            physical,
+            logical,
+            modifiers: self.state.modifiers,
+            state,
+        };
+        trace!("Send to {}: {:?}", id, event);
+        match widget.send(self, id, event) {
+            Response::Unhandled(_) => false,
+            _ => true,
         }
     }
 
-    fn end_key_event(&mut self, scancode: u32) {
+    pub(crate) fn end_key_event(&mut self, scancode: u32) {
+        if self.state.key_repeat.map(|r| r.0) == Some(scancode) {
+            self.state.key_repeat = None;
+        }
         // We must match scancode not vkey since the latter may have changed due to modifiers
         if let Some(id) = self.state.key_depress.remove(&scancode) {
             self.redraw(id);
@@ -410,6 +622,46 @@ impl<'a> Manager<'a> {
         self.state.mouse_grab.clone()
     }
 
+    /// Get the widget holding the mouse grab, if any (for [`crate::testing`])
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn mouse_grab_id(&self) -> Option<WidgetId> {
+        self.state.mouse_grab.as_ref().map(|grab| grab.start_id)
+    }
+
+    /// End any mouse grab (for [`crate::testing`])
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn release_mouse_grab(&mut self) {
+        if let Some(button) = self.state.mouse_grab.as_ref().map(|grab| grab.button) {
+            self.end_mouse_grab(button);
+        }
+    }
+
+    /// Set the cursor position, as if moved (for [`crate::testing`])
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn set_mouse_coord(&mut self, coord: Coord) {
+        self.state.last_mouse_coord = coord;
+    }
+
+    /// Pass `event`, sent to `id`, to any [`EventObserver`]
+    pub(crate) fn observe_event<W>(&mut self, widget: &W, id: WidgetId, event: &Event)
+    where
+        W: WidgetConfig + ?Sized,
+    {
+        let coord = self.state.last_mouse_coord;
+        if let Some(observer) = self.state.event_observer.as_mut() {
+            (observer.0)(widget.as_widget(), id, event, coord);
+        }
+    }
+
+    /// Get the widget with character focus, if any (for [`crate::testing`])
+    pub(crate) fn char_focus_id(&self) -> Option<WidgetId> {
+        if self.state.char_focus {
+            self.state.sel_focus
+        } else {
+            None
+        }
+    }
+
     fn end_mouse_grab(&mut self, button: MouseButton) {
         if self
             .state
@@ -482,8 +734,28 @@ impl<'a> Manager<'a> {
         }
     }
 
+    // Does the widget with char focus use the Return key itself?
+    fn char_focus_uses_return<W: Widget + ?Sized>(&self, widget: &W) -> bool {
+        (self.char_focus_id())
+            .and_then(|id| widget.find_leaf(id))
+            .map(|w| w.uses_return_key())
+            .unwrap_or(false)
+    }
+
+    // Send an event to the window root (for the window's default and cancel
+    // actions). Returns true if handled.
+    fn send_to_root<W: Widget + ?Sized>(&mut self, widget: &mut W, event: Event) -> bool {
+        let id = widget.id();
+        trace!("Send to {}: {:?}", id, event);
+        match widget.send(self, id, event) {
+            Response::Unhandled(_) => false,
+            _ => true,
+        }
+    }
+
     fn send_event<W: Widget + ?Sized>(&mut self, widget: &mut W, id: WidgetId, event: Event) {
         trace!("Send to {}: {:?}", id, event);
+        self.observe_event(widget, id, &event);
         let _ = widget.send(self, id, event);
     }
 
@@ -531,7 +803,16 @@ impl<'a: 'b, 'b> ConfigureManager<'a, 'b> {
     pub fn next_id(&mut self, old_id: WidgetId) -> WidgetId {
         let id = *self.id;
         *self.id = id.next();
-        self.map.insert(old_id, id);
+        // The default id is used by widgets not yet configured
+        if old_id != WidgetId::default() {
+            // If two widgets had the same id (e.g. one was cloned from the
+            // other), state referring to the old id is ambiguous: we map it to
+            // the default id, which is removed later.
+            self.map
+                .entry(old_id)
+                .and_modify(|new_id| *new_id = WidgetId::default())
+                .or_insert(id);
+        }
         id
     }
 
@@ -10,10 +10,11 @@ use std::time::{Duration, Instant};
 use std::u16;
 
 use super::*;
+use crate::conv::ConvFloat;
 use crate::draw::SizeHandle;
-use crate::geom::Coord;
-#[allow(unused)]
-use crate::WidgetConfig; // for doc-links
+use crate::geom::{Coord, Rect};
+use crate::layout::GridChildInfo;
+use crate::{Layout, WidgetChildren, WidgetConfig, WidgetCore};
 use crate::{ThemeAction, ThemeApi, TkAction, WidgetId, WindowId};
 
 impl<'a> std::ops::BitOrAssign<TkAction> for Manager<'a> {
@@ -81,6 +82,90 @@ impl ManagerState {
         }
         false
     }
+
+    /// Get the validation error associated with a widget, if any
+    ///
+    /// See [`Manager::set_widget_error`].
+    #[inline]
+    pub fn widget_error(&self, w_id: WidgetId) -> Option<&str> {
+        self.widget_errors.get(&w_id).map(|msg| msg.as_str())
+    }
+
+    /// Whether the system appearance is dark, if known
+    ///
+    /// This is `None` until the platform reports the appearance; see
+    /// [`Event::AppearanceChanged`].
+    #[inline]
+    pub fn dark_appearance(&self) -> Option<bool> {
+        self.dark_appearance
+    }
+
+    /// Whether the window currently has OS (keyboard) focus
+    ///
+    /// Windows are assumed to be focused until reported otherwise; see
+    /// [`Event::WindowFocus`].
+    #[inline]
+    pub fn window_has_focus(&self) -> bool {
+        self.window_focus
+    }
+
+    /// Get the rect of a widget, if known
+    ///
+    /// This is the widget's [`WidgetCore::rect`] as of the last time the
+    /// window (or a pop-up) was laid out, or the widget was re-positioned by
+    /// a library widget (e.g. scrolling a `ListView` or dragging a `Splitter`).
+    /// Custom widgets which move children outside of a window resize should
+    /// call [`Manager::update_rect_cache`].
+    ///
+    /// The result is in the widget's own coordinate space, thus is not
+    /// adjusted for scrolling by ancestors; as such it may be passed to
+    /// [`Response::Focus`] to scroll the widget into view.
+    ///
+    /// Returns `None` if `id` is unknown, not yet laid out or not currently
+    /// part of the layout (see [`Layout::is_child_active`](crate::Layout::is_child_active)).
+    ///
+    /// [`WidgetCore::rect`]: crate::WidgetCore::rect
+    #[inline]
+    pub fn rect_of(&self, id: WidgetId) -> Option<Rect> {
+        self.rects.get(&id).cloned()
+    }
+
+    /// Whether the current frame is an animation frame
+    ///
+    /// This is true when the frame being drawn was (at least partly) caused by
+    /// a timer update (see [`Manager::update_on_timer`]) and false when it was
+    /// caused only by input, resizing or other actions. Animations should
+    /// advance their state on [`Event::TimerUpdate`] and not from
+    /// [`kas::Layout::draw`]; this flag merely allows drawing to differ.
+    #[inline]
+    pub fn is_animation_frame(&self) -> bool {
+        self.animation_frame
+    }
+
+    /// Time at which the current frame began
+    #[inline]
+    pub fn frame_time(&self) -> Instant {
+        self.frame_time
+    }
+
+    /// Time elapsed between the start of the previous frame and this frame
+    ///
+    /// For the first frame, this is measured from construction of the
+    /// [`ManagerState`].
+    #[inline]
+    pub fn frame_delta(&self) -> Duration {
+        self.frame_delta
+    }
+
+    /// Iterate over all widgets with a validation error
+    ///
+    /// Items are `(id, message)` pairs in arbitrary order. A form may use this
+    /// to display a summary of errors.
+    pub fn widget_errors(&self) -> impl Iterator<Item = (WidgetId, &str)> {
+        self.widget_errors
+            .iter()
+            .map(|(id, msg)| (*id, msg.as_str()))
+    }
 }
 
 /// Public API (around toolkit and shell functionality)
@@ -91,6 +176,70 @@ impl<'a> Manager<'a> {
         self.state.modifiers
     }
 
+    /// Whether the window currently has OS (keyboard) focus
+    ///
+    /// See [`ManagerState::window_has_focus`].
+    #[inline]
+    pub fn window_has_focus(&self) -> bool {
+        self.state.window_focus
+    }
+
+    /// Time elapsed between the start of the previous frame and this frame
+    ///
+    /// See [`ManagerState::frame_delta`].
+    #[inline]
+    pub fn frame_delta(&self) -> Duration {
+        self.state.frame_delta
+    }
+
+    /// Get the rect of a widget, if known
+    ///
+    /// See [`ManagerState::rect_of`].
+    #[inline]
+    pub fn rect_of(&self, id: WidgetId) -> Option<Rect> {
+        self.state.rect_of(id)
+    }
+
+    /// Record the rect of `widget` and all active descendants
+    ///
+    /// This updates the values returned by [`Manager::rect_of`], forgetting
+    /// the rects of inactive descendants (see [`Layout::is_child_active`](crate::Layout::is_child_active)).
+    /// It is called automatically after resizing a window; widgets which later
+    /// move or switch their children (other than by scrolling offset) should
+    /// call it afterwards.
+    pub fn update_rect_cache(&mut self, widget: &dyn WidgetConfig) {
+        fn record(rects: &mut BTreeMap<WidgetId, Rect>, widget: &dyn WidgetConfig) {
+            for index in 0..widget.num_children() {
+                if widget.is_child_active(index) {
+                    if let Some(child) = widget.get_child(index) {
+                        record(rects, child);
+                    }
+                }
+            }
+            rects.insert(widget.id(), widget.rect());
+        }
+
+        let rects = &mut self.state.rects;
+        let range = widget.first_id()..=widget.id();
+        let stale: Vec<WidgetId> = rects.range(range).map(|(id, _)| *id).collect();
+        for id in stale {
+            rects.remove(&id);
+        }
+        record(rects, widget);
+    }
+
+    /// Get the configured delay before a touch press becomes a long-press
+    pub fn long_press_delay(&self) -> Duration {
+        let delay = self.state.config.borrow().long_press_delay;
+        Duration::from_millis(delay.into())
+    }
+
+    /// Get the configured timeout for type-ahead find
+    pub fn type_ahead_timeout(&self) -> Duration {
+        let timeout = self.state.config.borrow().type_ahead_timeout;
+        Duration::from_millis(timeout.into())
+    }
+
     /// Schedule an update
     ///
     /// Widgets requiring animation should schedule an update; as a result,
@@ -130,6 +279,28 @@ impl<'a> Manager<'a> {
         self.state.time_updates.sort_by(|a, b| b.cmp(a)); // reverse sort
     }
 
+    /// Request (or cancel) updates on every frame
+    ///
+    /// While enabled, [`Event::TimerUpdate`] is sent to `w_id` once
+    /// immediately before each frame is drawn and the window is redrawn
+    /// continuously (see [`kas::Window::continuous_redraw`]). Use
+    /// [`Manager::frame_delta`] to advance animations by the time elapsed.
+    ///
+    /// Updates are paused while the window does not have focus. Unlike
+    /// [`Manager::update_on_timer`], this remains in effect until cancelled.
+    pub fn update_on_frame(&mut self, w_id: WidgetId, enable: bool) {
+        trace!("Manager::update_on_frame: {} for {}", enable, w_id);
+        let updates = &mut self.state.frame_updates;
+        if enable {
+            if !updates.contains(&w_id) {
+                updates.push(w_id);
+                self.send_action(TkAction::REDRAW);
+            }
+        } else {
+            updates.retain(|id| *id != w_id);
+        }
+    }
+
     /// Subscribe to an update handle
     ///
     /// All widgets subscribed to an update handle will be sent
@@ -255,6 +426,11 @@ impl<'a> Manager<'a> {
     ///
     /// All widgets subscribed to the given [`UpdateHandle`], across all
     /// windows, will receive an update.
+    ///
+    /// Updates are coalesced: the update is delivered after the current event
+    /// has been handled, and if the same handle is triggered multiple times
+    /// before then, subscribed widgets receive [`Event::HandleUpdate`] only
+    /// once, with the most recent `payload`.
     #[inline]
     pub fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
         debug!("trigger_update: handle={:?}, payload={}", handle, payload);
@@ -282,6 +458,14 @@ impl<'a> Manager<'a> {
         self.shell.adjust_theme(&mut f);
     }
 
+    /// Adjust the event configuration
+    ///
+    /// Changes (for example to scroll speed or direction) apply immediately to
+    /// all windows sharing this configuration.
+    pub fn adjust_config<F: FnOnce(&mut Config) -> T, T>(&mut self, f: F) -> T {
+        f(&mut self.state.config.borrow_mut())
+    }
+
     /// Access a [`SizeHandle`]
     pub fn size_handle<F: FnMut(&mut dyn SizeHandle) -> T, T>(&mut self, mut f: F) -> T {
         let mut result = None;
@@ -311,6 +495,25 @@ impl<'a> Manager<'a> {
         }
     }
 
+    /// Set the default widget of the window
+    ///
+    /// When the `Return` or `Enter` key is pressed and no pop-up is open, the
+    /// default widget receives [`Event::Activate`], taking priority over the
+    /// widget with navigation focus. This is typically a dialog's "OK" button.
+    /// The window's default action (see
+    /// [`kas::widget::Window::set_default_action`]) takes priority over the
+    /// default widget, and a widget with character focus which uses the key
+    /// (see [`WidgetConfig::uses_return_key`]) receives it exclusively.
+    ///
+    /// Only one widget can be the default, and the *first* to set itself wins.
+    /// This should only be called from [`WidgetConfig::configure`].
+    pub fn set_default_widget(&mut self, id: WidgetId) {
+        if self.state.default_widget.is_none() {
+            debug!("Manager: default_widget = {}", id);
+            self.state.default_widget = Some(id);
+        }
+    }
+
     /// Add a new accelerator key layer and make it current
     ///
     /// This method affects the behaviour of [`Manager::add_accel_keys`] by
@@ -371,6 +574,10 @@ impl<'a> Manager<'a> {
     /// The top-most active layer gets first priority in matching input, but
     /// does not block previous layers.
     ///
+    /// If multiple widgets in a layer use the same key, pressing the key does
+    /// not activate any of them but instead cycles navigation focus between
+    /// them (allowing activation via `Space` or `Return`).
+    ///
     /// This should only be called from [`WidgetConfig::configure`].
     // TODO(type safety): consider only implementing on ConfigureManager
     #[inline]
@@ -378,7 +585,10 @@ impl<'a> Manager<'a> {
         if !self.read_only {
             if let Some(last) = self.state.accel_stack.last_mut() {
                 for key in keys {
-                    last.1.insert(*key, id);
+                    let ids = last.1.entry(*key).or_default();
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
                 }
             }
         }
@@ -445,6 +655,9 @@ impl<'a> Manager<'a> {
 
         let start_id = id;
         let mut pan_grab = (u16::MAX, 0);
+        let drag_threshold = self.state.config.borrow().drag_threshold;
+        let drag_threshold = self.size_handle(|sh| sh.scale_factor()) * drag_threshold;
+        let drag_start = Some((coord, i32::conv_nearest(drag_threshold)));
         match source {
             PressSource::Mouse(button, repetitions) => {
                 if self.state.mouse_grab.is_some() {
@@ -461,6 +674,7 @@ impl<'a> Manager<'a> {
                     depress: Some(id),
                     mode,
                     pan_grab,
+                    drag_start,
                 });
                 if let Some(icon) = cursor {
                     self.shell.set_cursor_icon(icon);
@@ -483,6 +697,7 @@ impl<'a> Manager<'a> {
                         coord,
                         mode,
                         pan_grab,
+                        drag_start,
                     },
                 );
             }
@@ -535,6 +750,32 @@ impl<'a> Manager<'a> {
         self.state.send_action(TkAction::REDRAW);
     }
 
+    /// Set or clear the validation error of a widget
+    ///
+    /// While an error is set, [`WidgetConfig::input_state`] reports
+    /// [`InputState::error`] for the widget, which themes usually draw with
+    /// error styling; the message is available via
+    /// [`ManagerState::widget_error`] for display as inline text or a
+    /// tool-tip. Pass `None` to clear the error once the input validates.
+    ///
+    /// Errors persist across reconfigures but are dropped when the widget is
+    /// removed.
+    ///
+    /// [`InputState::error`]: crate::draw::InputState::error
+    pub fn set_widget_error(&mut self, id: WidgetId, error: Option<String>) {
+        match error {
+            Some(msg) => self.state.widget_errors.insert(id, msg),
+            None => self.state.widget_errors.remove(&id),
+        };
+        self.redraw(id);
+    }
+
+    /// Get the validation error of a widget, if any
+    #[inline]
+    pub fn widget_error(&self, id: WidgetId) -> Option<&str> {
+        self.state.widget_error(id)
+    }
+
     /// Get the current keyboard navigation focus, if any
     ///
     /// This is the widget selected by navigating the UI with the Tab key.
@@ -556,8 +797,11 @@ impl<'a> Manager<'a> {
     ///
     /// [`WidgetConfig::key_nav`] *should* return true for the given widget,
     /// otherwise navigation behaviour may not be correct.
+    ///
+    /// The widget is sent [`Event::NavFocus`] after event handling completes.
     pub fn set_nav_focus(&mut self, id: WidgetId) {
         self.redraw(id);
+        self.state.pending.push(Pending::NavFocus(id));
         self.state.nav_focus = Some(id);
         self.state.nav_stack.clear();
         trace!("Manager: nav_focus = Some({})", id);
@@ -622,7 +866,24 @@ impl<'a> Manager<'a> {
             }
         }
 
-        // Progresses to the first child (or last if reverse).
+        // Find the first active child from index `from` towards `to`
+        // (inclusive), in either direction. See Layout::is_child_active.
+        fn active_child(widget: &dyn WidgetConfig, from: usize, to: usize) -> Option<usize> {
+            let mut index = from;
+            loop {
+                if widget.is_child_active(index) {
+                    return Some(index);
+                } else if index == to {
+                    return None;
+                } else if index < to {
+                    index += 1;
+                } else {
+                    index -= 1;
+                }
+            }
+        }
+
+        // Progresses to the first active child (or last if reverse).
         // Returns true if a child is found.
         // Breaks to given lifetime on error.
         macro_rules! do_child {
@@ -631,19 +892,24 @@ impl<'a> Manager<'a> {
                 if $widget.is_disabled() || range.1 == std::usize::MAX {
                     false
                 } else {
-                    // We have a child; the first is range.0 unless reverse
-                    let index = match reverse {
-                        false => range.0,
-                        true => range.1,
-                    };
-                    let new = match $widget.get_child(index) {
-                        None => break $lt,
-                        Some(w) => w,
+                    // The first child is range.0 unless reverse
+                    let (first, last) = match reverse {
+                        false => range,
+                        true => (range.1, range.0),
                     };
-                    $nav_stack.push(index.cast());
-                    $widget_stack.push($widget);
-                    $widget = new;
-                    true
+                    match active_child($widget, first, last) {
+                        None => false,
+                        Some(index) => {
+                            let new = match $widget.get_child(index) {
+                                None => break $lt,
+                                Some(w) => w,
+                            };
+                            $nav_stack.push(index.cast());
+                            $widget_stack.push($widget);
+                            $widget = new;
+                            true
+                        }
+                    }
                 }
             }};
         }
@@ -671,18 +937,16 @@ impl<'a> Manager<'a> {
                     std::mem::swap(&mut range.0, &mut range.1);
                 }
 
-                // Look for next sibling
-                let have_sibling = match reverse {
-                    false if index < range.1 => {
-                        index += 1;
-                        true
-                    }
-                    true if range.0 < index => {
-                        index -= 1;
-                        true
-                    }
-                    _ => false,
+                // Look for next active sibling
+                let sibling = match reverse {
+                    false if index < range.1 => active_child($widget, index + 1, range.1),
+                    true if range.0 < index => active_child($widget, index - 1, range.0),
+                    _ => None,
                 };
+                let have_sibling = sibling.is_some();
+                if let Some(i) = sibling {
+                    index = i;
+                }
 
                 if have_sibling {
                     let new = match $widget.get_child(index) {
@@ -744,4 +1008,78 @@ impl<'a> Manager<'a> {
 
         false
     }
+
+    /// Move the keyboard navigation focus spatially within a grid
+    ///
+    /// This is used by grid layouts to handle arrow keys not consumed by the
+    /// child with nav focus. `cells` lists the grid position of each child of
+    /// `widget` (by child index) and `from` is the index of the child
+    /// containing the current nav focus.
+    ///
+    /// The target is the nearest child in the direction of `cmd` overlapping
+    /// `from` on the other axis and containing some widget which accepts nav
+    /// focus. If there is none and `wrap` is true, the search restarts from
+    /// the opposite edge of the grid.
+    ///
+    /// On success, returns the `Rect` of the newly focussed widget (which
+    /// should be passed on via [`Response::Focus`]).
+    pub fn grid_nav_focus(
+        &mut self,
+        widget: &dyn WidgetConfig,
+        cells: &[GridChildInfo],
+        from: usize,
+        cmd: Command,
+        wrap: bool,
+    ) -> Option<Rect> {
+        fn nav_target(w: &dyn WidgetConfig) -> Option<&dyn WidgetConfig> {
+            if w.is_disabled() {
+                return None;
+            } else if w.key_nav() {
+                return Some(w);
+            }
+            let (a, b) = w.spatial_range();
+            if b == std::usize::MAX {
+                return None;
+            }
+            let n = if a <= b { b - a } else { a - b };
+            (0..=n)
+                .map(|k| if a <= b { a + k } else { a - k })
+                .filter(|i| w.is_child_active(*i))
+                .filter_map(|i| w.get_child(i))
+                .find_map(nav_target)
+        }
+
+        let f = cells.get(from)?;
+        let overlaps = |a0: u32, a1: u32, b0: u32, b1: u32| a0 < b1 && b0 < a1;
+        let diff = |a: u32, b: u32| i64::from(a) - i64::from(b);
+
+        // Candidates are (distance along axis, cross-axis offset, index).
+        // Cells behind `from` have non-positive distance and are only
+        // considered when wrapping, starting from the far edge.
+        let mut candidates: SmallVec<[(i64, i64, usize); 16]> = SmallVec::new();
+        for (i, c) in cells.iter().enumerate() {
+            let rows = overlaps(c.row, c.row_end, f.row, f.row_end);
+            let cols = overlaps(c.col, c.col_end, f.col, f.col_end);
+            let (dist, cross) = match cmd {
+                Command::Right if rows => (diff(c.col, f.col), diff(c.row, f.row)),
+                Command::Left if rows => (diff(f.col, c.col), diff(c.row, f.row)),
+                Command::Down if cols => (diff(c.row, f.row), diff(c.col, f.col)),
+                Command::Up if cols => (diff(f.row, c.row), diff(c.col, f.col)),
+                _ => continue,
+            };
+            if i != from && (dist > 0 || wrap) && widget.is_child_active(i) {
+                candidates.push((dist, cross.abs(), i));
+            }
+        }
+        candidates.sort_by_key(|(dist, cross, _)| (*dist <= 0, *dist, *cross));
+
+        for (_, _, i) in candidates {
+            if let Some(target) = widget.get_child(i).and_then(nav_target) {
+                let rect = target.rect();
+                self.set_nav_focus(target.id());
+                return Some(rect);
+            }
+        }
+        None
+    }
 }
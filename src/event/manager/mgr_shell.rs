@@ -7,16 +7,16 @@
 
 use log::*;
 use smallvec::SmallVec;
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
 use std::mem::swap;
 use std::time::{Duration, Instant};
 
 use super::*;
 use crate::conv::Conv;
 use crate::geom::{Coord, DVec2, Offset};
-#[allow(unused)]
-use crate::WidgetConfig; // for doc-links
-use crate::{ShellWindow, TkAction, Widget, WidgetId};
+use crate::WidgetConfig;
+use crate::{ShellWindow, TkAction, Widget, WidgetChildren, WidgetId};
 
 // TODO: this should be configurable or derived from the system
 const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_secs(1);
@@ -38,10 +38,14 @@ impl ManagerState {
             nav_focus: None,
             nav_fallback: None,
             nav_stack: SmallVec::new(),
+            default_widget: None,
+            focus_initialised: false,
             hover: None,
             hover_icon: CursorIcon::Default,
             key_depress: Default::default(),
+            key_repeat: None,
             last_mouse_coord: Coord::ZERO,
+            mouse_outside: None,
             last_click_button: FAKE_MOUSE_BUTTON,
             last_click_repetitions: 0,
             last_click_timeout: Instant::now(), // unimportant value
@@ -53,12 +57,22 @@ impl ManagerState {
             popups: Default::default(),
             new_popups: Default::default(),
             popup_removed: Default::default(),
+            widget_errors: HashMap::new(),
+            rects: BTreeMap::new(),
+            dark_appearance: None,
+            window_focus: true,
 
             time_start: Instant::now(),
             time_updates: vec![],
+            frame_updates: vec![],
+            timer_since_frame: false,
+            animation_frame: false,
+            frame_time: Instant::now(),
+            frame_delta: Duration::from_secs(0),
             handle_updates: HashMap::new(),
             pending: SmallVec::new(),
             action: TkAction::empty(),
+            event_observer: None,
         }
     }
 
@@ -73,7 +87,7 @@ impl ManagerState {
     /// renamed and removed widgets.
     pub fn configure<W>(&mut self, shell: &mut dyn ShellWindow, widget: &mut W)
     where
-        W: Widget<Msg = VoidMsg> + ?Sized,
+        W: Widget + ?Sized,
     {
         debug!("Manager::configure");
         self.action = TkAction::empty();
@@ -88,6 +102,8 @@ impl ManagerState {
         self.accel_stack.clear();
         self.accel_layers.clear();
         self.nav_fallback = None;
+        self.default_widget = None;
+        self.rects.clear();
         // These we merge later:
         let mut old_time_updates = Default::default();
         swap(&mut self.time_updates, &mut old_time_updates);
@@ -120,9 +136,18 @@ impl ManagerState {
         // Update input state to account for renamed widgets. Assumption: none
         // of this state is adjusted within widget configure methods.
         // TODO(safety): ensure these fields cannot be updated by configure?
+        // Ambiguous ids are dropped: it is safer to clear state than to risk
+        // applying it to the wrong widget.
+        renames.retain(|_, new_id| *new_id != WidgetId::default());
 
         self.sel_focus = self.sel_focus.and_then(|id| renames.get(&id).cloned());
+        if self.sel_focus.is_none() {
+            self.char_focus = false;
+        }
         self.nav_focus = self.nav_focus.and_then(|id| renames.get(&id).cloned());
+        if self.nav_focus.is_none() {
+            self.nav_stack.clear();
+        }
         self.mouse_grab = self.mouse_grab.as_ref().and_then(|grab| {
             renames.get(&grab.start_id).map(|id| MouseGrab {
                 button: grab.button,
@@ -131,6 +156,7 @@ impl ManagerState {
                 depress: grab.depress.and_then(|id| renames.get(&id).cloned()),
                 mode: grab.mode,
                 pan_grab: grab.pan_grab,
+                drag_start: grab.drag_start,
             })
         });
 
@@ -165,6 +191,34 @@ impl ManagerState {
             }
         });
 
+        // Pop-ups whose widget no longer exists are closed
+        let mut i = 0;
+        while i < self.popups.len() {
+            let popup = &mut self.popups[i].1;
+            let ids = (renames.get(&popup.id), renames.get(&popup.parent));
+            if let (Some(id), Some(parent)) = ids {
+                popup.id = *id;
+                popup.parent = *parent;
+                i += 1;
+            } else {
+                let (window_id, _) = self.popups.remove(i);
+                shell.close_window(window_id);
+            }
+        }
+        self.new_popups
+            .retain(|id| renames.get(id).map(|new_id| *id = *new_id).is_some());
+        self.popup_removed
+            .retain(|(id, _)| renames.get(id).map(|new_id| *id = *new_id).is_some());
+
+        self.frame_updates
+            .retain(|id| renames.get(id).map(|new_id| *id = *new_id).is_some());
+
+        let old_widget_errors = std::mem::take(&mut self.widget_errors);
+        self.widget_errors = old_widget_errors
+            .into_iter()
+            .filter_map(|(id, msg)| renames.get(&id).map(|id| (*id, msg)))
+            .collect();
+
         // We have to handle time_updates and handle_updates carefully since
         // these may be set during configure, *and* may carry old state forward
         // which must be renamed.
@@ -217,7 +271,34 @@ impl ManagerState {
                     false
                 }
             }
+            Pending::NavFocus(id) => {
+                if let Some(new_id) = renames.get(id) {
+                    *item = Pending::NavFocus(*new_id);
+                    true
+                } else {
+                    false
+                }
+            }
         });
+
+        if !self.focus_initialised {
+            self.focus_initialised = true;
+            if self.nav_focus.is_none() {
+                // Set focus via Manager::set_nav_focus for the NavFocus event
+                // and redraw. Note that the window's default and cancel
+                // actions still take priority for Return and Escape.
+                self.with(shell, |mgr| {
+                    let id = find_default_focus(widget.as_widget()).or_else(|| {
+                        mgr.next_nav_focus(widget.as_widget(), false);
+                        mgr.state.nav_focus
+                    });
+                    if let Some(id) = id {
+                        mgr.set_nav_focus(id);
+                    }
+                });
+                trace!("Manager: initial nav_focus = {:?}", self.nav_focus);
+            }
+        }
     }
 
     /// Update the widgets under the cursor and touch events
@@ -256,7 +337,41 @@ impl ManagerState {
 
     /// Get the next resume time
     pub fn next_resume(&self) -> Option<Instant> {
-        self.time_updates.last().map(|time| time.0)
+        let time = self.time_updates.last().map(|time| time.0);
+        match (time, self.key_repeat.map(|r| r.2)) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Set (or with `None`, remove) an observer of events
+    ///
+    /// The observer is called before each event is delivered to a widget by
+    /// the event manager, replacing any previous observer.
+    pub fn set_event_observer(&mut self, observer: Option<EventObserver>) {
+        self.event_observer = observer;
+    }
+
+    /// Whether the window should be redrawn continuously
+    ///
+    /// This is true when either [`kas::Window::continuous_redraw`] is true
+    /// for `window` or some widget requested per-frame updates (see
+    /// [`Manager::update_on_frame`]), but only while the window has focus.
+    pub fn continuous_redraw(&self, window: &dyn kas::Window) -> bool {
+        self.window_focus && (window.continuous_redraw() || !self.frame_updates.is_empty())
+    }
+
+    /// Begin a new frame
+    ///
+    /// The shell should call this immediately before drawing a window. It
+    /// updates the frame information reported by
+    /// [`ManagerState::is_animation_frame`] and [`ManagerState::frame_delta`].
+    pub fn begin_frame(&mut self) {
+        let now = Instant::now();
+        self.animation_frame = self.timer_since_frame;
+        self.timer_since_frame = false;
+        self.frame_delta = now - self.frame_time;
+        self.frame_time = now;
     }
 
     /// Set an action
@@ -363,10 +478,12 @@ impl ManagerState {
         // make mgr const, but merely pretend it is in the public API.
         mgr.read_only = true;
 
-        for item in mgr.state.pending.pop() {
+        let pending = std::mem::take(&mut mgr.state.pending);
+        for item in pending {
             let (id, event) = match item {
                 Pending::LostCharFocus(id) => (id, Event::LostCharFocus),
                 Pending::LostSelFocus(id) => (id, Event::LostSelFocus),
+                Pending::NavFocus(id) => (id, Event::NavFocus),
             };
             mgr.send_event(widget, id, event);
         }
@@ -380,8 +497,27 @@ impl ManagerState {
 /// Shell API
 #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
 impl<'a> Manager<'a> {
+    /// Send per-frame updates
+    ///
+    /// The shell should call this after [`ManagerState::begin_frame`] and
+    /// before drawing when [`ManagerState::continuous_redraw`] is true. Each
+    /// widget registered via [`Manager::update_on_frame`] is sent
+    /// [`Event::TimerUpdate`].
+    pub fn update_frame<W>(&mut self, widget: &mut W)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        self.state.animation_frame = true;
+        for id in self.state.frame_updates.clone() {
+            self.send_event(widget, id, Event::TimerUpdate);
+        }
+    }
+
     /// Update widgets due to timer
-    pub fn update_timer<W: Widget + ?Sized>(&mut self, widget: &mut W) {
+    pub fn update_timer<W>(&mut self, widget: &mut W)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
         let now = Instant::now();
 
         // assumption: time_updates are sorted in reverse order
@@ -391,10 +527,20 @@ impl<'a> Manager<'a> {
             }
 
             let update = self.state.time_updates.pop().unwrap();
+            self.state.timer_since_frame = true;
             self.send_event(widget, update.1, Event::TimerUpdate);
         }
 
         self.state.time_updates.sort_by(|a, b| b.cmp(a)); // reverse sort
+
+        if let Some((scancode, vkey, time)) = self.state.key_repeat {
+            if time <= now {
+                let interval = self.state.config.borrow().key_repeat_interval;
+                let next = now + Duration::from_millis(interval.into());
+                self.state.key_repeat = Some((scancode, vkey, next));
+                self.start_key_event(widget, vkey, scancode);
+            }
+        }
     }
 
     /// Update widgets due to handle
@@ -413,6 +559,67 @@ impl<'a> Manager<'a> {
         }
     }
 
+    /// Update widgets due to a queue of handles
+    ///
+    /// Each widget receives at most one [`Event::HandleUpdate`]: a widget
+    /// subscribed to several queued handles is updated only for the first.
+    pub fn update_handles<W: Widget + ?Sized>(&mut self, widget: &mut W, updates: &UpdateQueue) {
+        let mut sent = LinearSet::new();
+        for (handle, payload) in updates.iter() {
+            // NOTE: to avoid borrow conflict, we must clone values!
+            if let Some(mut values) = self.state.handle_updates.get(&handle).cloned() {
+                for w_id in values.drain() {
+                    if sent.insert(w_id) {
+                        let event = Event::HandleUpdate { handle, payload };
+                        self.send_event(widget, w_id, event);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Send a message to the window's root widget
+    ///
+    /// The message is delivered as [`Event::Message`].
+    pub fn send_message<W>(&mut self, widget: &mut W, msg: Box<dyn Any + Send>)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        let id = widget.id();
+        self.send_event(widget, id, Event::Message(Payload::new(msg)));
+    }
+
+    /// Handle raw mouse motion
+    ///
+    /// Not all platforms report cursor motion outside of the window, even
+    /// while a mouse button is held. The shell should forward device motion
+    /// (e.g. winit's `DeviceEvent::MouseMotion`) here; it is used only to
+    /// continue a mouse grab while the cursor is outside the window, and is
+    /// otherwise ignored.
+    ///
+    /// Device motion is not subject to pointer acceleration, thus is only an
+    /// approximation of cursor motion. It is accumulated separately from
+    /// positions reported by the window; once the cursor re-enters the
+    /// window, the next reported position replaces the estimate.
+    pub fn handle_mouse_motion<W>(&mut self, widget: &mut W, delta: DVec2)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        let last = match self.state.mouse_outside {
+            Some(pos) => pos,
+            None => return,
+        };
+        if let Some(grab) = self.mouse_grab() {
+            let pos = last + delta;
+            self.state.mouse_outside = Some(pos);
+            let coord = Coord::from(pos);
+            let delta = coord - Coord::from(last);
+            if delta != Offset::ZERO {
+                self.mouse_grab_move(widget, grab, coord, None, delta);
+            }
+        }
+    }
+
     /// Handle a winit `WindowEvent`.
     ///
     /// Note that some event types are not handled, since for these
@@ -437,13 +644,21 @@ impl<'a> Manager<'a> {
             HoveredFileCancelled => ,
             */
             ReceivedCharacter(c) => {
-                if let Some(id) = self.state.sel_focus {
+                // Filter out control codes (Unicode 5.11). These may be
+                // generated from combinations such as Ctrl+C by some other
+                // layer. We use our own shortcut system instead.
+                if c >= '\u{20}' && (c < '\u{7f}' || c > '\u{9f}') {
+                    let event = Event::ReceivedCharacter(c);
                     if self.state.char_focus {
-                        // Filter out control codes (Unicode 5.11). These may be
-                        // generated from combinations such as Ctrl+C by some other
-                        // layer. We use our own shortcut system instead.
-                        if c >= '\u{20}' && (c < '\u{7f}' || c > '\u{9f}') {
-                            let event = Event::ReceivedCharacter(c);
+                        if let Some(id) = self.state.sel_focus {
+                            self.send_event(widget, id, event);
+                        }
+                    } else if !self.state.modifiers.ctrl() && !self.state.modifiers.alt() {
+                        // Without char focus, characters are used for type-ahead
+                        let id = (self.state.nav_focus)
+                            .or_else(|| self.state.popups.last().map(|popup| popup.1.parent))
+                            .or(self.state.nav_fallback);
+                        if let Some(id) = id {
                             self.send_event(widget, id, event);
                         }
                     }
@@ -454,20 +669,61 @@ impl<'a> Manager<'a> {
                 is_synthetic,
                 ..
             } => {
-                if input.state == ElementState::Pressed && !is_synthetic {
+                let handled = !is_synthetic
+                    && self.send_key_event(
+                        widget,
+                        input.scancode,
+                        input.virtual_keycode,
+                        input.state,
+                    );
+                if input.state == ElementState::Pressed && !is_synthetic && !handled {
                     if let Some(vkey) = input.virtual_keycode {
-                        self.start_key_event(widget, vkey, input.scancode);
+                        self.start_key_press(widget, vkey, input.scancode);
                     }
                 } else if input.state == ElementState::Released {
                     self.end_key_event(input.scancode);
                 }
             }
+            Focused(focus) => {
+                if !focus {
+                    // Key release events may be lost while unfocused
+                    self.state.key_repeat = None;
+                }
+                if focus != self.state.window_focus {
+                    self.state.window_focus = focus;
+                    self.state.send_action(TkAction::REDRAW);
+                    self.send_event(widget, widget.id(), Event::WindowFocus(focus));
+                }
+            }
             ModifiersChanged(state) => {
                 if state.alt() != self.state.modifiers.alt() {
                     // This controls drawing of accelerator key indicators
                     self.state.send_action(TkAction::REDRAW);
                 }
                 self.state.modifiers = state;
+
+                // Notify widgets with focus or a press grab
+                let mut ids = SmallVec::<[WidgetId; 4]>::new();
+                let grabs = self.state.touch_grab.values().map(|grab| grab.start_id);
+                let iter = (self.state.sel_focus.iter().cloned())
+                    .chain(self.state.nav_focus.iter().cloned())
+                    .chain(self.state.mouse_grab.as_ref().map(|grab| grab.start_id))
+                    .chain(grabs);
+                for id in iter {
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
+                }
+                for id in ids {
+                    self.send_event(widget, id, Event::ModifiersChanged(state));
+                }
+            }
+            ThemeChanged(theme) => {
+                let dark = theme == winit::window::Theme::Dark;
+                self.state.dark_appearance = Some(dark);
+                // Widgets may query dark_appearance when drawing
+                self.state.send_action(TkAction::REDRAW);
+                self.send_event(widget, widget.id(), Event::AppearanceChanged { dark });
             }
             CursorMoved { position, .. } => {
                 self.state.last_click_button = FAKE_MOUSE_BUTTON;
@@ -475,24 +731,23 @@ impl<'a> Manager<'a> {
 
                 // Update hovered widget
                 let cur_id = widget.find_id(coord);
-                let delta = coord - self.state.last_mouse_coord;
-                self.set_hover(widget, cur_id);
+                // If motion was estimated outside the window, report the
+                // difference from the last estimate
+                let last = match self.state.mouse_outside.take() {
+                    Some(pos) => Coord::from(pos),
+                    None => self.state.last_mouse_coord,
+                };
+                let delta = coord - last;
+                self.state.last_mouse_coord = coord;
+                if self.state.hover == cur_id {
+                    // The icon may still depend on the position within the widget
+                    self.update_hover_icon(widget);
+                } else {
+                    self.set_hover(widget, cur_id);
+                }
 
                 if let Some(grab) = self.mouse_grab() {
-                    if grab.mode == GrabMode::Grab {
-                        let source = PressSource::Mouse(grab.button, grab.repetitions);
-                        let event = Event::PressMove {
-                            source,
-                            cur_id,
-                            coord,
-                            delta,
-                        };
-                        self.send_event(widget, grab.start_id, event);
-                    } else if let Some(pan) =
-                        self.state.pan_grab.get_mut(usize::conv(grab.pan_grab.0))
-                    {
-                        pan.coords[usize::conv(grab.pan_grab.1)].1 = coord;
-                    }
+                    self.mouse_grab_move(widget, grab, coord, cur_id, delta);
                 } else if let Some(id) = self.state.popups.last().map(|(_, p)| p.parent) {
                     let source = PressSource::Mouse(FAKE_MOUSE_BUTTON, 0);
                     let event = Event::PressMove {
@@ -505,8 +760,6 @@ impl<'a> Manager<'a> {
                 } else {
                     // We don't forward move events without a grab
                 }
-
-                self.state.last_mouse_coord = coord;
             }
             // CursorEntered { .. },
             CursorLeft { .. } => {
@@ -517,12 +770,15 @@ impl<'a> Manager<'a> {
                     // coordinates; if not, set a fake coordinate off the window
                     self.state.last_mouse_coord = Coord(-1, -1);
                     self.set_hover(widget, None);
+                } else {
+                    // ... though not on all platforms: see handle_mouse_motion
+                    self.state.mouse_outside = Some(self.state.last_mouse_coord.into());
                 }
             }
             MouseWheel { delta, .. } => {
                 self.state.last_click_button = FAKE_MOUSE_BUTTON;
 
-                let event = Event::Scroll(match delta {
+                let delta = match delta {
                     MouseScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(x, y),
                     MouseScrollDelta::PixelDelta(pos) => {
                         // The delta is given as a PhysicalPosition, so we need
@@ -530,7 +786,8 @@ impl<'a> Manager<'a> {
                         let coord = Coord::from(pos);
                         ScrollDelta::PixelDelta(Offset(coord.0, coord.1))
                     }
-                });
+                };
+                let event = Event::Scroll(self.state.config.borrow().adjust_scroll(delta));
                 if let Some(id) = self.state.hover {
                     self.send_event(widget, id, event);
                 }
@@ -605,6 +862,12 @@ impl<'a> Manager<'a> {
                         let mut pan_grab = None;
                         if let Some(grab) = self.get_touch(touch.id) {
                             if grab.mode == GrabMode::Grab {
+                                if within_drag_threshold(grab.drag_start, coord) {
+                                    // Not yet a drag; grab.coord stays at the
+                                    // start so that the eventual delta is complete
+                                    return;
+                                }
+                                grab.drag_start = None;
                                 let id = grab.start_id;
                                 let event = Event::PressMove {
                                     source,
@@ -676,4 +939,54 @@ impl<'a> Manager<'a> {
             _ => (),
         }
     }
+
+    // Report motion to the holder of a mouse grab
+    fn mouse_grab_move<W>(
+        &mut self,
+        widget: &mut W,
+        grab: MouseGrab,
+        coord: Coord,
+        cur_id: Option<WidgetId>,
+        delta: Offset,
+    ) where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        if grab.mode == GrabMode::Grab {
+            if within_drag_threshold(grab.drag_start, coord) {
+                // Not yet a drag: don't report movement
+            } else {
+                let delta = match grab.drag_start {
+                    // First movement: include movement within threshold
+                    Some((start, _)) => coord - start,
+                    None => delta,
+                };
+                if let Some(grab) = self.state.mouse_grab.as_mut() {
+                    grab.drag_start = None;
+                }
+                let source = PressSource::Mouse(grab.button, grab.repetitions);
+                let event = Event::PressMove {
+                    source,
+                    cur_id,
+                    coord,
+                    delta,
+                };
+                self.send_event(widget, grab.start_id, event);
+            }
+        } else if let Some(pan) = self.state.pan_grab.get_mut(usize::conv(grab.pan_grab.0)) {
+            pan.coords[usize::conv(grab.pan_grab.1)].1 = coord;
+        }
+    }
+}
+
+/// Find the first enabled widget under `widget` requesting default focus
+fn find_default_focus(widget: &dyn WidgetConfig) -> Option<WidgetId> {
+    if widget.is_disabled() {
+        return None;
+    }
+    if widget.is_default_focus() {
+        return Some(widget.id());
+    }
+    (0..widget.num_children())
+        .filter_map(|i| widget.get_child(i))
+        .find_map(find_default_focus)
 }
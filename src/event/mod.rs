@@ -75,6 +75,7 @@ mod handler;
 mod manager;
 mod response;
 mod shortcuts;
+mod tween;
 mod update;
 
 use smallvec::SmallVec;
@@ -85,18 +86,19 @@ use std::fmt::Debug;
 use kas::{draw::InputState, Layout, WidgetCore};
 
 #[cfg(feature = "winit")]
-pub use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
+pub use winit::event::{ElementState, ModifiersState, MouseButton, VirtualKeyCode};
 #[cfg(feature = "winit")]
 pub use winit::window::CursorIcon;
 
-pub use config::{Config, ConfigError};
+pub use config::{Config, ConfigError, ConfigFormat};
 #[cfg(not(feature = "winit"))]
-pub use enums::{CursorIcon, ModifiersState, MouseButton, VirtualKeyCode};
+pub use enums::{CursorIcon, ElementState, ModifiersState, MouseButton, VirtualKeyCode};
 pub use events::*;
 pub use handler::{Handler, SendEvent};
-pub use manager::{ConfigureManager, GrabMode, Manager, ManagerState};
+pub use manager::{ConfigureManager, EventObserver, GrabMode, Manager, ManagerState};
 pub use response::Response;
-pub use update::UpdateHandle;
+pub use tween::{Easing, Tween};
+pub use update::{UpdateHandle, UpdateQueue};
 
 /// A type supporting a small number of key bindings
 ///
@@ -189,16 +189,25 @@ impl Shortcuts {
     /// Note: text-editor navigation keys (e.g. arrows, home/end) result in the
     /// same output with and without Shift pressed. Editors should check the
     /// status of the Shift modifier directly where this has an affect.
+    /// This also applies to movement commands bound with other modifiers, thus
+    /// e.g. Shift+Ctrl+Left matches [`Command::WordLeft`] (if Ctrl+Left does).
     pub fn get(&self, mut modifiers: ModifiersState, vkey: VirtualKeyCode) -> Option<Command> {
         if let Some(result) = self.map.get(&modifiers).and_then(|m| m.get(&vkey)) {
             return Some(*result);
         }
+        let shift = modifiers.shift();
         modifiers.remove(ModifiersState::SHIFT);
         if modifiers.is_empty() {
             // These keys get matched with and without Shift:
             return Command::new(vkey);
+        } else if !shift {
+            return None;
         }
-        None
+        self.map
+            .get(&modifiers)
+            .and_then(|m| m.get(&vkey))
+            .cloned()
+            .filter(|cmd| cmd.is_movement())
     }
 }
 
@@ -0,0 +1,172 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Animated value transitions
+
+use std::time::Duration;
+
+/// Easing functions
+///
+/// These map progress `t` in the range `0.0..=1.0` to an eased progress value,
+/// also in the range `0.0..=1.0` (see [`Easing::apply`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Quadratic: start slowly
+    EaseIn,
+    /// Quadratic: finish slowly
+    EaseOut,
+    /// Quadratic: start and finish slowly
+    EaseInOut,
+    /// Cubic: start slowly
+    CubicIn,
+    /// Cubic: finish slowly
+    CubicOut,
+    /// Cubic: start and finish slowly
+    CubicInOut,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::EaseInOut
+    }
+}
+
+impl Easing {
+    /// Apply to progress `t`
+    ///
+    /// Input is clamped to the range `0.0..=1.0`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.max(0.0).min(1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    let u = 1.0 - t;
+                    1.0 - 2.0 * u * u
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => {
+                let u = 1.0 - t;
+                1.0 - u * u * u
+            }
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 1.0 - t;
+                    1.0 - 4.0 * u * u * u
+                }
+            }
+        }
+    }
+}
+
+/// A value transition over time
+///
+/// A `Tween` does not track time itself; the widget should record the start
+/// time and, while [`Tween::finished`] is false, request a timer update via
+/// [`super::Manager::update_on_timer`], calling [`Tween::sample`] on each
+/// [`super::Event::TimerUpdate`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tween {
+    from: f32,
+    to: f32,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl Tween {
+    /// Construct, transitioning from `from` to `to` over `duration`
+    pub fn new(from: f32, to: f32, duration: Duration, easing: Easing) -> Self {
+        Tween {
+            from,
+            to,
+            duration,
+            easing,
+        }
+    }
+
+    /// Get the start value
+    #[inline]
+    pub fn from(&self) -> f32 {
+        self.from
+    }
+
+    /// Get the final value
+    #[inline]
+    pub fn to(&self) -> f32 {
+        self.to
+    }
+
+    /// Get the duration
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Get the value after time `elapsed`
+    ///
+    /// Before the start this is `from`; after `duration` this is `to`.
+    pub fn sample(&self, elapsed: Duration) -> f32 {
+        if self.finished(elapsed) {
+            return self.to;
+        }
+        let t = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        self.from + (self.to - self.from) * self.easing.apply(t)
+    }
+
+    /// True when `elapsed` is at least the duration
+    #[inline]
+    pub fn finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn easing_ends() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+        ]
+        .iter()
+        {
+            assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+            assert_eq!(easing.apply(-1.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.apply(2.0), 1.0, "{:?}", easing);
+        }
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert_eq!(Easing::CubicInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn tween_sample() {
+        let tween = Tween::new(10.0, 20.0, Duration::from_millis(100), Easing::Linear);
+        assert_eq!(tween.sample(Duration::from_millis(0)), 10.0);
+        assert!((tween.sample(Duration::from_millis(50)) - 15.0).abs() < 1e-4);
+        assert!(!tween.finished(Duration::from_millis(99)));
+        assert!(tween.finished(Duration::from_millis(100)));
+        assert_eq!(tween.sample(Duration::from_millis(150)), 20.0);
+
+        let instant = Tween::new(0.0, 1.0, Duration::from_secs(0), Easing::Linear);
+        assert_eq!(instant.sample(Duration::from_secs(0)), 1.0);
+    }
+}
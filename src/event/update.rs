@@ -36,3 +36,77 @@ impl UpdateHandle {
         }
     }
 }
+
+/// A queue of triggered updates, coalesced by handle
+///
+/// Each [`UpdateHandle`] appears at most once; triggering a handle already in
+/// the queue replaces its payload. Handles are kept in the order first
+/// triggered.
+///
+/// This is used by shells to collect updates over an event cycle before
+/// dispatching via `Manager::update_handles`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UpdateQueue(Vec<(UpdateHandle, u64)>);
+
+impl UpdateQueue {
+    /// Construct an empty queue
+    #[inline]
+    pub fn new() -> Self {
+        UpdateQueue(vec![])
+    }
+
+    /// True if the queue is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of unique handles in the queue
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Add an update
+    ///
+    /// Returns `true` if `handle` was not already queued.
+    pub fn push(&mut self, handle: UpdateHandle, payload: u64) -> bool {
+        for entry in &mut self.0 {
+            if entry.0 == handle {
+                entry.1 = payload;
+                return false;
+            }
+        }
+        self.0.push((handle, payload));
+        true
+    }
+
+    /// Iterate over `(handle, payload)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (UpdateHandle, u64)> + '_ {
+        self.0.iter().cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn coalesce_repeated() {
+        let h1 = UpdateHandle::new();
+        let h2 = UpdateHandle::new();
+        let mut queue = UpdateQueue::new();
+        assert!(queue.is_empty());
+
+        assert!(queue.push(h1, 0));
+        for payload in 1..10 {
+            assert!(!queue.push(h1, payload));
+        }
+        assert_eq!(queue.len(), 1);
+
+        assert!(queue.push(h2, 5));
+        assert!(!queue.push(h1, 10));
+        let updates: Vec<_> = queue.iter().collect();
+        assert_eq!(updates, vec![(h1, 10), (h2, 5)]);
+    }
+}
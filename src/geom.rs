@@ -4,6 +4,21 @@
 //     https://www.apache.org/licenses/LICENSE-2.0
 
 //! Geometry data types
+//!
+//! ## Pixel units
+//!
+//! [`Coord`], [`Size`], [`Offset`] and [`Rect`] are always measured in
+//! **physical pixels**, relative to the window's top-left corner. This
+//! applies to layout ([`kas::Layout::set_rect`]), event coordinates, drawing
+//! and clip regions (which are passed directly to the GPU scissor rect).
+//! The float types [`Vec2`] and [`Quad`] used for drawing also use physical
+//! pixels.
+//!
+//! Logical (scale-independent) units should only appear as inputs to size
+//! calculations, where they must be multiplied by the scale factor (see
+//! [`kas::draw::SizeHandle::scale_factor`]); for example theme dimensions and
+//! values such as [`kas::event::Config::drag_threshold`]. Conversion from
+//! winit's logical types is explicit: see [`Coord::from_logical`].
 
 use kas::conv::Conv;
 use kas::dir::Directional;
@@ -217,6 +232,22 @@ impl From<Coord> for kas_text::Vec2 {
     }
 }
 
+#[cfg(feature = "glam")]
+impl From<glam::IVec2> for Coord {
+    #[inline]
+    fn from(v: glam::IVec2) -> Coord {
+        Coord(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Coord> for glam::IVec2 {
+    #[inline]
+    fn from(coord: Coord) -> glam::IVec2 {
+        glam::IVec2::new(coord.0, coord.1)
+    }
+}
+
 #[cfg(feature = "winit")]
 impl<X: Pixel> From<PhysicalPosition<X>> for Coord {
     #[inline]
@@ -242,7 +273,9 @@ impl<X: Pixel> From<Coord> for PhysicalPosition<X> {
 ///
 /// A `Size` is expected to be non-negative; some methods such as [`Size::new`]
 /// and implementations of subtraction will check this, but only in debug mode
-/// (similar to overflow checks on integers).
+/// (similar to overflow checks on integers). Where the result of subtraction
+/// may be negative, use [`Size::clamped_sub`] (saturating at zero) or convert
+/// to [`Offset`] first.
 ///
 /// This may be converted to [`Offset`] with `from` / `into`.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
@@ -377,6 +410,22 @@ impl From<Size> for kas_text::Vec2 {
     }
 }
 
+#[cfg(feature = "glam")]
+impl From<glam::UVec2> for Size {
+    #[inline]
+    fn from(v: glam::UVec2) -> Size {
+        Size(i32::conv(v.x), i32::conv(v.y))
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Size> for glam::UVec2 {
+    #[inline]
+    fn from(size: Size) -> glam::UVec2 {
+        glam::UVec2::new(u32::conv(size.0), u32::conv(size.1))
+    }
+}
+
 #[cfg(feature = "winit")]
 impl<X: Pixel> From<PhysicalSize<X>> for Size {
     #[inline]
@@ -409,7 +458,7 @@ impl From<Size> for winit::dpi::Size {
 /// A `(x, y)` offset, also known as a **vector**
 ///
 /// This is a relative position. It can be added to or subtracted from a
-/// [`Coord`], and it can be added to or subtracted from itself. It can be
+/// [`Coord`], and it can be added to or subtracted from itself or a [`Size`]. It can be
 /// negative. It can be multiplied by a scalar.
 ///
 /// This may be converted to [`Size`] with `from` / `into`.
@@ -501,6 +550,54 @@ impl std::ops::Div<f32> for Offset {
     }
 }
 
+impl std::ops::Add<Size> for Offset {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Size) -> Self {
+        Offset(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+impl std::ops::AddAssign<Size> for Offset {
+    #[inline]
+    fn add_assign(&mut self, rhs: Size) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl std::ops::Sub<Size> for Offset {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Size) -> Self {
+        Offset(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+impl std::ops::SubAssign<Size> for Offset {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Size) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::IVec2> for Offset {
+    #[inline]
+    fn from(v: glam::IVec2) -> Offset {
+        Offset(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Offset> for glam::IVec2 {
+    #[inline]
+    fn from(offset: Offset) -> glam::IVec2 {
+        glam::IVec2::new(offset.0, offset.1)
+    }
+}
+
 impl From<Size> for Offset {
     fn from(v: Size) -> Self {
         Self(v.0, v.1)
@@ -546,7 +643,19 @@ impl Rect {
             && c.1 < self.pos.1 + (self.size.1)
     }
 
+    /// Check whether `rhs` lies entirely within this rect
+    ///
+    /// Edges may coincide. An empty `rhs` is contained if its position is
+    /// within (or on the edge of) this rect.
+    #[inline]
+    pub fn contains_rect(&self, rhs: &Rect) -> bool {
+        self.pos.le(rhs.pos) && rhs.pos2().le(self.pos2())
+    }
+
     /// Calculate the intersection of two rects
+    ///
+    /// Returns `None` if the rects do not overlap. Rects which touch on an
+    /// edge (or corner) yield an empty rect (with zero width or height).
     #[inline]
     pub fn intersection(&self, rhs: &Rect) -> Option<Rect> {
         let (l1, l2) = (self.pos, self.pos2());
@@ -560,6 +669,17 @@ impl Rect {
         }
     }
 
+    /// Calculate the smallest rect containing both `self` and `rhs`
+    ///
+    /// Note that empty rects are not ignored: the result always includes the
+    /// position of both inputs.
+    #[inline]
+    pub fn union(&self, rhs: &Rect) -> Rect {
+        let pos = self.pos.min(rhs.pos);
+        let pos2 = self.pos2().max(rhs.pos2());
+        Rect::new(pos, (pos2 - pos).into())
+    }
+
     /// Shrink self in all directions by the given `n`
     #[inline]
     pub fn shrink(&self, n: i32) -> Rect {
@@ -598,3 +718,85 @@ impl std::ops::SubAssign<Offset> for Rect {
         self.pos -= offset;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rect(x: i32, y: i32, w: i32, h: i32) -> Rect {
+        Rect::new(Coord(x, y), Size(w, h))
+    }
+
+    #[test]
+    fn rect_intersection() {
+        let a = rect(0, 0, 10, 10);
+        assert_eq!(a.intersection(&rect(5, 5, 10, 10)), Some(rect(5, 5, 5, 5)));
+        assert_eq!(a.intersection(&rect(2, 3, 4, 4)), Some(rect(2, 3, 4, 4)));
+        assert_eq!(a.intersection(&rect(11, 0, 5, 5)), None);
+        assert_eq!(a.intersection(&rect(0, -6, 5, 5)), None);
+    }
+
+    #[test]
+    fn rect_intersection_touching() {
+        let a = rect(0, 0, 10, 10);
+        assert_eq!(a.intersection(&rect(10, 2, 5, 5)), Some(rect(10, 2, 0, 5)));
+        assert_eq!(
+            a.intersection(&rect(10, 10, 5, 5)),
+            Some(rect(10, 10, 0, 0))
+        );
+    }
+
+    #[test]
+    fn rect_union() {
+        let a = rect(0, 0, 10, 10);
+        assert_eq!(a.union(&rect(5, 5, 10, 10)), rect(0, 0, 15, 15));
+        assert_eq!(a.union(&rect(2, 2, 2, 2)), a);
+        assert_eq!(a.union(&rect(-5, 20, 0, 0)), rect(-5, 0, 15, 20));
+    }
+
+    #[test]
+    fn rect_contains_rect() {
+        let a = rect(0, 0, 10, 10);
+        assert!(a.contains_rect(&a));
+        assert!(a.contains_rect(&rect(2, 2, 8, 8)));
+        assert!(a.contains_rect(&rect(10, 10, 0, 0)));
+        assert!(!a.contains_rect(&rect(2, 2, 9, 8)));
+        assert!(!a.contains_rect(&rect(-1, 0, 5, 5)));
+    }
+
+    #[test]
+    fn offset_size_ops() {
+        let size = Size(4, 6);
+        assert_eq!(Offset::from(Size(3, 3)) - size, Offset(-1, -3));
+        assert_eq!(Offset(-1, -3) + size, Offset(3, 3));
+        assert_eq!(Size(3, 3).clamped_sub(size), Size(0, 0));
+        assert_eq!(Coord(1, 1) + size - size, Coord(1, 1));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam_round_trip() {
+        let coord = Coord(-3, 7);
+        assert_eq!(Coord::from(glam::IVec2::from(coord)), coord);
+        let offset = Offset(5, -2);
+        assert_eq!(Offset::from(glam::IVec2::from(offset)), offset);
+        let size = Size(12, 0);
+        assert_eq!(Size::from(glam::UVec2::from(size)), size);
+        let v = Vec2(1.5, -0.25);
+        assert_eq!(Vec2::from(glam::Vec2::from(v)), v);
+        assert_eq!(glam::Vec2::from(v), glam::Vec2::new(1.5, -0.25));
+    }
+
+    #[test]
+    fn quad_intersection() {
+        let a = Quad::with_coords(Vec2(0.0, 0.0), Vec2(10.0, 10.0));
+        let b = Quad::with_coords(Vec2(5.0, 5.0), Vec2(15.0, 15.0));
+        let c = Quad::with_coords(Vec2(5.0, 5.0), Vec2(10.0, 10.0));
+        assert_eq!(a.intersection(&b), Some(c));
+        // Touching edges give no intersection
+        let d = Quad::with_coords(Vec2(10.0, 0.0), Vec2(20.0, 10.0));
+        assert_eq!(a.intersection(&d), None);
+        let e = Quad::with_coords(Vec2(20.0, 20.0), Vec2(30.0, 30.0));
+        assert_eq!(a.intersection(&e), None);
+    }
+}
@@ -103,6 +103,14 @@ impl Quad {
     }
 }
 
+impl Add<Vec2> for Quad {
+    type Output = Quad;
+    #[inline]
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Quad::with_coords(self.a + rhs, self.b + rhs)
+    }
+}
+
 impl From<Rect> for Quad {
     #[inline]
     fn from(rect: Rect) -> Quad {
@@ -397,6 +405,22 @@ impl From<Vec2> for kas_text::Vec2 {
 impl_vec2!(Vec2, f32);
 impl_vec2!(DVec2, f64);
 
+#[cfg(feature = "glam")]
+impl From<glam::Vec2> for Vec2 {
+    #[inline]
+    fn from(v: glam::Vec2) -> Vec2 {
+        Vec2(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Vec2> for glam::Vec2 {
+    #[inline]
+    fn from(v: Vec2) -> glam::Vec2 {
+        glam::Vec2::new(v.0, v.1)
+    }
+}
+
 /// 3D vector
 ///
 /// Usually used for a 2D coordinate with a depth value.
@@ -50,7 +50,8 @@ pub use row_solver::{RowPositionSolver, RowSetter, RowSolver};
 pub use single_solver::{SingleSetter, SingleSolver};
 pub use size_rules::SizeRules;
 pub use size_types::{FrameRules, Margins, StretchPolicy};
-pub use sizer::{solve_size_rules, RulesSetter, RulesSolver, SolveCache};
+pub(crate) use sizer::WidgetHeirarchy;
+pub use sizer::{debug_size_rules, solve_size_rules, RulesSetter, RulesSolver, SolveCache};
 pub use storage::{
     DynGridStorage, DynRowStorage, FixedGridStorage, FixedRowStorage, GridStorage, RowStorage,
     RowTemp, Storage,
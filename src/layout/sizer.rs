@@ -85,6 +85,26 @@ pub fn solve_size_rules<W: Widget>(
     widget.size_rules(size_handle, AxisInfo::new(true, x_size));
 }
 
+/// Query the size rules of a widget, for debugging purposes
+///
+/// Returns horizontal and vertical [`SizeRules`] respectively. The vertical
+/// axis is sized for the widget's current width.
+///
+/// Note that this is *state-changing*: `size_rules` may update the widget's
+/// layout storage and cached state (for example, text is re-prepared with the
+/// bounds implied by `axis`). Until `set_rect` is next called on the widget
+/// (e.g. by a resize), it may draw incorrectly. This function is therefore only
+/// suitable for debugging.
+pub fn debug_size_rules(
+    widget: &mut dyn WidgetConfig,
+    size_handle: &mut dyn SizeHandle,
+) -> (SizeRules, SizeRules) {
+    let width = widget.rect().size.0;
+    let w = widget.size_rules(size_handle, AxisInfo::new(false, None));
+    let h = widget.size_rules(size_handle, AxisInfo::new(true, Some(width)));
+    (w, h)
+}
+
 /// Size solver
 ///
 /// This struct is used to solve widget layout, read size constraints and
@@ -217,6 +237,7 @@ impl SolveCache {
             rect.size.1 -= self.margins.sum_vert();
         }
         widget.set_rect(mgr, rect, AlignHints::NONE);
+        mgr.update_rect_cache(widget);
 
         trace!(
             "layout::solve_and_set for size={:?} has hierarchy:{}",
@@ -228,7 +249,7 @@ impl SolveCache {
     }
 }
 
-struct WidgetHeirarchy<'a>(&'a dyn WidgetConfig, usize);
+pub(crate) struct WidgetHeirarchy<'a>(pub &'a dyn WidgetConfig, pub usize);
 impl<'a> fmt::Display for WidgetHeirarchy<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(
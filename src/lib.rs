@@ -44,6 +44,8 @@ pub mod event;
 pub mod geom;
 pub mod layout;
 pub mod prelude;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod text;
 pub mod widget;
 
@@ -112,6 +112,28 @@
 //! coordinates within the widget directly to the child's [`WidgetId`], causing
 //! clicks on the parent area to send events directly to the child.
 //!
+//! Grid layouts also support arrow-key navigation: when an arrow key is not
+//! handled by the child with navigation focus, focus moves to the nearest
+//! child in that direction which can accept it (see
+//! [`Manager::grid_nav_focus`]). This stops at the grid's edges unless the
+//! `wrap` parameter is given (e.g. `#[layout(grid, wrap)]`), in which case it
+//! continues from the opposite edge. This is part of the derived
+//! [`SendEvent`] implementation, thus is not available with
+//! `#[handler(send=noauto)]`.
+//!
+//! Grid layouts accept further parameters:
+//!
+//! -   `halign = ...`, `valign = ...` — default alignment for children which
+//!     do not specify their own (values as for children; see below)
+//! -   `col_gap = N`, `row_gap = N` — space (in logical pixels, scaled by the
+//!     theme's scale factor) inserted between columns and rows respectively.
+//!     This is in addition to the children's margins. Children spanning
+//!     several columns or rows include the gaps between these in their rect.
+//!     A gap is only inserted before a column or row in which some child
+//!     starts.
+//!
+//! For example: `#[layout(grid, halign = left, col_gap = 8, row_gap = 4)]`.
+//!
 //! **Child widget placement**
 //!
 //! All fields with attribute `#[widget]` are considered child widgets. For most
@@ -133,6 +155,19 @@
 //! -   `halign = ...` — one of `default`, `left`, `centre`, `center`, `right`, `stretch`
 //! -   `valign = ...` — one of `default`, `top`, `centre`, `center`, `bottom`, `stretch`
 //!
+//! Where children overlap (e.g. a badge placed over a button in the same grid
+//! cell), draw order may be controlled with `z = ...` (an integer, default 0).
+//! Children are drawn in order of increasing `z`, thus higher values appear
+//! above lower ones, while [`Layout::find_id`] tests children with higher `z`
+//! first. Children with equal `z` are drawn in order of definition.
+//!
+//! Note that `z` only orders draw calls within the parent's draw pass. Themes
+//! and graphics back-ends may batch primitives by type within a pass (e.g.
+//! `kas-wgpu` draws all text after all shapes), thus where the content of
+//! overlapping children differs in type it may not be layered as requested.
+//! Content which must appear on top should be drawn in a separate pass (see
+//! [`DrawHandle::clip_region`](kas::draw::DrawHandle::clip_region)).
+//!
 //! **Layout data storage**
 //!
 //! When deriving [`Layout`], data storage is required (exception: layout
@@ -229,9 +264,19 @@
 //! In other cases, if no explicit handler is provided, an error will result:
 //!
 //! ```none
-//! error[E0277]: the trait bound `kas::event::VoidMsg: std::convert::From<Item>` is not satisfied
+//! error[E0277]: the trait bound `VoidMsg: From<Item>` is not satisfied
+//!   --> src/main.rs:11:5
+//!    |
+//! 11 |     child: ItemWidget,
+//!    |     ^^^^^ the trait `From<Item>` is not implemented for `VoidMsg`
+//!    |
+//!    = note: required because of the requirements on the impl of `Into<VoidMsg>` for `Item`
+//!    = note: required because of the requirements on the impl of `ConvertMsgOrAddHandler<VoidMsg>` for `Item`
 //! ```
 //!
+//! This error points at the offending `#[widget]` field. To fix, either add a
+//! handler (below) or implement `From<Item>` for the parent's message type.
+//!
 //! A handler is a method on the parent struct with signature
 //! `fn f(&mut self, mgr: &mut Manager, item: Item) -> Response<Out>`
 //! (where `Item` is the child's message type and `Out` is the parent's message
@@ -410,13 +455,70 @@
 //! #[widget] display: for<W: Widget<Msg = VoidMsg>> Frame<W> =
 //!     Frame::new(Label::new("example")),
 //! ```
+//!
+//! Non-widget fields with elided type are given the bounds `Debug + 'static`
+//! (as required by the derived trait implementations), plus any bound given
+//! via `impl Trait`.
+//!
+//! ### Captured values
+//!
+//! Field initialisers are evaluated where `make_widget!` is invoked, thus may
+//! reference local variables; these are *moved* into the widget. To keep using
+//! a local after constructing the widget, mark the field `#[capture]`: the
+//! widget then stores a clone of the value (`Clone::clone(&value)`), taken at
+//! construction time. Later changes to the local are not seen by the widget;
+//! to share state, capture an `Rc<Cell<T>>` or similar.
+//!
+//! ```nocompile
+//! let prefix = String::from("Item");
+//! let unit = "px";
+//! let widget = make_widget! {
+//!     #[layout(single)]
+//!     #[handler(msg = VoidMsg)]
+//!     struct {
+//!         #[widget] label: Label<String> = Label::new(prefix.clone()),
+//!         #[capture] prefix: String = prefix,
+//!         #[capture] unit: impl std::fmt::Display = unit,
+//!     }
+//!     impl {
+//!         fn set_index(&mut self, n: usize) -> TkAction {
+//!             let text = format!("{}: {}{}", self.prefix, n, self.unit);
+//!             self.label.set_string(text)
+//!         }
+//!     }
+//! };
+//! println!("prefix is still usable: {}", prefix);
+//! ```
+//!
+//! As with other fields, the type may be elided (`#[capture] prefix = prefix`)
+//! but the field then has opaque type; specify the type or a trait bound to
+//! call methods on the captured value.
+
+use crate::event::Response;
 
 // Imported for doc-links
 #[allow(unused)]
 use crate::{
-    event::{Handler, SendEvent},
+    event::{Handler, Manager, SendEvent},
     layout::AlignHints,
     CoreData, Layout, LayoutData, Widget, WidgetChildren, WidgetConfig, WidgetCore, WidgetId,
 };
 
 pub use kas_macros::{make_widget, VoidMsg, Widget};
+
+/// Conversion of a child's message to the parent's message type
+///
+/// This is used by the derived [`SendEvent`] implementation for children
+/// without a handler. It is implemented for all `M: Into<N>`; when conversion
+/// is missing, its name appears in the error as a hint.
+#[doc(hidden)]
+pub trait ConvertMsgOrAddHandler<N>: Sized {
+    fn convert(r: Response<Self>) -> Response<N>;
+}
+
+impl<M: Into<N>, N> ConvertMsgOrAddHandler<N> for M {
+    #[inline]
+    fn convert(r: Response<M>) -> Response<N> {
+        r.into()
+    }
+}
@@ -0,0 +1,985 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Testing utilities
+//!
+//! [`TestHarness`] allows widgets to be tested without a window, theme or
+//! graphics device. Layout uses fixed, theme-independent dimensions. Text is
+//! measured using the default font, which must be available.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::num::NonZeroU32;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use crate::conv::{Cast, ConvFloat};
+use crate::dir::Direction;
+use crate::draw::{ClipRegion, Colour, Draw, DrawHandle, InputState, Pass};
+use crate::draw::{Metrics, SizeHandle, TextClass};
+use crate::event::{
+    self, Command, Config, Event, EventObserver, Manager, ManagerState, MouseButton, PressSource,
+    Response, ScrollDelta, UpdateHandle, VirtualKeyCode, VoidMsg,
+};
+use crate::geom::{Coord, Offset, Quad, Rect, Size, Vec2};
+use crate::layout::{AxisInfo, FrameRules, Margins, SizeRules, SolveCache, StretchPolicy};
+use crate::text::{AccelString, Effect, Text, TextApi, TextApiExt, TextDisplay};
+use crate::{Layout, Popup, ShellWindow, ThemeAction, ThemeApi, TkAction, Widget, WidgetId};
+use crate::{WidgetChildren, WidgetCore, WindowId};
+
+mod record;
+
+pub use record::{RecordedEvent, RecordedInput, Recording, ReplayError, WidgetPath};
+
+/// A [`SizeHandle`] using fixed dimensions
+///
+/// All dimensions are in pixels at a scale factor of 1. Text is scaled by
+/// [`TestSizeHandle::set_scale_factor`]; other dimensions are fixed.
+///
+/// Text is measured using the default font, which must be loaded (see
+/// [`load_fonts`]). Calls to [`SizeHandle::text_bound`] are counted, allowing
+/// tests of size caching.
+#[derive(Debug)]
+pub struct TestSizeHandle {
+    scale_factor: f32,
+    text_bound_calls: usize,
+}
+
+impl Default for TestSizeHandle {
+    fn default() -> Self {
+        TestSizeHandle {
+            scale_factor: 1.0,
+            text_bound_calls: 0,
+        }
+    }
+}
+
+impl TestSizeHandle {
+    /// Construct
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the scale factor (default: 1)
+    ///
+    /// This simulates a DPI change, scaling text.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+    }
+
+    /// Number of calls to [`SizeHandle::text_bound`] since construction
+    pub fn text_bound_calls(&self) -> usize {
+        self.text_bound_calls
+    }
+}
+
+/// Load the default font
+///
+/// This is called by [`TestHarness::new`]; only the first call has any
+/// effect. Panics on failure.
+pub fn load_fonts() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        if let Err(e) = crate::text::fonts::fonts().load_default() {
+            panic!("Error loading font: {}", e);
+        }
+    });
+}
+
+const FRAME: i32 = 2;
+const MARGIN: u16 = 2;
+const LINE_HEIGHT: i32 = 16;
+const PT_SIZE: f32 = 12.0;
+
+impl SizeHandle for TestSizeHandle {
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn frame(&self, _vert: bool) -> FrameRules {
+        FrameRules::new_sym(FRAME, 0, (0, 0))
+    }
+
+    fn menu_frame(&self, _vert: bool) -> FrameRules {
+        FrameRules::new_sym(FRAME, 0, (0, 0))
+    }
+
+    fn separator(&self) -> Size {
+        Size::splat(FRAME)
+    }
+
+    fn inner_margin(&self) -> Size {
+        Size::splat(MARGIN.into())
+    }
+
+    fn outer_margins(&self) -> Margins {
+        Margins::splat(MARGIN)
+    }
+
+    fn line_height(&self, _class: TextClass) -> i32 {
+        LINE_HEIGHT
+    }
+
+    fn text_dpem(&self, _class: TextClass) -> f32 {
+        PT_SIZE * self.scale_factor
+    }
+
+    fn metrics(&self) -> Metrics {
+        let margin = i32::from(MARGIN);
+        Metrics {
+            gap: margin,
+            inner_margin: margin,
+            text_margin: margin,
+            frame: FRAME,
+            line_height: LINE_HEIGHT,
+            control_height: LINE_HEIGHT + 2 * (margin + FRAME),
+            icon_size: LINE_HEIGHT,
+            corner_radius: 0.0,
+        }
+    }
+
+    fn text_bound(
+        &mut self,
+        text: &mut dyn TextApi,
+        class: TextClass,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        self.text_bound_calls += 1;
+        let dpp = self.scale_factor;
+        let required = text.update_env(|env| {
+            env.set_dpp(dpp);
+            env.set_pt_size(PT_SIZE);
+            let mut bounds = crate::text::Vec2::INFINITY;
+            if let Some(size) = axis.size_other_if_fixed(false) {
+                bounds.1 = size.cast();
+            } else if let Some(size) = axis.size_other_if_fixed(true) {
+                bounds.0 = size.cast();
+            }
+            env.set_bounds(bounds);
+            env.set_wrap(match class {
+                TextClass::Label | TextClass::Link | TextClass::EditMulti => true,
+                _ => false,
+            });
+        });
+
+        let margins = (MARGIN, MARGIN);
+        if axis.is_horizontal() {
+            let size = i32::conv_ceil(required.0);
+            SizeRules::new(size, size, margins, StretchPolicy::LowUtility)
+        } else {
+            let size = i32::conv_ceil(required.1).max(LINE_HEIGHT);
+            SizeRules::new(size, size, margins, StretchPolicy::Filler)
+        }
+    }
+
+    fn text_size(&mut self, text: &str, _class: TextClass, wrap_width: Option<f32>) -> Vec2 {
+        let mut t = Text::new_multi(text);
+        let dpp = self.scale_factor;
+        let required = t.update_env(|env| {
+            env.set_dpp(dpp);
+            env.set_pt_size(PT_SIZE);
+            let width = wrap_width.unwrap_or(f32::INFINITY);
+            env.set_bounds(crate::text::Vec2(width, f32::INFINITY));
+            env.set_wrap(wrap_width.is_some());
+        });
+        Vec2::from(required)
+    }
+
+    fn edit_marker_width(&self) -> f32 {
+        1.0
+    }
+
+    fn button_surround(&self, _vert: bool) -> FrameRules {
+        FrameRules::new_sym(FRAME, MARGIN.into(), (MARGIN, MARGIN))
+    }
+
+    fn edit_surround(&self, _vert: bool) -> FrameRules {
+        FrameRules::new_sym(FRAME, MARGIN.into(), (0, 0))
+    }
+
+    fn checkbox(&self) -> Size {
+        Size::splat(LINE_HEIGHT)
+    }
+
+    fn radiobox(&self) -> Size {
+        Size::splat(LINE_HEIGHT)
+    }
+
+    fn scrollbar(&self) -> (Size, i32) {
+        let size = Size(LINE_HEIGHT, LINE_HEIGHT / 2);
+        (size, 2 * size.0)
+    }
+
+    fn slider(&self) -> (Size, i32) {
+        let size = Size(LINE_HEIGHT, LINE_HEIGHT);
+        (size, 2 * size.0)
+    }
+
+    fn progress_bar(&self) -> Size {
+        Size(2 * LINE_HEIGHT, LINE_HEIGHT / 2)
+    }
+
+    fn spinner(&self) -> Size {
+        Size::splat(LINE_HEIGHT)
+    }
+}
+
+// A Draw implementation which does nothing
+#[derive(Debug, Default)]
+struct TestDraw;
+
+impl Draw for TestDraw {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn add_clip_region(&mut self, _: Rect, depth: f32) -> Pass {
+        Pass::new_pass_with_depth(0, depth)
+    }
+    fn push_clip_region(&mut self, _: Rect, depth: f32) -> Pass {
+        Pass::new_pass_with_depth(0, depth)
+    }
+    fn pop_clip_region(&mut self) {}
+    fn rect(&mut self, _: Pass, _: Quad, _: Colour) {}
+    fn frame(&mut self, _: Pass, _: Quad, _: Quad, _: Colour) {}
+}
+
+/// A [`DrawHandle`] recording calls
+///
+/// Nothing is drawn; instead the name of each [`DrawHandle`] method called is
+/// recorded (in order), allowing tests of what a widget draws. Sizes are as
+/// for [`TestSizeHandle`].
+#[derive(Debug, Default)]
+pub struct TestDrawHandle {
+    size_handle: TestSizeHandle,
+    draw: TestDraw,
+    target: Rect,
+    calls: Vec<&'static str>,
+}
+
+impl TestDrawHandle {
+    /// Construct, with the given target (visible) rect
+    pub fn new(target: Rect) -> Self {
+        TestDrawHandle {
+            target,
+            ..Default::default()
+        }
+    }
+
+    /// Names of methods called, in order
+    pub fn calls(&self) -> &[&'static str] {
+        &self.calls
+    }
+}
+
+impl DrawHandle for TestDrawHandle {
+    fn size_handle_dyn(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        f(&mut self.size_handle);
+    }
+    fn draw_device(&mut self) -> (Pass, Offset, &mut dyn Draw) {
+        let pass = Pass::new_pass_with_depth(0, 0.0);
+        (pass, Offset::ZERO, &mut self.draw)
+    }
+    fn clip_region(
+        &mut self,
+        rect: Rect,
+        _: Offset,
+        _: ClipRegion,
+        f: &mut dyn FnMut(&mut dyn DrawHandle),
+    ) {
+        self.calls.push("clip_region");
+        let target = std::mem::replace(&mut self.target, rect);
+        f(self);
+        self.target = target;
+    }
+    fn target_rect(&self) -> Rect {
+        self.target
+    }
+    fn quad(&mut self, _: Quad, _: Colour) {
+        self.calls.push("quad");
+    }
+    fn frame(&mut self, _: Quad, _: Quad, _: Colour) {
+        self.calls.push("frame");
+    }
+    fn line(&mut self, _: Vec2, _: Vec2, _: f32, _: Colour) {
+        self.calls.push("line");
+    }
+    fn circle(&mut self, _: Quad, _: f32, _: Colour) {
+        self.calls.push("circle");
+    }
+    fn text_col(&mut self, _: Coord, _: &dyn TextApi, _: Colour) {
+        self.calls.push("text_col");
+    }
+    fn outer_frame(&mut self, _: Rect) {
+        self.calls.push("outer_frame");
+    }
+    fn menu_frame(&mut self, _: Rect) {
+        self.calls.push("menu_frame");
+    }
+    fn separator(&mut self, _: Rect) {
+        self.calls.push("separator");
+    }
+    fn selection_box(&mut self, _: Rect) {
+        self.calls.push("selection_box");
+    }
+    fn text_offset(&mut self, _: Coord, _: Vec2, _: Offset, _: &TextDisplay, _: TextClass) {
+        self.calls.push("text_offset");
+    }
+    fn text_effects(&mut self, _: Coord, _: Offset, _: &dyn TextApi, _: TextClass) {
+        self.calls.push("text_effects");
+    }
+    fn text_col_spans(
+        &mut self,
+        _: Coord,
+        _: Offset,
+        _: &dyn TextApi,
+        _: &[Effect<Option<Colour>>],
+        _: TextClass,
+    ) {
+        self.calls.push("text_col_spans");
+    }
+    fn text_accel(&mut self, _: Coord, _: &Text<AccelString>, _: bool, _: TextClass) {
+        self.calls.push("text_accel");
+    }
+    fn text_selected_range(
+        &mut self,
+        _: Coord,
+        _: Vec2,
+        _: Offset,
+        _: &TextDisplay,
+        _: Range<usize>,
+        _: TextClass,
+    ) {
+        self.calls.push("text_selected_range");
+    }
+    fn edit_marker(
+        &mut self,
+        _: Coord,
+        _: Vec2,
+        _: Offset,
+        _: &TextDisplay,
+        _: TextClass,
+        _: usize,
+    ) {
+        self.calls.push("edit_marker");
+    }
+    fn menu_entry(&mut self, _: Rect, _: InputState) {
+        self.calls.push("menu_entry");
+    }
+    fn button(&mut self, _: Rect, _: InputState) {
+        self.calls.push("button");
+    }
+    fn edit_box(&mut self, _: Rect, _: InputState) {
+        self.calls.push("edit_box");
+    }
+    fn checkbox(&mut self, _: Rect, _: bool, _: InputState) {
+        self.calls.push("checkbox");
+    }
+    fn radiobox(&mut self, _: Rect, _: bool, _: InputState) {
+        self.calls.push("radiobox");
+    }
+    fn scrollbar(&mut self, _: Rect, _: Rect, _: Direction, _: InputState) {
+        self.calls.push("scrollbar");
+    }
+    fn slider(&mut self, _: Rect, _: Rect, _: Direction, _: InputState) {
+        self.calls.push("slider");
+    }
+    fn progress_bar(&mut self, _: Rect, _: Direction, _: InputState, _: f32) {
+        self.calls.push("progress_bar");
+    }
+    fn spinner(&mut self, _: Rect, _: InputState, _: f32) {
+        self.calls.push("spinner");
+    }
+}
+
+/// A [`ShellWindow`] recording requests
+#[derive(Debug, Default)]
+struct TestShell {
+    size_handle: TestSizeHandle,
+    next_window_id: u32,
+    closed: Vec<WindowId>,
+    updates: Vec<(UpdateHandle, u64)>,
+    clipboard: Option<String>,
+}
+
+impl TestShell {
+    fn next_window_id(&mut self) -> WindowId {
+        self.next_window_id += 1;
+        WindowId::new(NonZeroU32::new(self.next_window_id).unwrap())
+    }
+}
+
+impl ShellWindow for TestShell {
+    fn add_popup(&mut self, _popup: Popup) -> WindowId {
+        self.next_window_id()
+    }
+
+    fn add_window(&mut self, _widget: Box<dyn crate::Window>) -> WindowId {
+        self.next_window_id()
+    }
+
+    fn close_window(&mut self, id: WindowId) {
+        self.closed.push(id);
+    }
+
+    fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
+        self.updates.push((handle, payload));
+    }
+
+    fn get_clipboard(&mut self) -> Option<String> {
+        self.clipboard.clone()
+    }
+
+    fn set_clipboard<'c>(&mut self, content: std::borrow::Cow<'c, str>) {
+        self.clipboard = Some(content.into());
+    }
+
+    fn adjust_theme(&mut self, _f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {}
+
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        f(&mut self.size_handle);
+    }
+
+    fn set_cursor_icon(&mut self, _icon: event::CursorIcon) {}
+}
+
+/// Headless driver for widget tests
+///
+/// The harness owns a widget, configures it on construction and lays it out
+/// via [`TestHarness::resize`]. Events may then be injected; messages emitted
+/// by the widget and [`TkAction`]s are captured.
+///
+/// Example:
+/// ```no_run
+/// use kas::geom::{Coord, Size};
+/// use kas::testing::TestHarness;
+/// use kas::widget::TextButton;
+///
+/// let mut harness = TestHarness::with_size(TextButton::new_msg("OK", 1), Size(100, 30));
+/// harness.click_at(Coord(50, 15));
+/// assert_eq!(harness.take_messages(), vec![1]);
+/// ```
+pub struct TestHarness<W: Widget> {
+    widget: W,
+    state: ManagerState,
+    shell: TestShell,
+    messages: Vec<W::Msg>,
+    action: TkAction,
+    // Input recording and time of the last recorded event
+    recording: Rc<RefCell<Option<(Instant, Recording)>>>,
+}
+
+impl<W: Widget> TestHarness<W> {
+    /// Construct, configuring `widget`
+    ///
+    /// This loads the default font (see [`load_fonts`]); it panics on failure.
+    pub fn new(mut widget: W) -> Self {
+        load_fonts();
+        let config = Rc::new(RefCell::new(Config::default()));
+        let mut state = ManagerState::new(config);
+        let mut shell = TestShell::default();
+        state.configure(&mut shell, &mut widget);
+        TestHarness {
+            widget,
+            state,
+            shell,
+            messages: vec![],
+            action: TkAction::empty(),
+            recording: Default::default(),
+        }
+    }
+
+    /// Construct, configuring `widget` and solving its layout for `size`
+    ///
+    /// This is [`TestHarness::new`] followed by [`TestHarness::resize`].
+    pub fn with_size(widget: W, size: Size) -> Self {
+        let mut harness = TestHarness::new(widget);
+        harness.resize(size);
+        harness
+    }
+
+    /// Access the widget
+    pub fn widget(&self) -> &W {
+        &self.widget
+    }
+
+    /// Access the widget mutably
+    ///
+    /// Note: after adding or removing child widgets, call
+    /// [`TestHarness::reconfigure`].
+    pub fn widget_mut(&mut self) -> &mut W {
+        &mut self.widget
+    }
+
+    /// Access the event manager state
+    pub fn manager_state(&self) -> &ManagerState {
+        &self.state
+    }
+
+    /// Run a closure with a [`Manager`] and the widget
+    ///
+    /// Any resulting action is captured.
+    pub fn with_manager<F: FnOnce(&mut Manager, &mut W) -> T, T>(&mut self, f: F) -> T {
+        let widget = &mut self.widget;
+        let mut action = TkAction::empty();
+        let mut result = None;
+        self.state.with(&mut self.shell, |mgr| {
+            result = Some(f(mgr, widget));
+            action = mgr.pop_action();
+        });
+        self.action |= action;
+        result.unwrap()
+    }
+
+    /// Re-configure the widget
+    pub fn reconfigure(&mut self) {
+        self.state.configure(&mut self.shell, &mut self.widget);
+    }
+
+    /// Solve layout and set widget positions for the given window size
+    pub fn resize(&mut self, size: Size) {
+        let widget = &mut self.widget;
+        let mut solve = SolveCache::find_constraints(widget, &mut self.shell.size_handle);
+        let rect = Rect::new(Coord::ZERO, size);
+        self.state.with(&mut self.shell, |mgr| {
+            solve.apply_rect(widget, mgr, rect, true);
+        });
+    }
+
+    /// Send `event` to widget `id`
+    ///
+    /// If the widget emits a message, this is both captured and returned.
+    /// Input events are recorded (see [`TestHarness::start_recording`]).
+    pub fn send(&mut self, id: WidgetId, event: Event) -> Response<W::Msg>
+    where
+        W::Msg: Clone,
+    {
+        let response = self.with_manager(|mgr, widget| {
+            mgr.observe_event(widget, id, &event);
+            widget.send(mgr, id, event)
+        });
+        if let Response::Msg(ref msg) = response {
+            self.messages.push(msg.clone());
+        }
+        response
+    }
+
+    /// Find the widget at `coord`
+    pub fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.widget.find_id(coord)
+    }
+
+    /// Click the primary mouse button at `coord`
+    ///
+    /// The press is sent to the widget at `coord`. If this widget (or any
+    /// other) requests a mouse grab, the release is sent to the grab holder.
+    ///
+    /// Returns false if there is no widget at `coord`.
+    pub fn click_at(&mut self, coord: Coord) -> bool
+    where
+        W::Msg: Clone,
+    {
+        self.drag(coord, coord)
+    }
+
+    /// Drag with the primary mouse button from `start` to `end`
+    ///
+    /// As [`TestHarness::click_at`], except that if a mouse grab is requested
+    /// and `end != start`, a single motion to `end` is sent to the grab
+    /// holder before the release.
+    ///
+    /// Returns false if there is no widget at `start`.
+    pub fn drag(&mut self, start: Coord, end: Coord) -> bool
+    where
+        W::Msg: Clone,
+    {
+        let start_id = match self.find_id(start) {
+            Some(id) => id,
+            None => return false,
+        };
+        self.with_manager(|mgr, _| mgr.set_mouse_coord(start));
+        let source = PressSource::Mouse(MouseButton::Left, 1);
+        let event = Event::PressStart {
+            source,
+            start_id,
+            coord: start,
+        };
+        self.send(start_id, event);
+
+        let grab_id = self.with_manager(|mgr, _| mgr.mouse_grab_id());
+        if let Some(id) = grab_id {
+            let end_id = self.find_id(end);
+            if end != start {
+                self.with_manager(|mgr, _| mgr.set_mouse_coord(end));
+                let event = Event::PressMove {
+                    source,
+                    cur_id: end_id,
+                    coord: end,
+                    delta: end - start,
+                };
+                self.send(id, event);
+            }
+            let event = Event::PressEnd {
+                source,
+                end_id,
+                coord: end,
+            };
+            self.send(id, event);
+            self.with_manager(|mgr, _| mgr.release_mouse_grab());
+        }
+        true
+    }
+
+    /// Scroll by `delta` at `coord`
+    ///
+    /// Returns false if there is no widget at `coord`.
+    pub fn scroll_at(&mut self, coord: Coord, delta: ScrollDelta) -> bool
+    where
+        W::Msg: Clone,
+    {
+        match self.find_id(coord) {
+            Some(id) => {
+                self.with_manager(|mgr, _| mgr.set_mouse_coord(coord));
+                self.send(id, Event::Scroll(delta));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Type `text` into the widget with character focus
+    ///
+    /// Usually a widget must be clicked (see [`TestHarness::click_at`]) before
+    /// it has character focus. Returns false if no widget has focus.
+    pub fn type_text(&mut self, text: &str) -> bool
+    where
+        W::Msg: Clone,
+    {
+        let id = match self.with_manager(|mgr, _| mgr.char_focus_id()) {
+            Some(id) => id,
+            None => return false,
+        };
+        for c in text.chars() {
+            self.send(id, Event::ReceivedCharacter(c));
+        }
+        true
+    }
+
+    /// Send a command to the widget with character focus
+    ///
+    /// Returns false if no widget has focus.
+    pub fn command(&mut self, cmd: Command) -> bool
+    where
+        W::Msg: Clone,
+    {
+        let id = match self.with_manager(|mgr, _| mgr.char_focus_id()) {
+            Some(id) => id,
+            None => return false,
+        };
+        self.send(id, Event::Command(cmd, false));
+        true
+    }
+
+    /// Press and release `vkey`
+    ///
+    /// The key is routed as a key press from the window (without modifiers),
+    /// thus may trigger a window's default or cancel action, activate the
+    /// widget with navigation focus, etc.
+    pub fn press_key(&mut self, vkey: VirtualKeyCode)
+    where
+        W: Widget<Msg = VoidMsg>,
+    {
+        self.with_manager(|mgr, widget| {
+            mgr.start_key_event(widget, vkey, 0);
+            mgr.end_key_event(0);
+        });
+    }
+
+    /// Process pending events, as the shell does after each input event
+    ///
+    /// For example, [`Event::NavFocus`] is only sent to a widget given
+    /// navigation focus by this method. Any resulting action is captured.
+    pub fn update(&mut self)
+    where
+        W: Widget<Msg = VoidMsg>,
+    {
+        self.action |= self.state.update(&mut self.shell, &mut self.widget);
+    }
+
+    /// Start recording input
+    ///
+    /// Input events sent to widgets, whether via [`TestHarness::send`] or by
+    /// the event manager (e.g. from [`TestHarness::press_key`]), are recorded
+    /// with their target; see [`Recording`]. Any previous recording is
+    /// discarded.
+    pub fn start_recording(&mut self) {
+        *self.recording.borrow_mut() = Some((Instant::now(), Recording::default()));
+        let recording = self.recording.clone();
+        let observer = EventObserver::new(move |root, id, event, coord| {
+            if let Some((last, recording)) = recording.borrow_mut().as_mut() {
+                let now = Instant::now();
+                if recording.record(root, id, event, coord, now - *last) {
+                    *last = now;
+                }
+            }
+        });
+        self.state.set_event_observer(Some(observer));
+    }
+
+    /// Stop recording, returning the result
+    pub fn stop_recording(&mut self) -> Recording {
+        self.state.set_event_observer(None);
+        let recording = self.recording.borrow_mut().take();
+        recording.map(|r| r.1).unwrap_or_default()
+    }
+
+    /// Replay a recording
+    ///
+    /// Each event is resolved against the widget's current layout. Clicks and
+    /// scrolling are injected as with [`TestHarness::click_at`] and
+    /// [`TestHarness::scroll_at`]; text and commands are sent to the recorded
+    /// target, which is given character focus for text input. If `timing`,
+    /// recorded delays between events are preserved (by sleeping).
+    ///
+    /// Stops at the first event which cannot be replayed.
+    pub fn replay(&mut self, recording: &Recording, timing: bool) -> Result<(), ReplayError>
+    where
+        W::Msg: Clone,
+    {
+        for (i, event) in recording.events.iter().enumerate() {
+            if timing {
+                std::thread::sleep(Duration::from_millis(event.delay));
+            }
+            let (pos, id) = match event.target.resolve(&self.widget) {
+                Some(widget) => (widget.rect().pos, widget.id()),
+                None => return Err(ReplayError::TargetNotFound(i)),
+            };
+            let ok = match event.input {
+                RecordedInput::Click(offset) => self.click_at(pos + offset),
+                RecordedInput::Scroll(offset, delta) => self.scroll_at(pos + offset, delta),
+                RecordedInput::Text(ref text) => {
+                    self.with_manager(|mgr, _| {
+                        if mgr.char_focus_id() != Some(id) {
+                            mgr.request_char_focus(id);
+                        }
+                    });
+                    for c in text.chars() {
+                        self.send(id, Event::ReceivedCharacter(c));
+                    }
+                    true
+                }
+                RecordedInput::Command(cmd) => {
+                    self.send(id, Event::Command(cmd, false));
+                    true
+                }
+            };
+            if !ok {
+                return Err(ReplayError::TargetNotFound(i));
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw the widget, returning the [`DrawHandle`] methods called
+    ///
+    /// See [`TestDrawHandle`].
+    pub fn draw(&self) -> Vec<&'static str> {
+        let mut handle = TestDrawHandle::new(self.widget.rect());
+        self.widget.draw(&mut handle, &self.state, false);
+        handle.calls
+    }
+
+    /// Take captured messages
+    pub fn take_messages(&mut self) -> Vec<W::Msg> {
+        std::mem::take(&mut self.messages)
+    }
+
+    /// Take the captured [`TkAction`]
+    pub fn take_action(&mut self) -> TkAction {
+        std::mem::replace(&mut self.action, TkAction::empty())
+    }
+
+    /// Take updates triggered via [`Manager::trigger_update`]
+    pub fn take_updates(&mut self) -> Vec<(UpdateHandle, u64)> {
+        std::mem::take(&mut self.shell.updates)
+    }
+
+    /// Take the list of windows closed via [`Manager::close_window`]
+    pub fn take_closed_windows(&mut self) -> Vec<WindowId> {
+        std::mem::take(&mut self.shell.closed)
+    }
+
+    /// Get the clipboard contents
+    pub fn clipboard(&self) -> Option<&str> {
+        self.shell.clipboard.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event::VoidMsg;
+    use crate::macros::Widget;
+    use crate::widget::{Column, TextButton};
+    use crate::CoreData;
+    use std::borrow::Cow;
+
+    #[test]
+    fn click_button() {
+        let mut harness = TestHarness::new(TextButton::new_msg("OK", 1));
+        harness.resize(Size(100, 30));
+        assert!(harness.click_at(Coord(50, 15)));
+        assert_eq!(harness.take_messages(), vec![1]);
+        assert!(!harness.click_at(Coord(150, 15)));
+        assert!(harness.take_messages().is_empty());
+    }
+
+    #[test]
+    fn click_in_tree() {
+        let buttons = vec![TextButton::new_msg("A", 'a'), TextButton::new_msg("B", 'b')];
+        let mut harness = TestHarness::new(Column::new(buttons));
+        harness.resize(Size(100, 100));
+
+        // TestSizeHandle gives buttons fixed, non-overlapping rects
+        let (a, b) = (harness.widget()[0].rect(), harness.widget()[1].rect());
+        assert!(a.pos.1 + a.size.1 <= b.pos.1);
+        assert!(b.size.1 >= LINE_HEIGHT);
+
+        assert!(harness.click_at(b.pos + b.size / 2));
+        assert!(harness.click_at(a.pos + a.size / 2));
+        assert_eq!(harness.take_messages(), vec![(1, 'b'), (0, 'a')]);
+    }
+
+    #[test]
+    fn shell_requests() {
+        let mut harness = TestHarness::new(TextButton::new_msg("OK", ()));
+        let handle = UpdateHandle::new();
+        let id = WindowId::new(NonZeroU32::new(7).unwrap());
+        let pasted = harness.with_manager(|mgr, _| {
+            mgr.trigger_update(handle, 3);
+            mgr.close_window(id);
+            mgr.set_clipboard(Cow::Borrowed("text"));
+            mgr.get_clipboard()
+        });
+        assert_eq!(harness.take_updates(), vec![(handle, 3)]);
+        assert_eq!(harness.take_closed_windows(), vec![id]);
+        assert_eq!(pasted.as_deref(), Some("text"));
+        assert_eq!(harness.clipboard(), Some("text"));
+    }
+
+    fn buttons() -> Column<TextButton<char>> {
+        let buttons = vec!["A", "B", "C"];
+        let iter = buttons.into_iter().zip("abc".chars());
+        Column::new(iter.map(|(s, c)| TextButton::new_msg(s, c)).collect())
+    }
+
+    fn centre(rect: Rect) -> Coord {
+        rect.pos + rect.size / 2
+    }
+
+    #[test]
+    fn record_replay() {
+        let mut harness = TestHarness::new(buttons());
+        harness.resize(Size(100, 100));
+        harness.start_recording();
+        for i in &[2, 0, 2] {
+            let rect = harness.widget()[*i].rect();
+            assert!(harness.click_at(centre(rect)));
+        }
+        let recording = harness.stop_recording();
+        let messages = harness.take_messages();
+        assert_eq!(messages, vec![(2, 'c'), (0, 'a'), (2, 'c')]);
+        assert_eq!(recording.events.len(), 3);
+
+        // Replay against a fresh instance with a different layout
+        let mut harness = TestHarness::new(buttons());
+        harness.resize(Size(60, 200));
+        assert_eq!(harness.replay(&recording, false), Ok(()));
+        assert_eq!(harness.take_messages(), messages);
+
+        // Replay fails where the target no longer exists
+        let _ = harness.widget_mut().pop();
+        harness.reconfigure();
+        harness.resize(Size(60, 200));
+        let result = harness.replay(&recording, false);
+        assert_eq!(result, Err(ReplayError::TargetNotFound(0)));
+    }
+
+    #[test]
+    fn replay_text_to_target() {
+        use crate::class::HasStr;
+        use crate::widget::EditBox;
+
+        let boxes = || Column::new(vec![EditBox::new(""), EditBox::new("")]);
+        let mut harness = TestHarness::new(boxes());
+        harness.resize(Size(100, 100));
+        let rect = harness.widget()[1].rect();
+        assert!(harness.click_at(centre(rect)));
+        harness.start_recording();
+        assert!(harness.type_text("hi"));
+        let recording = harness.stop_recording();
+        assert_eq!(recording.events.len(), 2);
+
+        // The fresh instance has no character focus
+        let mut harness = TestHarness::new(boxes());
+        harness.resize(Size(100, 100));
+        assert_eq!(harness.replay(&recording, false), Ok(()));
+        assert_eq!(harness.widget()[0].get_str(), "");
+        assert_eq!(harness.widget()[1].get_str(), "hi");
+    }
+
+    #[test]
+    fn draw_z_order() {
+        use crate::dir::Right;
+        use crate::widget::{CheckBoxBare, ProgressBar, Separator};
+
+        // Three children in the same cell, defined in the opposite order to z
+        #[derive(Debug, Widget)]
+        #[layout(grid)]
+        struct Stack {
+            #[widget_core]
+            core: CoreData,
+            #[layout_data]
+            layout_data: <Self as crate::LayoutData>::Data,
+            #[widget(row = 0, col = 0, z = 1)]
+            top: CheckBoxBare<VoidMsg>,
+            #[widget(row = 0, col = 0)]
+            middle: Separator<VoidMsg>,
+            #[widget(row = 0, col = 0, z = -1)]
+            bottom: ProgressBar<Right>,
+        }
+
+        let mut harness = TestHarness::new(Stack {
+            core: Default::default(),
+            layout_data: Default::default(),
+            top: CheckBoxBare::new(),
+            middle: Separator::new(),
+            bottom: ProgressBar::new(),
+        });
+        harness.resize(Size(50, 50));
+        let calls = harness.draw();
+        assert_eq!(calls, vec!["progress_bar", "separator", "checkbox"]);
+
+        let top = harness.widget().top.rect();
+        let id = harness.find_id(centre(top));
+        assert_eq!(id, Some(harness.widget().top.id()));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn recording_json() {
+        let mut harness = TestHarness::new(buttons());
+        harness.resize(Size(100, 100));
+        harness.start_recording();
+        let rect = harness.widget()[1].rect();
+        assert!(harness.click_at(centre(rect)));
+        let recording = harness.stop_recording();
+
+        let json = serde_json::to_string(&recording).unwrap();
+        let recording2: Recording = serde_json::from_str(&json).unwrap();
+        assert_eq!(recording2, recording);
+    }
+}
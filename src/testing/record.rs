@@ -0,0 +1,207 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Event recording and playback
+//!
+//! Recording is implemented by [`super::TestHarness`] (see
+//! [`super::TestHarness::start_recording`]) using an
+//! [`crate::event::EventObserver`], and covers input events as delivered to
+//! their target, whether by the event manager or injected via
+//! [`super::TestHarness::send`]. Recordings are replayed by
+//! [`super::TestHarness::replay`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::event::{Command, ConfigError, ConfigFormat, Event, ScrollDelta};
+use crate::geom::{Coord, Offset};
+use crate::{WidgetChildren, WidgetConfig, WidgetCore, WidgetId};
+
+/// Stable identity of a widget
+///
+/// Unlike [`WidgetId`], which changes whenever widgets are added or removed
+/// anywhere in the tree, a path depends only on the widget's ancestors: it
+/// lists the child index at each level, starting from the root. The widget's
+/// type name is included to detect mismatches.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WidgetPath {
+    indices: Vec<usize>,
+    name: String,
+}
+
+impl WidgetPath {
+    /// Get the path of widget `id` within `root`
+    ///
+    /// Returns `None` if `id` is not a descendant of `root`.
+    pub fn of(root: &dyn WidgetConfig, id: WidgetId) -> Option<Self> {
+        let mut indices = vec![];
+        let mut widget = root;
+        while widget.id() != id {
+            let index = widget.find_child(id)?;
+            indices.push(index);
+            widget = widget.get_child(index)?;
+        }
+        let name = widget.widget_name().to_string();
+        Some(WidgetPath { indices, name })
+    }
+
+    /// Find the widget at this path within `root`
+    ///
+    /// Returns `None` if there is no widget at this path or if its type name
+    /// does not match.
+    pub fn resolve<'a>(&self, root: &'a dyn WidgetConfig) -> Option<&'a dyn WidgetConfig> {
+        let mut widget = root;
+        for index in &self.indices {
+            widget = widget.get_child(*index)?;
+        }
+        if widget.widget_name() == self.name {
+            Some(widget)
+        } else {
+            None
+        }
+    }
+}
+
+/// A recorded input
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RecordedInput {
+    /// Primary-button click at `offset` from the target's position
+    Click(Offset),
+    /// Scroll at `offset` from the target's position
+    Scroll(Offset, ScrollDelta),
+    /// Text input (to the target, which has character focus)
+    Text(String),
+    /// Command input (to the target, which has character focus)
+    Command(Command),
+}
+
+/// A recorded input with target
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordedEvent {
+    /// Milliseconds since the previous event (or start of recording)
+    pub delay: u64,
+    /// Target widget
+    pub target: WidgetPath,
+    /// The input
+    pub input: RecordedInput,
+}
+
+/// A sequence of recorded events
+///
+/// Recordings are made and replayed using a [`super::TestHarness`] (see
+/// [module docs](self)). With
+/// the `json` or `yaml` feature, they may be saved to and read from a file, for
+/// example to attach a reproduction to a bug report.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recording {
+    pub events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    /// Record `event`, sent to widget `id` within `root`
+    ///
+    /// Only input events are recorded (see [`RecordedInput`]); returns true
+    /// if `event` was recorded. `mouse_coord` is the cursor position, which is
+    /// used for scroll events.
+    pub(crate) fn record(
+        &mut self,
+        root: &dyn WidgetConfig,
+        id: WidgetId,
+        event: &Event,
+        mouse_coord: Coord,
+        delay: Duration,
+    ) -> bool {
+        let target = match WidgetPath::of(root, id) {
+            Some(path) => path,
+            None => return false,
+        };
+        let pos = match target.resolve(root) {
+            Some(widget) => widget.rect().pos,
+            None => return false,
+        };
+        let input = match *event {
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                RecordedInput::Click(coord - pos)
+            }
+            Event::Scroll(delta) => RecordedInput::Scroll(mouse_coord - pos, delta),
+            Event::ReceivedCharacter(c) => RecordedInput::Text(c.to_string()),
+            Event::Command(cmd, _) => RecordedInput::Command(cmd),
+            _ => return false,
+        };
+        self.events.push(RecordedEvent {
+            delay: delay.as_millis() as u64,
+            target,
+            input,
+        });
+        true
+    }
+
+    fn guess_format(path: &Path) -> ConfigFormat {
+        match path.extension() {
+            Some(ext) if ext == "json" => ConfigFormat::Json,
+            Some(ext) if ext == "yaml" => ConfigFormat::Yaml,
+            _ => ConfigFormat::Unknown,
+        }
+    }
+
+    /// Read from a path
+    pub fn from_path(path: &Path, mut format: ConfigFormat) -> Result<Self, ConfigError> {
+        if format == ConfigFormat::None {
+            format = Self::guess_format(path);
+        }
+
+        match format {
+            #[cfg(feature = "json")]
+            ConfigFormat::Json => {
+                let r = std::io::BufReader::new(std::fs::File::open(path)?);
+                Ok(serde_json::from_reader(r)?)
+            }
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => {
+                let r = std::io::BufReader::new(std::fs::File::open(path)?);
+                Ok(serde_yaml::from_reader(r)?)
+            }
+            _ => Err(ConfigError::UnsupportedFormat(format)),
+        }
+    }
+
+    /// Write to a path
+    pub fn write_path(&self, path: &Path, mut format: ConfigFormat) -> Result<(), ConfigError> {
+        if format == ConfigFormat::None {
+            format = Self::guess_format(path);
+        }
+
+        match format {
+            #[cfg(feature = "json")]
+            ConfigFormat::Json => {
+                let w = std::io::BufWriter::new(std::fs::File::create(path)?);
+                serde_json::to_writer_pretty(w, self)?;
+                Ok(())
+            }
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => {
+                let w = std::io::BufWriter::new(std::fs::File::create(path)?);
+                serde_yaml::to_writer(w, self)?;
+                Ok(())
+            }
+            _ => Err(ConfigError::UnsupportedFormat(format)),
+        }
+    }
+}
+
+/// Error from [`super::TestHarness::replay`]
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The target of the event with this index was not found
+    #[error("event {0}: target widget not found")]
+    TargetNotFound(usize),
+}
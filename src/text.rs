@@ -16,6 +16,9 @@ pub use kas_text::*;
 mod selection;
 pub use selection::SelectionHelper;
 
+mod rich;
+pub use rich::{RichText, SpanStyle};
+
 mod string;
 pub use string::AccelString;
 
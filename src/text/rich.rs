@@ -0,0 +1,223 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Rich text: a sequence of styled spans
+
+use kas::conv::Conv;
+use kas::draw::Colour;
+use kas::text::fonts::FontId;
+use kas::text::format::{FontToken, FormattableText};
+#[cfg(not(feature = "gat"))]
+use kas::text::OwningVecIter;
+use kas::text::{Effect, EffectFlags};
+
+/// Style of a [`RichText`] span
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpanStyle {
+    /// Font face
+    ///
+    /// Use this to select e.g. a bold or italic face loaded via
+    /// [`kas::text::fonts`]. The default value selects the default font.
+    pub font_id: FontId,
+    /// Font size, relative to the theme's font size
+    pub scale: f32,
+    /// Text effects: underline, strikethrough
+    pub flags: EffectFlags,
+    /// Text colour
+    ///
+    /// If `None`, the theme's colour for the text class is used. Note that
+    /// [`kas::widget::RichLabel`] only draws span colours when the `min_spec`
+    /// feature is enabled.
+    pub colour: Option<Colour>,
+}
+
+impl Default for SpanStyle {
+    fn default() -> Self {
+        SpanStyle {
+            font_id: Default::default(),
+            scale: 1.0,
+            flags: EffectFlags::empty(),
+            colour: None,
+        }
+    }
+}
+
+impl SpanStyle {
+    /// Underlined text
+    pub fn underline() -> Self {
+        SpanStyle {
+            flags: EffectFlags::UNDERLINE,
+            ..Default::default()
+        }
+    }
+
+    /// Text scaled relative to the theme's font size
+    pub fn scaled(scale: f32) -> Self {
+        SpanStyle {
+            scale,
+            ..Default::default()
+        }
+    }
+
+    /// Text in the given colour
+    pub fn coloured(colour: Colour) -> Self {
+        SpanStyle {
+            colour: Some(colour),
+            ..Default::default()
+        }
+    }
+}
+
+/// Text composed of styled spans
+///
+/// Each span has a [`SpanStyle`]; text wraps across spans as normal. This type
+/// may be used with any widget which is generic over text type, e.g.
+/// [`kas::widget::RichLabel`].
+///
+/// Spans without a [`SpanStyle::colour`] use the theme's text colour.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichText {
+    text: String,
+    // Start index and style of each span; never contains an empty span
+    spans: Vec<(u32, SpanStyle)>,
+    effects: Vec<Effect<()>>,
+    col_effects: Vec<Effect<Option<Colour>>>,
+}
+
+impl RichText {
+    /// Construct an empty instance
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Append a span
+    pub fn push(&mut self, text: &str, style: SpanStyle) {
+        if text.is_empty() {
+            return;
+        }
+        let start = u32::conv(self.text.len());
+        self.text.push_str(text);
+        self.spans.push((start, style));
+
+        let last_flags = self.effects.last().map(|e| e.flags);
+        if last_flags.unwrap_or(EffectFlags::empty()) != style.flags {
+            self.effects.push(Effect {
+                start,
+                flags: style.flags,
+                aux: (),
+            });
+        }
+
+        let last = self.col_effects.last().map(|e| (e.flags, e.aux));
+        if last.unwrap_or((EffectFlags::empty(), None)) != (style.flags, style.colour) {
+            self.col_effects.push(Effect {
+                start,
+                flags: style.flags,
+                aux: style.colour,
+            });
+        }
+    }
+
+    /// Append a span (inline)
+    pub fn with(mut self, text: &str, style: SpanStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// Append a span with default style (inline)
+    pub fn with_plain(self, text: &str) -> Self {
+        self.with(text, SpanStyle::default())
+    }
+
+    /// Get the number of spans
+    pub fn num_spans(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Get the style of span `index`
+    pub fn span_style(&self, index: usize) -> Option<SpanStyle> {
+        self.spans.get(index).map(|span| span.1)
+    }
+
+    /// Get the text of span `index`
+    pub fn span_text(&self, index: usize) -> Option<&str> {
+        let start = usize::conv(self.spans.get(index)?.0);
+        let end = self
+            .spans
+            .get(index + 1)
+            .map(|span| usize::conv(span.0))
+            .unwrap_or(self.text.len());
+        Some(&self.text[start..end])
+    }
+
+    /// Find the span containing text index `index`
+    ///
+    /// Returns `None` if `index` is beyond the end of the text.
+    pub fn span_at(&self, index: usize) -> Option<usize> {
+        if index >= self.text.len() {
+            return None;
+        }
+        let index = u32::conv(index);
+        match self.spans.binary_search_by_key(&index, |span| span.0) {
+            Ok(i) => Some(i),
+            Err(i) => Some(i - 1),
+        }
+    }
+
+    /// Get effects including per-span colours
+    ///
+    /// This is like [`FormattableText::effect_tokens`] but additionally
+    /// includes the colour of each span, as used by
+    /// [`kas::draw::DrawHandle::text_col_spans`].
+    pub fn colour_effects(&self) -> &[Effect<Option<Colour>>] {
+        &self.col_effects
+    }
+
+    fn make_font_tokens(&self, dpp: f32, pt_size: f32) -> Vec<FontToken> {
+        let is_default =
+            |style: &SpanStyle| style.font_id == FontId::default() && style.scale == 1.0;
+        if self.spans.iter().all(|span| is_default(&span.1)) {
+            return vec![];
+        }
+        let dpem = dpp * pt_size;
+        self.spans
+            .iter()
+            .map(|(start, style)| FontToken {
+                start: *start,
+                font_id: style.font_id,
+                dpem: dpem * style.scale,
+            })
+            .collect()
+    }
+}
+
+impl FormattableText for RichText {
+    #[cfg(feature = "gat")]
+    type FontTokenIter<'a> = std::vec::IntoIter<FontToken>;
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    #[cfg(feature = "gat")]
+    fn font_tokens<'a>(&'a self, dpp: f32, pt_size: f32) -> Self::FontTokenIter<'a> {
+        self.make_font_tokens(dpp, pt_size).into_iter()
+    }
+    #[cfg(not(feature = "gat"))]
+    fn font_tokens(&self, dpp: f32, pt_size: f32) -> OwningVecIter<FontToken> {
+        OwningVecIter::new(self.make_font_tokens(dpp, pt_size))
+    }
+
+    fn effect_tokens(&self) -> &[Effect<()>] {
+        &self.effects
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        RichText::new().with_plain(text)
+    }
+}
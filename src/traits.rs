@@ -9,7 +9,9 @@ use std::any::Any;
 use std::fmt;
 use std::ops::DerefMut;
 
+use crate::draw::Colour;
 use crate::event::{self, Manager};
+use crate::geom::Coord;
 use crate::{dir::Direction, layout, WidgetId, WindowId};
 
 mod impls;
@@ -70,7 +72,47 @@ pub struct Popup {
     pub direction: Direction,
 }
 
+/// Initial position of a window
+///
+/// See [`Window::position`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WindowPosition {
+    /// Let the platform choose
+    Default,
+    /// Centred on the current monitor
+    ///
+    /// Note: the full monitor area is used; areas reserved by the desktop
+    /// environment (e.g. panels) are not excluded, thus the window may appear
+    /// off-centre or partially obscured.
+    Centered,
+    /// At the given coordinate (top-left corner of the window's frame, in
+    /// physical pixels relative to the desktop)
+    At(Coord),
+    /// Centred on the monitor with the given index
+    ///
+    /// Indices refer to the list of available monitors as reported by the
+    /// platform. If no monitor with this index exists, the platform chooses.
+    ///
+    /// Note: as with [`WindowPosition::Centered`], the full monitor area is
+    /// used. Additionally, the window's initial size is calculated using the
+    /// scale factor of the monitor on which the window is created, not the
+    /// target monitor. If these differ, the window is resized after moving
+    /// (once the platform reports the new scale factor) and may then no longer
+    /// be exactly centred.
+    OnMonitor(usize),
+}
+
+impl Default for WindowPosition {
+    fn default() -> Self {
+        WindowPosition::Default
+    }
+}
+
 /// Functionality required by a window
+///
+/// This trait is object-safe. Shells store windows as `Box<dyn Window>` (see
+/// for example [`Manager::add_window`]), thus there is no need to make
+/// window-construction code generic over the window type.
 pub trait Window: Widget<Msg = event::VoidMsg> {
     /// Get the window title
     fn title(&self) -> &str;
@@ -87,6 +129,38 @@ pub trait Window: Widget<Msg = event::VoidMsg> {
     /// windows.
     fn restrict_dimensions(&self) -> (bool, bool);
 
+    /// Whether to size the window to its content before first show
+    ///
+    /// If true, the window is initially hidden, then sized to the ideal size
+    /// of its content as calculated at the window's actual scale factor
+    /// (never less than the minimum size and, where possible, no larger than
+    /// the monitor). Without this, the initial size is calculated using the
+    /// scale factor of the previous window, which may be inaccurate.
+    ///
+    /// Default: `false`.
+    fn size_to_content(&self) -> bool {
+        false
+    }
+
+    /// Initial position of the window
+    ///
+    /// Default: [`WindowPosition::Default`].
+    fn position(&self) -> WindowPosition {
+        WindowPosition::Default
+    }
+
+    /// Whether the window should be redrawn continuously
+    ///
+    /// If true, the shell draws new frames at a regular interval (up to around
+    /// 60 per second, also limited by vsync), instead of waiting for input or
+    /// a timer. This is paused while the window does not have focus.
+    /// See also [`Manager::update_on_frame`].
+    ///
+    /// Default: `false`.
+    fn continuous_redraw(&self) -> bool {
+        false
+    }
+
     /// Add a pop-up as a layer in the current window
     ///
     /// Each [`Popup`] is assigned a [`WindowId`]; both are passed.
@@ -128,6 +202,31 @@ pub enum ThemeAction {
     ThemeResize,
 }
 
+/// A standard colour scheme
+///
+/// Themes may support additional schemes by name; see
+/// [`ThemeApi::set_colours`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ColourScheme {
+    /// Dark text on a light background
+    Light,
+    /// Light text on a dark background
+    Dark,
+    /// A scheme maximising contrast
+    HighContrast,
+}
+
+impl ColourScheme {
+    /// The scheme name, as used by [`ThemeApi::set_colours`]
+    pub fn name(self) -> &'static str {
+        match self {
+            ColourScheme::Light => "light",
+            ColourScheme::Dark => "dark",
+            ColourScheme::HighContrast => "contrast",
+        }
+    }
+}
+
 /// Interface through which a theme can be adjusted at run-time
 ///
 /// All methods return a [`ThemeAction`] to enable correct action when a theme
@@ -139,12 +238,43 @@ pub trait ThemeApi {
     /// Units: Points per Em (standard unit of font size)
     fn set_font_size(&mut self, pt_size: f32) -> ThemeAction;
 
+    /// Set font scale
+    ///
+    /// This factor multiplies the font size only, without affecting other
+    /// dimensions (unlike [`event::Config::ui_scale`]). Default value: 1.
+    ///
+    /// The default implementation does nothing.
+    fn set_font_scale(&mut self, _scale: f32) -> ThemeAction {
+        ThemeAction::None
+    }
+
     /// Change the colour scheme
     ///
     /// If no scheme by this name is found the scheme is left unchanged.
     // TODO: revise scheme identification and error handling?
     fn set_colours(&mut self, _scheme: &str) -> ThemeAction;
 
+    /// Change to a standard colour scheme
+    ///
+    /// The default implementation calls [`ThemeApi::set_colours`] with
+    /// [`ColourScheme::name`].
+    fn set_colour_scheme(&mut self, scheme: ColourScheme) -> ThemeAction {
+        self.set_colours(scheme.name())
+    }
+
+    /// Override a named palette colour
+    ///
+    /// Standard entries are `"selection_bg"` (selected text and list items),
+    /// `"hover_bg"` (buttons and menu entries under the mouse) and
+    /// `"focus_ring"` (keyboard navigation highlight). Themes may support
+    /// other entries. Overrides persist when the colour scheme is changed (see
+    /// [`ThemeApi::set_colours`]).
+    ///
+    /// The default implementation does nothing.
+    fn set_palette_colour(&mut self, _name: &str, _col: Colour) -> ThemeAction {
+        ThemeAction::None
+    }
+
     /// Switch the theme
     ///
     /// Most themes do not react to this method; `kas_theme::MultiTheme` uses
@@ -158,9 +288,18 @@ impl<T: ThemeApi> ThemeApi for Box<T> {
     fn set_font_size(&mut self, size: f32) -> ThemeAction {
         self.deref_mut().set_font_size(size)
     }
+    fn set_font_scale(&mut self, scale: f32) -> ThemeAction {
+        self.deref_mut().set_font_scale(scale)
+    }
     fn set_colours(&mut self, scheme: &str) -> ThemeAction {
         self.deref_mut().set_colours(scheme)
     }
+    fn set_colour_scheme(&mut self, scheme: ColourScheme) -> ThemeAction {
+        self.deref_mut().set_colour_scheme(scheme)
+    }
+    fn set_palette_colour(&mut self, name: &str, col: Colour) -> ThemeAction {
+        self.deref_mut().set_palette_colour(name, col)
+    }
     fn set_theme(&mut self, theme: &str) -> ThemeAction {
         self.deref_mut().set_theme(theme)
     }
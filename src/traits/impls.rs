@@ -82,12 +82,21 @@ impl<M: 'static> WidgetConfig for Box<dyn Widget<Msg = M>> {
     fn key_nav(&self) -> bool {
         self.as_ref().key_nav()
     }
+    fn is_default_focus(&self) -> bool {
+        self.as_ref().is_default_focus()
+    }
+    fn uses_return_key(&self) -> bool {
+        self.as_ref().uses_return_key()
+    }
     fn hover_highlight(&self) -> bool {
         self.as_ref().hover_highlight()
     }
     fn cursor_icon(&self) -> event::CursorIcon {
         self.as_ref().cursor_icon()
     }
+    fn cursor_icon_at(&self, coord: Coord) -> Option<event::CursorIcon> {
+        self.as_ref().cursor_icon_at(coord)
+    }
 }
 
 impl<M: 'static> Layout for Box<dyn Widget<Msg = M>> {
@@ -99,6 +108,10 @@ impl<M: 'static> Layout for Box<dyn Widget<Msg = M>> {
         self.as_mut().set_rect(mgr, rect, align);
     }
 
+    fn is_child_active(&self, child_index: usize) -> bool {
+        self.as_ref().is_child_active(child_index)
+    }
+
     fn find_id(&self, coord: Coord) -> Option<WidgetId> {
         self.as_ref().find_id(coord)
     }
@@ -120,8 +120,8 @@ pub trait WidgetCore: Any + fmt::Debug {
     /// The `disabled` flag is inherited from parents. [`InputState::disabled`]
     /// will be true if either `disabled` or `self.is_disabled()` are true.
     ///
-    /// The error state defaults to `false` since most widgets don't support
-    /// this.
+    /// The error state is set when a validation error has been associated
+    /// with this widget via [`Manager::set_widget_error`].
     ///
     /// Note: most state changes should automatically cause a redraw, but change
     /// in `hover` status will not (since this happens frequently and many
@@ -132,7 +132,8 @@ pub trait WidgetCore: Any + fmt::Debug {
         let (char_focus, sel_focus) = mgr.char_focus(id);
         InputState {
             disabled: self.core_data().disabled || disabled,
-            error: false,
+            error: mgr.widget_error(id).is_some(),
+            read_only: false,
             hover: mgr.is_hovered(id),
             depress: mgr.is_depressed(id),
             nav_focus: mgr.nav_focus(id),
@@ -195,6 +196,19 @@ pub trait WidgetChildren: WidgetCore {
     /// This method may be removed in the future.
     fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig>;
 
+    /// Iterate over child widgets
+    ///
+    /// This iterates over direct children only, in index order; see
+    /// [`Self::walk_children`] for a recursive walk.
+    #[inline]
+    fn children(&self) -> ChildIter<'_> {
+        ChildIter {
+            widget: self.as_widget(),
+            start: 0,
+            end: self.num_children(),
+        }
+    }
+
     /// Check whether `id` is a descendant
     ///
     /// This function assumes that `id` is a valid widget.
@@ -359,6 +373,26 @@ pub trait WidgetConfig: Layout {
         false
     }
 
+    /// Should this widget receive navigation focus when the window opens?
+    ///
+    /// When a window is first configured, navigation focus is given to the
+    /// first enabled widget returning true, or, if there is none, to the first
+    /// widget for which [`WidgetConfig::key_nav`] is true.
+    fn is_default_focus(&self) -> bool {
+        false
+    }
+
+    /// Does this widget use the Return key while it has character focus?
+    ///
+    /// If false, the Return key goes to the window's default action (see
+    /// [`crate::widget::Window::set_default_action`]) and default widget
+    /// before this widget. A multi-line edit box should return true.
+    ///
+    /// Defaults to `false`.
+    fn uses_return_key(&self) -> bool {
+        false
+    }
+
     /// Does this widget have hover-state highlighting?
     ///
     /// If true, a redraw will be requested whenever this widget gains or loses
@@ -373,6 +407,61 @@ pub trait WidgetConfig: Layout {
     fn cursor_icon(&self) -> event::CursorIcon {
         event::CursorIcon::Default
     }
+
+    /// Which cursor icon should be used at the given coordinate?
+    ///
+    /// This is consulted whenever the mouse moves over this widget (or one of
+    /// its descendants), allowing the icon to depend on the pointer position
+    /// or on widget state. The `coord` is in this widget's coordinate space.
+    /// If `Some(icon)` is returned, this overrides [`WidgetConfig::cursor_icon`].
+    ///
+    /// Defaults to `None`.
+    fn cursor_icon_at(&self, coord: Coord) -> Option<event::CursorIcon> {
+        let _ = coord;
+        None
+    }
+}
+
+/// Iterator over the children of a widget
+///
+/// This is returned by [`WidgetChildren::children`].
+#[derive(Clone)]
+pub struct ChildIter<'a> {
+    widget: &'a dyn WidgetConfig,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Iterator for ChildIter<'a> {
+    type Item = &'a dyn WidgetConfig;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.start < self.end {
+            let index = self.start;
+            self.start += 1;
+            if let Some(child) = self.widget.get_child(index) {
+                return Some(child);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.start))
+    }
+}
+
+impl<'a> DoubleEndedIterator for ChildIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.start < self.end {
+            self.end -= 1;
+            if let Some(child) = self.widget.get_child(self.end) {
+                return Some(child);
+            }
+        }
+        None
+    }
 }
 
 /// Positioning and drawing routines for widgets
@@ -423,6 +512,9 @@ pub trait Layout: WidgetChildren {
     /// One may assume that `size_rules` has been called at least once for each
     /// axis with current size information before this method, however
     /// `size_rules` might not be re-called before calling `set_rect` again.
+    ///
+    /// The `rect` is in physical pixels (see
+    /// [pixel units](kas::geom#pixel-units)).
     #[inline]
     fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
         let _ = (mgr, align);
@@ -460,6 +552,19 @@ pub trait Layout: WidgetChildren {
         (0, self.num_children().wrapping_sub(1))
     }
 
+    /// Whether a child is currently part of the layout
+    ///
+    /// Widgets holding children which are not currently positioned, drawn or
+    /// accessible (e.g. the inactive pages of a `TabView` or unused widgets
+    /// of a view's pool) should return `false` for these. Such children are
+    /// excluded from [`Manager::rect_of`].
+    ///
+    /// The default implementation returns `true`.
+    #[inline]
+    fn is_child_active(&self, _child_index: usize) -> bool {
+        true
+    }
+
     /// Find a widget by coordinate
     ///
     /// Used to find the widget responsible for handling events at this `coord`
@@ -530,7 +635,16 @@ pub trait Layout: WidgetChildren {
 /// `<M, W: Widget<Msg = M>>`.
 ///
 /// [`derive(Widget)`]: macros/index.html#the-derivewidget-macro
-pub trait Widget: event::SendEvent {}
+pub trait Widget: event::SendEvent {
+    /// Describe the layout of this widget and its descendants
+    ///
+    /// The result lists one widget per line, indented by depth, with its id,
+    /// name and rect. See also [`kas::layout::debug_size_rules`].
+    fn debug_layout(&self) -> String {
+        let s = crate::layout::WidgetHeirarchy(self.as_widget(), 0).to_string();
+        s.trim_start().to_string()
+    }
+}
 
 /// Provides a convenient `.boxed()` method on implementors
 pub trait Boxed<T: ?Sized> {
@@ -7,6 +7,7 @@
 
 use std::fmt::{self, Debug};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use kas::draw::TextClass;
 use kas::event::{self, VirtualKeyCode, VirtualKeyCodes};
@@ -24,14 +25,27 @@ pub struct TextButton<M: 'static> {
     // label_rect: Rect,
     label: Text<AccelString>,
     on_push: Option<Rc<dyn Fn(&mut Manager) -> Option<M>>>,
+    repeat: Option<Repeat>,
+    // While repeating: time of next repeat and current interval
+    repeat_next: Option<(Instant, Duration)>,
+    is_default: bool,
+}
+
+/// Auto-repeat parameters
+#[derive(Clone, Copy, Debug)]
+struct Repeat {
+    delay: Duration,
+    interval: Duration,
+    factor: f32,
+    min_interval: Duration,
 }
 
 impl<M: 'static> Debug for TextButton<M> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "TextButton {{ core: {:?}, keys1: {:?}, frame_size: {:?}, label: {:?}, ... }}",
-            self.core, self.keys1, self.frame_size, self.label,
+            "TextButton {{ core: {:?}, keys1: {:?}, frame_size: {:?}, label: {:?}, repeat: {:?}, is_default: {:?}, ... }}",
+            self.core, self.keys1, self.frame_size, self.label, self.repeat, self.is_default,
         )
     }
 }
@@ -40,11 +54,17 @@ impl<M: 'static> WidgetConfig for TextButton<M> {
     fn configure(&mut self, mgr: &mut Manager) {
         mgr.add_accel_keys(self.id(), &self.keys1);
         mgr.add_accel_keys(self.id(), &self.label.text().keys());
+        if self.is_default {
+            mgr.set_default_widget(self.id());
+        }
     }
 
     fn key_nav(&self) -> bool {
         true
     }
+    fn is_default_focus(&self) -> bool {
+        self.is_default
+    }
     fn hover_highlight(&self) -> bool {
         true
     }
@@ -93,6 +113,9 @@ impl TextButton<VoidMsg> {
             // label_rect: Default::default(),
             label: text,
             on_push: None,
+            repeat: None,
+            repeat_next: None,
+            is_default: false,
         }
     }
 
@@ -112,6 +135,9 @@ impl TextButton<VoidMsg> {
             frame_size: self.frame_size,
             label: self.label,
             on_push: Some(Rc::new(f)),
+            repeat: self.repeat,
+            repeat_next: None,
+            is_default: self.is_default,
         }
     }
 }
@@ -151,6 +177,52 @@ impl<M: 'static> TextButton<M> {
         self.keys1.extend_from_slice(keys);
         self
     }
+
+    /// Make this the default button (chain style)
+    ///
+    /// The default button receives navigation focus when the window opens and
+    /// is activated by the `Return` key (see [`Manager::set_default_widget`]).
+    pub fn with_default(mut self, is_default: bool) -> Self {
+        self.is_default = is_default;
+        self
+    }
+
+    /// Enable auto-repeat (chain style)
+    ///
+    /// When enabled, the button activates immediately on press (instead of on
+    /// release), then again after `initial_delay` and every `interval`
+    /// thereafter, for as long as the press is held over the button. This is
+    /// intended for things like increment/decrement and scroll-step buttons.
+    ///
+    /// Activation via the keyboard or [`Event::Activate`] is not repeated.
+    pub fn with_repeat(mut self, initial_delay: Duration, interval: Duration) -> Self {
+        self.repeat = Some(Repeat {
+            delay: initial_delay,
+            interval,
+            factor: 1.0,
+            min_interval: interval,
+        });
+        self
+    }
+
+    /// Set auto-repeat acceleration (chain style)
+    ///
+    /// After each repeat, the interval is multiplied by `factor` (expected to
+    /// be less than 1), down to a minimum of `min_interval`. Has no effect
+    /// unless [`TextButton::with_repeat`] is also used.
+    pub fn with_repeat_acceleration(mut self, factor: f32, min_interval: Duration) -> Self {
+        if let Some(repeat) = self.repeat.as_mut() {
+            repeat.factor = factor;
+            repeat.min_interval = min_interval;
+        }
+        self
+    }
+
+    // Schedule the next repeat, `delay` from now
+    fn schedule_repeat(&mut self, mgr: &mut Manager, delay: Duration, interval: Duration) {
+        self.repeat_next = Some((Instant::now() + delay, interval));
+        mgr.update_on_timer(delay, self.id());
+    }
 }
 
 impl<M: 'static> HasStr for TextButton<M> {
@@ -175,12 +247,54 @@ impl<M: 'static> event::Handler for TextButton<M> {
 
     #[inline]
     fn activation_via_press(&self) -> bool {
-        true
+        // With auto-repeat we handle press events ourselves
+        self.repeat.is_none()
     }
 
     fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<M> {
         match event {
             Event::Activate => self.on_push.as_ref().and_then(|f| f(mgr)).into(),
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                let repeat = match self.repeat {
+                    Some(repeat) => repeat,
+                    None => return Response::Unhandled(event),
+                };
+                if mgr.request_grab(self.id(), source, coord, event::GrabMode::Grab, None) {
+                    mgr.set_grab_depress(source, Some(self.id()));
+                    self.schedule_repeat(mgr, repeat.delay, repeat.interval);
+                }
+                self.on_push.as_ref().and_then(|f| f(mgr)).into()
+            }
+            Event::PressMove { source, cur_id, .. } if self.repeat.is_some() => {
+                let target = cur_id.filter(|id| *id == self.id());
+                mgr.set_grab_depress(source, target);
+                if target.is_none() {
+                    self.repeat_next = None;
+                } else if self.repeat_next.is_none() {
+                    let interval = self.repeat.unwrap().interval;
+                    self.schedule_repeat(mgr, interval, interval);
+                }
+                Response::None
+            }
+            Event::PressEnd { .. } if self.repeat.is_some() => {
+                self.repeat_next = None;
+                Response::None
+            }
+            Event::TimerUpdate => {
+                let (repeat, (next, interval)) = match (self.repeat, self.repeat_next) {
+                    (Some(repeat), Some(next)) => (repeat, next),
+                    _ => return Response::None,
+                };
+                let now = Instant::now();
+                if now < next {
+                    // An earlier timer fired (e.g. from a previous press)
+                    mgr.update_on_timer(next - now, self.id());
+                    return Response::None;
+                }
+                let next_interval = interval.mul_f32(repeat.factor).max(repeat.min_interval);
+                self.schedule_repeat(mgr, interval, next_interval);
+                self.on_push.as_ref().and_then(|f| f(mgr)).into()
+            }
             event => Response::Unhandled(event),
         }
     }
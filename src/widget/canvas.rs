@@ -0,0 +1,141 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Canvas: custom drawing via a closure
+
+use std::fmt::{self, Debug};
+use std::time::Duration;
+
+use kas::draw::ClipRegion;
+use kas::event::{self, ManagerState};
+use kas::geom::Vec2;
+use kas::prelude::*;
+
+/// A canvas for custom drawing
+///
+/// Each time the canvas is drawn, the closure `draw_fn` is called with the
+/// canvas's `rect`, a [`DrawHandle`] and the [`ManagerState`]. Drawing is
+/// clipped to `rect`. This is a simpler alternative to implementing
+/// [`Layout::draw`] on a custom widget, e.g. for charts and gauges.
+///
+/// For animation, either configure a frame interval via
+/// [`Canvas::with_frame_interval`] or request redraws explicitly via
+/// [`Canvas::request_redraw`] (e.g. on data updates).
+#[derive(Clone, Widget)]
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+pub struct Canvas<F: Fn(Rect, &mut dyn DrawHandle, &ManagerState) + 'static> {
+    #[widget_core]
+    core: CoreData,
+    min_size: Vec2,
+    frame_interval: Option<Duration>,
+    draw_fn: F,
+}
+
+impl<F: Fn(Rect, &mut dyn DrawHandle, &ManagerState) + 'static> Canvas<F> {
+    /// Construct a canvas with the given draw function
+    ///
+    /// For example:
+    ///```
+    /// use kas::widget::Canvas;
+    /// use kas::prelude::*;
+    ///
+    /// let canvas = Canvas::new(|rect, draw_handle, _| {
+    ///     draw_handle.selection_box(rect);
+    /// });
+    ///```
+    #[inline]
+    pub fn new(draw_fn: F) -> Self {
+        Canvas {
+            core: Default::default(),
+            min_size: Vec2(32.0, 32.0),
+            frame_interval: None,
+            draw_fn,
+        }
+    }
+
+    /// Set the minimum size (inline)
+    ///
+    /// Units are logical pixels (scaled by the scale factor). The canvas
+    /// stretches to fill available space beyond this. Default: 32×32.
+    #[inline]
+    pub fn with_min_size(mut self, width: f32, height: f32) -> Self {
+        self.min_size = Vec2(width, height);
+        self
+    }
+
+    /// Redraw continuously at the given interval (inline)
+    ///
+    /// Pass [`Duration::default()`] to redraw every frame.
+    #[inline]
+    pub fn with_frame_interval(mut self, interval: Duration) -> Self {
+        self.frame_interval = Some(interval);
+        self
+    }
+
+    /// Request a redraw of this canvas
+    #[inline]
+    pub fn request_redraw(&self, mgr: &mut Manager) {
+        mgr.redraw(self.id());
+    }
+
+    fn schedule_frame(&self, mgr: &mut Manager) {
+        if let Some(interval) = self.frame_interval {
+            // Zero durations are not scheduled; use the shortest positive one
+            let interval = interval.max(Duration::from_nanos(1));
+            mgr.update_on_timer(interval, self.id());
+        }
+    }
+}
+
+impl<F: Fn(Rect, &mut dyn DrawHandle, &ManagerState) + 'static> WidgetConfig for Canvas<F> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        self.schedule_frame(mgr);
+    }
+}
+
+impl<F: Fn(Rect, &mut dyn DrawHandle, &ManagerState) + 'static> Layout for Canvas<F> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let min = match axis.is_horizontal() {
+            true => self.min_size.0,
+            false => self.min_size.1,
+        };
+        let min = i32::conv_ceil(min * size_handle.scale_factor());
+        SizeRules::new(min, min, (0, 0), StretchPolicy::HighUtility)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, _: bool) {
+        let rect = self.core.rect;
+        let class = ClipRegion::Scroll;
+        draw_handle.clip_region(rect, Offset::ZERO, class, &mut |draw_handle| {
+            (self.draw_fn)(rect, draw_handle, mgr);
+        });
+    }
+}
+
+impl<F: Fn(Rect, &mut dyn DrawHandle, &ManagerState) + 'static> event::Handler for Canvas<F> {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::TimerUpdate => {
+                mgr.redraw(self.id());
+                self.schedule_frame(mgr);
+                Response::None
+            }
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+impl<F: Fn(Rect, &mut dyn DrawHandle, &ManagerState) + 'static> Debug for Canvas<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Canvas {{ core: {:?}, min_size: {:?}, frame_interval: {:?}, draw_fn: [omitted] }}",
+            self.core, self.min_size, self.frame_interval,
+        )
+    }
+}
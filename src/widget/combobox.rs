@@ -8,7 +8,7 @@
 use std::fmt::Debug;
 use std::iter::FromIterator;
 
-use super::{Column, MenuEntry, MenuFrame};
+use super::{Column, MenuEntry, MenuFrame, TypeAhead};
 use kas::draw::TextClass;
 use kas::event::{self, Command, GrabMode};
 use kas::prelude::*;
@@ -29,6 +29,7 @@ pub struct ComboBox<M: Clone + Debug + 'static> {
     active: usize,
     opening: bool,
     popup_id: Option<WindowId>,
+    type_ahead: TypeAhead,
 }
 
 impl<M: Clone + Debug + 'static> kas::Layout for ComboBox<M> {
@@ -101,6 +102,7 @@ impl<M: Clone + Debug + 'static> ComboBox<M> {
             active: 0,
             opening: false,
             popup_id: None,
+            type_ahead: Default::default(),
         }
     }
 
@@ -196,6 +198,13 @@ impl<M: Clone + Debug + 'static> ComboBox<M> {
 }
 
 impl<M: Clone + Debug + 'static> ComboBox<M> {
+    // Type-ahead find: get the index of the first matching choice
+    fn find_prefix(&mut self, mgr: &Manager, c: char) -> Option<usize> {
+        let prefix = self.type_ahead.push(mgr, c)?;
+        let column = &self.popup.inner.inner;
+        (0..column.len()).find(|i| TypeAhead::matches(prefix, column[*i].get_str()))
+    }
+
     fn map_response(&mut self, mgr: &mut Manager, r: Response<(usize, ())>) -> Response<M> {
         match r {
             Response::None => Response::None,
@@ -216,6 +225,15 @@ impl<M: Clone + Debug + 'static> ComboBox<M> {
                         _ => Response::Unhandled(event),
                     }
                 }
+                Event::ReceivedCharacter(c) => match self.find_prefix(mgr, c) {
+                    Some(index) => {
+                        if let Some(w) = self.popup.inner.inner.get_child(index) {
+                            mgr.set_nav_focus(w.id());
+                        }
+                        Response::None
+                    }
+                    None => Response::Unhandled(event),
+                },
                 event => Response::Unhandled(event),
             },
             Response::Focus(x) => Response::Focus(x),
@@ -348,6 +366,14 @@ impl<M: Clone + Debug + 'static> event::Handler for ComboBox<M> {
                 debug_assert_eq!(Some(id), self.popup_id);
                 self.popup_id = None;
             }
+            Event::ReceivedCharacter(c) => match self.find_prefix(mgr, c) {
+                Some(index) if index != self.active => {
+                    *mgr |= self.set_active(index);
+                    return Response::Msg(self.msg());
+                }
+                Some(_) => (),
+                None => return Response::Unhandled(event),
+            },
             event => return Response::Unhandled(event),
         }
         Response::None
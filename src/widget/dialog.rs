@@ -7,16 +7,74 @@
 //!
 //! KAS dialog boxes are pre-configured windows, usually allowing some
 //! customisation.
+//!
+//! Dialogs are opened like any other window, e.g. via
+//! [`Manager::add_window`]. Since each window is independent, a dialog reports
+//! the user's choice by triggering an [`UpdateHandle`]; see [`confirm`].
+//!
+//! With the `rfd` feature, native file dialogs are available via
+//! [`open_file`] and [`save_file`].
 
-use kas::event::VirtualKeyCode;
+use kas::event::{UpdateHandle, VirtualKeyCode};
 use kas::prelude::*;
 use kas::text::format::FormattableText;
 use kas::widget::{Label, TextButton};
 use kas::WindowId;
+#[cfg(feature = "rfd")]
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, VoidMsg)]
 enum DialogButton {
     Close,
+    Yes,
+    No,
+}
+
+/// Construct a [`MessageBox`]
+///
+/// This is a shortcut for [`MessageBox::new`].
+pub fn message<A: ToString, T: FormattableText + 'static>(title: A, text: T) -> MessageBox<T> {
+    MessageBox::new(title, text)
+}
+
+/// Construct a [`ConfirmBox`]
+///
+/// This is a shortcut for [`ConfirmBox::new`].
+pub fn confirm<A: ToString, T: FormattableText + 'static>(
+    title: A,
+    text: T,
+    handle: UpdateHandle,
+) -> ConfirmBox<T> {
+    ConfirmBox::new(title, text, handle)
+}
+
+/// Show a native "open file" dialog
+///
+/// Each filter is a name and a list of extensions, e.g.
+/// `("Images", &["png", "jpg"])`.
+///
+/// This call blocks until the user makes a choice, returning `None` if the
+/// dialog was cancelled.
+#[cfg(feature = "rfd")]
+pub fn open_file(filters: &[(&str, &[&str])]) -> Option<PathBuf> {
+    let mut dialog = rfd::FileDialog::new();
+    for (name, extensions) in filters {
+        dialog = dialog.add_filter(name, extensions);
+    }
+    dialog.pick_file()
+}
+
+/// Show a native "save file" dialog
+///
+/// Filters are as for [`open_file`]. This call blocks until the user makes a
+/// choice, returning `None` if the dialog was cancelled.
+#[cfg(feature = "rfd")]
+pub fn save_file(filters: &[(&str, &[&str])]) -> Option<PathBuf> {
+    let mut dialog = rfd::FileDialog::new();
+    for (name, extensions) in filters {
+        dialog = dialog.add_filter(name, extensions);
+    }
+    dialog.save_file()
 }
 
 /// A simple message box.
@@ -42,17 +100,20 @@ impl<T: FormattableText + 'static> MessageBox<T> {
             layout_data: Default::default(),
             title: title.to_string(),
             label: Label::new(message),
-            button: TextButton::new_msg("Ok", DialogButton::Close).with_keys(&[
-                VirtualKeyCode::Return,
-                VirtualKeyCode::Space,
-                VirtualKeyCode::NumpadEnter,
-            ]),
+            button: TextButton::new_msg("Ok", DialogButton::Close)
+                .with_keys(&[
+                    VirtualKeyCode::Return,
+                    VirtualKeyCode::Space,
+                    VirtualKeyCode::NumpadEnter,
+                ])
+                .with_default(true),
         }
     }
 
     fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
         match msg {
             DialogButton::Close => mgr.send_action(TkAction::CLOSE),
+            DialogButton::Yes | DialogButton::No => (),
         };
         Response::None
     }
@@ -81,3 +142,85 @@ impl<T: FormattableText + 'static> kas::Window for MessageBox<T> {
     fn remove_popup(&mut self, _: &mut Manager, _: WindowId) {}
     fn resize_popups(&mut self, _: &mut Manager) {}
 }
+
+/// A yes/no confirmation box
+///
+/// When the user chooses "Yes" or "No", all widgets subscribed to the given
+/// [`UpdateHandle`] (see [`Manager::update_on_handle`]) receive
+/// [`Event::HandleUpdate`] with payload [`ConfirmBox::YES`] or
+/// [`ConfirmBox::NO`], and the dialog closes. No update is sent if the window
+/// is closed by other means.
+#[derive(Clone, Debug, Widget)]
+#[layout(grid)]
+#[widget(config=noauto)]
+pub struct ConfirmBox<T: FormattableText + 'static> {
+    #[widget_core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: String,
+    handle: UpdateHandle,
+    #[widget(cspan = 2)]
+    label: Label<T>,
+    #[widget(row = 1, col = 0, handler = handle_button)]
+    yes: TextButton<DialogButton>,
+    #[widget(row = 1, col = 1, handler = handle_button)]
+    no: TextButton<DialogButton>,
+}
+
+impl<T: FormattableText + 'static> ConfirmBox<T> {
+    /// Update payload sent on "Yes"
+    pub const YES: u64 = 1;
+    /// Update payload sent on "No"
+    pub const NO: u64 = 0;
+
+    /// Construct
+    ///
+    /// The user's choice is reported via `handle`.
+    pub fn new<A: ToString>(title: A, message: T, handle: UpdateHandle) -> Self {
+        ConfirmBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: title.to_string(),
+            handle,
+            label: Label::new(message),
+            yes: TextButton::new_msg("&Yes", DialogButton::Yes)
+                .with_keys(&[VirtualKeyCode::Return, VirtualKeyCode::NumpadEnter])
+                .with_default(true),
+            no: TextButton::new_msg("&No", DialogButton::No).with_keys(&[VirtualKeyCode::Escape]),
+        }
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
+        let payload = match msg {
+            DialogButton::Yes => Self::YES,
+            _ => Self::NO,
+        };
+        mgr.trigger_update(self.handle, payload);
+        mgr.send_action(TkAction::CLOSE);
+        Response::None
+    }
+}
+
+impl<T: FormattableText + 'static> kas::WidgetConfig for ConfirmBox<T> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.enable_alt_bypass(true);
+    }
+}
+
+impl<T: FormattableText + 'static> kas::Window for ConfirmBox<T> {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn restrict_dimensions(&self) -> (bool, bool) {
+        (true, true)
+    }
+
+    fn add_popup(&mut self, _: &mut Manager, _: WindowId, _: kas::Popup) {
+        panic!("ConfirmBox does not (currently) support pop-ups");
+    }
+
+    fn remove_popup(&mut self, _: &mut Manager, _: WindowId) {}
+    fn resize_popups(&mut self, _: &mut Manager) {}
+}
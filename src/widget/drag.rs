@@ -7,9 +7,23 @@
 
 use std::fmt::Debug;
 
-use kas::event::{self, PressSource};
+use kas::event::{self, Command, PressSource};
 use kas::prelude::*;
 
+/// Message type of [`DragHandle`]
+///
+/// Each variant carries the handle's offset relative to the start of its
+/// track. A drag is reported as `Start`, zero or more `Move`, then `End`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, VoidMsg)]
+pub enum DragMsg {
+    /// The handle was grabbed
+    Start(Offset),
+    /// The handle was moved
+    Move(Offset),
+    /// The handle was released
+    End(Offset),
+}
+
 /// Draggable Handle
 ///
 /// A `DragHandle` is a draggable object with a given size which is restricted
@@ -25,9 +39,13 @@ use kas::prelude::*;
 /// 3.  [`Layout::draw`] does nothing. The parent should handle all drawing.
 /// 4.  Optionally, this widget can handle clicks on the track area via
 ///     [`DragHandle::handle_press_on_track`].
+///
+/// Movement may optionally be stepped; see [`DragHandle::set_snap`].
+/// Optionally (see [`DragHandle::with_key_nav`]) the handle may receive
+/// keyboard focus, supporting the arrow keys and `Home` / `End`.
 #[derive(Clone, Debug, Default, Widget)]
 #[handler(handle=noauto)]
-#[widget(config(hover_highlight = true, cursor_icon = event::CursorIcon::Grab))]
+#[widget(config=noauto)]
 pub struct DragHandle {
     #[widget_core]
     core: CoreData,
@@ -35,6 +53,9 @@ pub struct DragHandle {
     track: Rect,
     press_source: Option<event::PressSource>,
     press_coord: Coord,
+    snap: Size,
+    key_nav: bool,
+    key_step: Size,
 }
 
 impl DragHandle {
@@ -45,9 +66,89 @@ impl DragHandle {
             track: Default::default(),
             press_source: None,
             press_coord: Coord::ZERO,
+            snap: Size::ZERO,
+            key_nav: false,
+            key_step: Size::ZERO,
         }
     }
 
+    /// Enable keyboard navigation (inline)
+    ///
+    /// When enabled, the handle is included in the keyboard navigation order
+    /// and responds to the arrow keys (moving by [`DragHandle::key_step`],
+    /// snapped as with dragging) and `Home`/`End` (moving to the start/end of
+    /// the track). Default: disabled.
+    #[inline]
+    pub fn with_key_nav(mut self, key_nav: bool) -> Self {
+        self.key_nav = key_nav;
+        self
+    }
+
+    /// Set the step used by arrow keys
+    ///
+    /// A zero component selects the default on that axis: one twentieth of
+    /// the range of motion (at least one pixel).
+    pub fn set_key_step(&mut self, step: Size) {
+        self.key_step = step;
+    }
+
+    /// Set the step used by arrow keys (inline)
+    ///
+    /// See [`DragHandle::set_key_step`].
+    pub fn with_key_step(mut self, step: Size) -> Self {
+        self.key_step = step;
+        self
+    }
+
+    /// Get the step used by arrow keys
+    ///
+    /// This resolves the default (see [`DragHandle::set_key_step`]) against
+    /// the current track and handle size.
+    pub fn key_step(&self) -> Size {
+        let max = self.max_offset();
+        let step = |s: i32, max: i32| if s > 0 { s } else { (max / 20).max(1) };
+        Size(step(self.key_step.0, max.0), step(self.key_step.1, max.1))
+    }
+
+    /// Set the snap increment
+    ///
+    /// When dragged (or moved by [`DragHandle::handle_press_on_track`]), each
+    /// component of the handle's offset is rounded to the nearest multiple of
+    /// the corresponding component of `step` before clamping to the track. A
+    /// zero component disables snapping on that axis; by default `step` is
+    /// [`Size::ZERO`] (no snapping).
+    ///
+    /// This does not affect [`DragHandle::set_offset`].
+    pub fn set_snap(&mut self, step: Size) {
+        self.snap = step;
+    }
+
+    /// Set the snap increment (inline)
+    ///
+    /// See [`DragHandle::set_snap`].
+    pub fn with_snap(mut self, step: Size) -> Self {
+        self.snap = step;
+        self
+    }
+
+    /// Get the snap increment
+    #[inline]
+    pub fn snap(&self) -> Size {
+        self.snap
+    }
+
+    // Round offset to the nearest multiple of the snap step
+    fn snap_offset(&self, offset: Offset) -> Offset {
+        fn round(x: i32, step: i32) -> i32 {
+            if step > 0 {
+                (x + step / 2).div_euclid(step) * step
+            } else {
+                x
+            }
+        }
+        Offset(round(offset.0, self.snap.0), round(offset.1, self.snap.1))
+    }
+
     /// Set a new handle size and offset
     ///
     /// Returns [`TkAction::REDRAW`] if a redraw is required.
@@ -73,7 +174,7 @@ impl DragHandle {
     /// This depends on size of the handle and the track.
     #[inline]
     pub fn max_offset(&self) -> Offset {
-        Offset::from(self.track.size) - Offset::from(self.core.rect.size)
+        Offset::from(self.track.size) - self.core.rect.size
     }
 
     /// Set a new handle offset
@@ -98,24 +199,27 @@ impl DragHandle {
     /// then the parent widget should call this method when receiving
     /// [`Event::PressStart`].
     ///
-    /// This method moves the handle immediately and returns the new offset.
+    /// This method moves the handle immediately and starts a drag, returning
+    /// [`DragMsg::Start`] with the new offset (or [`Response::None`] if the
+    /// press could not be grabbed). Further messages ([`DragMsg::Move`] and
+    /// [`DragMsg::End`]) are emitted by the handle as with any other drag.
     pub fn handle_press_on_track(
         &mut self,
         mgr: &mut Manager,
         source: PressSource,
         coord: Coord,
-    ) -> Offset {
+    ) -> Response<DragMsg> {
         if !self.grab_press(mgr, source, coord) {
-            return self.offset();
+            return Response::None;
         }
 
         self.press_coord = self.track.pos + self.core.rect.size / 2;
 
         // Since the press is not on the handle, we move the bar immediately.
-        let (offset, action) = self.set_offset(coord - self.press_coord);
-        debug_assert!(action == TkAction::REDRAW);
+        let offset = self.snap_offset(coord - self.press_coord);
+        let (offset, action) = self.set_offset(offset);
         mgr.send_action(action);
-        offset
+        Response::Msg(DragMsg::Start(offset))
     }
 
     fn grab_press(&mut self, mgr: &mut Manager, source: PressSource, coord: Coord) -> bool {
@@ -131,6 +235,18 @@ impl DragHandle {
     }
 }
 
+impl WidgetConfig for DragHandle {
+    fn key_nav(&self) -> bool {
+        self.key_nav
+    }
+    fn hover_highlight(&self) -> bool {
+        true
+    }
+    fn cursor_icon(&self) -> event::CursorIcon {
+        event::CursorIcon::Grab
+    }
+}
+
 /// This implementation is unusual in that:
 ///
 /// 1.  `size_rules` always returns [`SizeRules::EMPTY`]
@@ -151,7 +267,7 @@ impl Layout for DragHandle {
 }
 
 impl event::Handler for DragHandle {
-    type Msg = Offset;
+    type Msg = DragMsg;
 
     fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
         match event {
@@ -162,23 +278,121 @@ impl event::Handler for DragHandle {
 
                 // Event delivery implies coord is over the handle.
                 self.press_coord = coord - self.offset();
-                Response::None
+                Response::Msg(DragMsg::Start(self.offset()))
             }
             Event::PressMove { source, coord, .. } if Some(source) == self.press_source => {
-                let offset = coord - self.press_coord;
+                let offset = self.snap_offset(coord - self.press_coord);
                 let (offset, action) = self.set_offset(offset);
                 if action.is_empty() {
                     Response::None
                 } else {
                     mgr.send_action(action);
-                    Response::Msg(offset)
+                    Response::Msg(DragMsg::Move(offset))
                 }
             }
             Event::PressEnd { source, .. } if Some(source) == self.press_source => {
                 self.press_source = None;
-                Response::None
+                Response::Msg(DragMsg::End(self.offset()))
+            }
+            Event::Command(cmd, shift) if self.key_nav => {
+                // Step by at least the snap increment, so that snapping
+                // cannot round a move back to the current position.
+                let step = self.key_step().max(self.snap);
+                let max = self.max_offset();
+                let offset = self.offset();
+                // Keys moving along an axis without freedom are not ours
+                let offset = match cmd {
+                    Command::Left if max.0 > 0 => offset - Offset(step.0, 0),
+                    Command::Right if max.0 > 0 => offset + Offset(step.0, 0),
+                    Command::Up if max.1 > 0 => offset - Offset(0, step.1),
+                    Command::Down if max.1 > 0 => offset + Offset(0, step.1),
+                    Command::Home => Offset::ZERO,
+                    Command::End => max,
+                    _ => return Response::Unhandled(Event::Command(cmd, shift)),
+                };
+                let (offset, action) = self.set_offset(self.snap_offset(offset));
+                if action.is_empty() {
+                    Response::None
+                } else {
+                    mgr.send_action(action);
+                    Response::Msg(DragMsg::Move(offset))
+                }
             }
             event => Response::Unhandled(event),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    // A handle of size 10×10 on a horizontal track of length 100
+    fn handle(snap: Size) -> TestHarness<DragHandle> {
+        let handle = DragHandle::new()
+            .with_key_nav(true)
+            .with_key_step(Size(3, 0))
+            .with_snap(snap);
+        let mut harness = TestHarness::with_size(handle, Size(100, 10));
+        harness.with_manager(|mgr, w| {
+            *mgr |= w.set_size_and_offset(Size(10, 10), Offset::ZERO);
+        });
+        harness
+    }
+
+    fn key(harness: &mut TestHarness<DragHandle>, cmd: Command) -> Response<DragMsg> {
+        let id = harness.widget().id();
+        harness.send(id, Event::Command(cmd, false))
+    }
+
+    fn keys(harness: &mut TestHarness<DragHandle>, cmds: &[Command]) {
+        for cmd in cmds {
+            let _ = key(harness, *cmd);
+        }
+    }
+
+    #[test]
+    fn key_step() {
+        let mut harness = handle(Size::ZERO);
+        let cmds = [Command::Right, Command::Right, Command::Left, Command::End];
+        keys(&mut harness, &cmds);
+        assert_eq!(
+            harness.take_messages(),
+            vec![
+                DragMsg::Move(Offset(3, 0)),
+                DragMsg::Move(Offset(6, 0)),
+                DragMsg::Move(Offset(3, 0)),
+                DragMsg::Move(Offset(90, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn key_step_snaps() {
+        let mut harness = handle(Size(5, 0));
+        let cmds = [Command::Right, Command::Right, Command::Left];
+        keys(&mut harness, &cmds);
+        assert_eq!(
+            harness.take_messages(),
+            vec![
+                DragMsg::Move(Offset(5, 0)),
+                DragMsg::Move(Offset(10, 0)),
+                DragMsg::Move(Offset(5, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn off_axis_keys_unhandled() {
+        let mut harness = handle(Size::ZERO);
+        let response = key(&mut harness, Command::Down);
+        assert!(matches!(
+            response,
+            Response::Unhandled(Event::Command(Command::Down, false))
+        ));
+        let response = key(&mut harness, Command::Up);
+        assert!(matches!(response, Response::Unhandled(_)));
+        assert!(harness.take_messages().is_empty());
+    }
+}
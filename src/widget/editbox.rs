@@ -17,6 +17,37 @@ use kas::macros::*;
 use kas::prelude::*;
 use kas::text::SelectionHelper;
 
+// Character displayed in place of each character of a password
+const PASSWORD_CHAR: char = '•';
+
+#[derive(Clone, Debug, Default)]
+struct Password {
+    value: String,
+    revealed: bool,
+    copy_masked: bool,
+}
+
+impl Password {
+    // Map a byte index in the masked string to one in `value`
+    fn index_from_masked(&self, index: usize) -> usize {
+        let n = index / PASSWORD_CHAR.len_utf8();
+        self.value
+            .char_indices()
+            .nth(n)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    // Map a byte index in `value` to one in the masked string
+    fn index_to_masked(&self, index: usize) -> usize {
+        self.value[0..index].chars().count() * PASSWORD_CHAR.len_utf8()
+    }
+
+    fn masked(&self) -> String {
+        self.value.chars().map(|_| PASSWORD_CHAR).collect()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 enum LastEdit {
     None,
@@ -109,7 +140,7 @@ where
 {
     type Msg = M;
     fn activate(edit: &mut EditField<Self>, mgr: &mut Manager) -> Option<Self::Msg> {
-        (edit.guard.0)(edit.text.text(), mgr)
+        (edit.guard.0)(edit.get_str(), mgr)
     }
 }
 impl<F: FnMut(&str, &mut Manager) -> Option<M>, M> Debug for EditActivate<F, M> {
@@ -127,10 +158,10 @@ where
 {
     type Msg = M;
     fn activate(edit: &mut EditField<Self>, mgr: &mut Manager) -> Option<Self::Msg> {
-        (edit.guard.0)(edit.text.text(), mgr)
+        (edit.guard.0)(edit.get_str(), mgr)
     }
     fn focus_lost(edit: &mut EditField<Self>, mgr: &mut Manager) -> Option<Self::Msg> {
-        (edit.guard.0)(edit.text.text(), mgr)
+        (edit.guard.0)(edit.get_str(), mgr)
     }
 }
 impl<F: FnMut(&str, &mut Manager) -> Option<M>, M> Debug for EditAFL<F, M> {
@@ -148,7 +179,7 @@ where
 {
     type Msg = M;
     fn edit(edit: &mut EditField<Self>, mgr: &mut Manager) -> Option<Self::Msg> {
-        (edit.guard.0)(edit.text.text(), mgr)
+        (edit.guard.0)(edit.get_str(), mgr)
     }
 }
 impl<F: FnMut(&str, &mut Manager) -> Option<M>, M> Debug for EditEdit<F, M> {
@@ -163,7 +194,7 @@ pub struct EditUpdate<F: FnMut(&str)>(pub F);
 impl<F: FnMut(&str) + 'static> EditGuard for EditUpdate<F> {
     type Msg = VoidMsg;
     fn update(edit: &mut EditField<Self>) {
-        (edit.guard.0)(edit.text.text());
+        (edit.guard.0)(edit.get_str());
     }
 }
 impl<F: FnMut(&str)> Debug for EditUpdate<F> {
@@ -289,6 +320,9 @@ impl EditBox<()> {
 
 impl<G: EditGuard> EditBox<G> {
     /// Set whether this `EditBox` is editable (inline)
+    ///
+    /// See [`EditField::editable`]. A read-only box is drawn with a
+    /// different background to a disabled one.
     #[inline]
     pub fn editable(mut self, editable: bool) -> Self {
         self.inner = self.inner.editable(editable);
@@ -314,6 +348,31 @@ impl<G: EditGuard> EditBox<G> {
         self
     }
 
+    /// Set password mode
+    ///
+    /// See [`EditField::set_password`].
+    #[inline]
+    pub fn set_password(&mut self, password: bool) -> TkAction {
+        self.inner.set_password(password)
+    }
+
+    /// Set placeholder text (inline)
+    ///
+    /// See [`EditField::set_placeholder`].
+    #[inline]
+    pub fn with_placeholder(mut self, text: &str) -> Self {
+        self.inner = self.inner.with_placeholder(text);
+        self
+    }
+
+    /// Set placeholder text
+    ///
+    /// This text is drawn dimmed when the box is empty and unfocussed.
+    #[inline]
+    pub fn set_placeholder(&mut self, text: &str) -> TkAction {
+        self.inner.set_placeholder(text)
+    }
+
     /// Get whether the input state is erroneous
     #[inline]
     pub fn has_error(&self) -> bool {
@@ -322,8 +381,8 @@ impl<G: EditGuard> EditBox<G> {
 
     /// Set the error state
     ///
-    /// When true, the input field's background is drawn red.
-    // TODO: possibly change type to Option<String> and display the error
+    /// When true, the input field's background is drawn red. To associate an
+    /// error message, use [`Manager::set_widget_error`] instead.
     #[inline]
     pub fn set_error_state(&mut self, error_state: bool) {
         self.inner.set_error_state(error_state);
@@ -359,7 +418,8 @@ impl<G: EditGuard> Layout for EditBox<G> {
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         // We draw highlights for input state of inner:
         let mut input_state = self.inner.input_state(mgr, disabled);
-        input_state.error = self.inner.has_error();
+        input_state.error |= self.inner.has_error() || mgr.widget_error(self.id()).is_some();
+        input_state.read_only = !self.inner.is_editable();
         draw_handle.edit_box(self.core.rect, input_state);
         let disabled = disabled || self.is_disabled();
         self.inner.draw(draw_handle, mgr, disabled);
@@ -407,7 +467,7 @@ impl<G: EditGuard> std::ops::DerefMut for EditBox<G> {
 /// line-wrapping and a larger vertical height). This mode is only recommended
 /// for short texts for performance reasons.
 #[derive(Clone, Default, Debug, Widget)]
-#[widget(config(key_nav = true, cursor_icon = event::CursorIcon::Text))]
+#[widget(config=noauto)]
 #[handler(handle=noauto, generics = <> where G: EditGuard)]
 pub struct EditField<G: EditGuard = ()> {
     #[widget_core]
@@ -424,10 +484,34 @@ pub struct EditField<G: EditGuard = ()> {
     last_edit: LastEdit,
     error_state: bool,
     touch_phase: TouchPhase,
+    placeholder: Option<Text<String>>,
+    password: Option<Password>,
     /// The associated [`EditGuard`] implementation
     pub guard: G,
 }
 
+impl<G: EditGuard> WidgetConfig for EditField<G> {
+    fn key_nav(&self) -> bool {
+        true
+    }
+    fn uses_return_key(&self) -> bool {
+        self.multi_line
+    }
+    fn cursor_icon(&self) -> event::CursorIcon {
+        event::CursorIcon::Text
+    }
+    fn cursor_icon_at(&self, coord: Coord) -> Option<event::CursorIcon> {
+        // Show an I-beam only over the text area and only while enabled
+        let bounds = Vec2::from(self.text.env().bounds);
+        let text_rect = Rect::new(self.rect().pos, Size::from(bounds));
+        if text_rect.contains(coord) && !self.is_disabled() {
+            Some(event::CursorIcon::Text)
+        } else {
+            Some(event::CursorIcon::Default)
+        }
+    }
+}
+
 impl<G: EditGuard> Layout for EditField<G> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let class = if self.multi_line {
@@ -465,6 +549,18 @@ impl<G: EditGuard> Layout for EditField<G> {
                 env.set_wrap(multi_line);
             })
             .into();
+        if let Some(placeholder) = self.placeholder.as_mut() {
+            // Font size and DPI are only set on self.text by size_rules.
+            // This also prepares the placeholder.
+            let text_env = self.text.env();
+            placeholder.update_env(|env| {
+                env.set_dpp(text_env.dpp);
+                env.set_pt_size(text_env.pt_size);
+                env.set_align(text_env.align);
+                env.set_bounds(text_env.bounds);
+                env.set_wrap(multi_line);
+            });
+        }
         self.set_view_offset_from_edit_pos();
     }
 
@@ -475,6 +571,19 @@ impl<G: EditGuard> Layout for EditField<G> {
             TextClass::Edit
         };
         let bounds = self.text.env().bounds.into();
+        let char_focus = self.input_state(mgr, disabled).char_focus;
+        if let Some(placeholder) = self.placeholder.as_ref() {
+            if self.text.str_len() == 0 && !char_focus {
+                draw_handle.text_offset(
+                    self.rect().pos,
+                    bounds,
+                    Offset::ZERO,
+                    placeholder.as_ref(),
+                    TextClass::Placeholder,
+                );
+                return;
+            }
+        }
         if self.selection.is_empty() {
             draw_handle.text_offset(
                 self.rect().pos,
@@ -496,7 +605,12 @@ impl<G: EditGuard> Layout for EditField<G> {
                 class,
             );
         }
-        if self.input_state(mgr, disabled).char_focus {
+        if char_focus {
+            // Dim the marker while the window is inactive
+            let class = match mgr.window_has_focus() {
+                true => class,
+                false => TextClass::Placeholder,
+            };
             draw_handle.edit_marker(
                 self.rect().pos,
                 bounds,
@@ -529,6 +643,8 @@ impl EditField<()> {
             last_edit: LastEdit::None,
             error_state: false,
             touch_phase: TouchPhase::None,
+            placeholder: None,
+            password: None,
             guard: (),
         }
     }
@@ -556,6 +672,8 @@ impl EditField<()> {
             last_edit: self.last_edit,
             error_state: self.error_state,
             touch_phase: self.touch_phase,
+            placeholder: self.placeholder,
+            password: self.password,
             guard,
         };
         let _ = G::update(&mut edit);
@@ -620,6 +738,10 @@ impl EditField<()> {
 
 impl<G: EditGuard> EditField<G> {
     /// Set whether this `EditField` is editable (inline)
+    ///
+    /// A non-editable field is read-only: it may still receive focus, and its
+    /// text may be navigated, selected and copied, but not modified. To
+    /// prevent all interaction, disable the widget instead.
     #[inline]
     pub fn editable(mut self, editable: bool) -> Self {
         self.editable = editable;
@@ -643,6 +765,35 @@ impl<G: EditGuard> EditField<G> {
         self
     }
 
+    /// Set placeholder text (inline)
+    ///
+    /// See [`EditField::set_placeholder`].
+    #[inline]
+    pub fn with_placeholder(mut self, text: &str) -> Self {
+        let _ = self.set_placeholder(text);
+        self
+    }
+
+    /// Set placeholder text
+    ///
+    /// This text is drawn dimmed when the field is empty and does not have
+    /// character focus. It is not part of the field's value. Pass an empty
+    /// string to remove the placeholder.
+    ///
+    /// The placeholder is prepared when the field is sized, thus this returns
+    /// [`TkAction::SET_SIZE`] (unless removing the placeholder).
+    pub fn set_placeholder(&mut self, text: &str) -> TkAction {
+        if text.is_empty() {
+            self.placeholder = None;
+            return TkAction::REDRAW;
+        }
+        let placeholder = self
+            .placeholder
+            .get_or_insert_with(|| Text::new(Default::default(), String::new()));
+        placeholder.set_string(text.to_string());
+        TkAction::SET_SIZE
+    }
+
     /// Get whether the input state is erroneous
     pub fn has_error(&self) -> bool {
         self.error_state
@@ -650,12 +801,129 @@ impl<G: EditGuard> EditField<G> {
 
     /// Set the error state
     ///
-    /// When true, the input field's background is drawn red.
-    // TODO: possibly change type to Option<String> and display the error
+    /// When true, the input field's background is drawn red. To associate an
+    /// error message, use [`Manager::set_widget_error`] instead.
     pub fn set_error_state(&mut self, error_state: bool) {
         self.error_state = error_state;
     }
 
+    /// Set password mode
+    ///
+    /// In password mode, each character is displayed as a bullet while the
+    /// real value (as returned by [`HasStr::get_str`]) is kept internally.
+    /// Selection and editing operate on the masked text, copying is disabled
+    /// (see [`EditField::set_password_copy_masked`]) and undo is unavailable.
+    pub fn set_password(&mut self, password: bool) -> TkAction {
+        if password == self.password.is_some() {
+            return TkAction::empty();
+        }
+        let edit_pos = self.selection.edit_pos();
+        let sel_pos = self.selection.sel_pos();
+        let (string, edit_pos, sel_pos) = if password {
+            let password = Password {
+                value: self.text.clone_string(),
+                ..Default::default()
+            };
+            let string = password.masked();
+            let edit_pos = password.index_to_masked(edit_pos);
+            let sel_pos = password.index_to_masked(sel_pos);
+            self.password = Some(password);
+            (string, edit_pos, sel_pos)
+        } else {
+            let password = self.password.take().unwrap();
+            let edit_pos = password.index_from_masked(edit_pos);
+            let sel_pos = password.index_from_masked(sel_pos);
+            (password.value, edit_pos, sel_pos)
+        };
+        self.set_display_string(string, edit_pos, sel_pos)
+    }
+
+    /// Get whether password mode is enabled
+    pub fn is_password(&self) -> bool {
+        self.password.is_some()
+    }
+
+    /// Reveal or mask the password
+    ///
+    /// Does nothing when not in password mode. While revealed, text may be
+    /// copied.
+    pub fn set_password_revealed(&mut self, revealed: bool) -> TkAction {
+        let password = match self.password.as_mut() {
+            Some(password) if password.revealed != revealed => password,
+            _ => return TkAction::empty(),
+        };
+        password.revealed = revealed;
+        let edit_pos = self.selection.edit_pos();
+        let sel_pos = self.selection.sel_pos();
+        let (string, edit_pos, sel_pos) = if revealed {
+            (
+                password.value.clone(),
+                password.index_from_masked(edit_pos),
+                password.index_from_masked(sel_pos),
+            )
+        } else {
+            (
+                password.masked(),
+                password.index_to_masked(edit_pos),
+                password.index_to_masked(sel_pos),
+            )
+        };
+        self.set_display_string(string, edit_pos, sel_pos)
+    }
+
+    /// Get whether the password is revealed
+    pub fn is_password_revealed(&self) -> bool {
+        self.password.as_ref().map(|p| p.revealed).unwrap_or(false)
+    }
+
+    /// Allow copying masked text in password mode
+    ///
+    /// By default, copy and cut are disabled while the password is masked.
+    /// If enabled, the masked text (bullets) is copied instead.
+    pub fn set_password_copy_masked(&mut self, copy_masked: bool) {
+        if let Some(password) = self.password.as_mut() {
+            password.copy_masked = copy_masked;
+        }
+    }
+
+    fn can_copy(&self) -> bool {
+        match self.password.as_ref() {
+            Some(password) => password.revealed || password.copy_masked,
+            None => true,
+        }
+    }
+
+    // Replace displayed text, without changing the value in password mode
+    fn set_display_string(&mut self, string: String, edit_pos: usize, sel_pos: usize) -> TkAction {
+        let avail = self.core.rect.size;
+        let action = kas::text::util::set_string_and_prepare(&mut self.text, string, avail);
+        self.selection.set_edit_pos(edit_pos);
+        self.selection.set_sel_pos(sel_pos);
+        self.old_state = None;
+        self.last_edit = LastEdit::None;
+        self.edit_x_coord = None;
+        self.set_view_offset_from_edit_pos();
+        action | TkAction::REDRAW
+    }
+
+    // Replace `range` of the displayed text with `s`, updating the password
+    // value if any. Returns the length of the inserted display text.
+    fn replace_range(&mut self, range: Range<usize>, s: &str) -> usize {
+        if let Some(password) = self.password.as_mut() {
+            if !password.revealed {
+                let start = password.index_from_masked(range.start);
+                let end = password.index_from_masked(range.end);
+                password.value.replace_range(start..end, s);
+                let masked: String = s.chars().map(|_| PASSWORD_CHAR).collect();
+                self.text.replace_range(range, &masked);
+                return masked.len();
+            }
+            password.value.replace_range(range.clone(), s);
+        }
+        self.text.replace_range(range, s);
+        s.len()
+    }
+
     fn received_char(&mut self, mgr: &mut Manager, c: char) -> EditAction {
         if !self.editable {
             return EditAction::Unhandled;
@@ -668,14 +936,14 @@ impl<G: EditGuard> EditField<G> {
             self.old_state = Some((self.text.clone_string(), pos, self.selection.sel_pos()));
             self.last_edit = LastEdit::Insert;
         }
+        let mut buf = [0u8; 4];
+        let s = c.encode_utf8(&mut buf);
         if have_sel {
-            let mut buf = [0u8; 4];
-            let s = c.encode_utf8(&mut buf);
-            let _ = self.text.replace_range(selection.clone(), s);
-            self.selection.set_pos(selection.start + s.len());
+            let len = self.replace_range(selection.clone(), s);
+            self.selection.set_pos(selection.start + len);
         } else {
-            let _ = self.text.insert_char(pos, c);
-            self.selection.set_pos(pos + c.len_utf8());
+            let len = self.replace_range(pos..pos, s);
+            self.selection.set_pos(pos + len);
         }
         self.edit_x_coord = None;
         self.text.prepare();
@@ -685,10 +953,6 @@ impl<G: EditGuard> EditField<G> {
     }
 
     fn control_key(&mut self, mgr: &mut Manager, key: Command, mut shift: bool) -> EditAction {
-        if !self.editable {
-            return EditAction::Unhandled;
-        }
-
         let mut buf = [0u8; 4];
         let pos = self.selection.edit_pos();
         let selection = self.selection.range();
@@ -805,11 +1069,14 @@ impl<G: EditGuard> EditField<G> {
                 Action::Move(pos, None)
             }
             Command::End => {
-                let pos = self
-                    .text
-                    .find_line(pos)
-                    .map(|r| r.1.end)
-                    .unwrap_or(self.text.str_len());
+                let pos = match self.text.find_line(pos) {
+                    Some((_, range)) => {
+                        // Stop before any hard line-break terminating the line
+                        let line = &self.text.text()[range.clone()];
+                        range.start + line.trim_end_matches(|c| c == '\n' || c == '\r').len()
+                    }
+                    None => self.text.str_len(),
+                };
                 Action::Move(pos, None)
             }
             Command::DocHome => Action::Move(0, None),
@@ -878,11 +1145,11 @@ impl<G: EditGuard> EditField<G> {
                 shift = true; // hack
                 Action::Move(self.text.str_len(), None)
             }
-            Command::Cut if have_sel => {
+            Command::Cut if have_sel && self.editable && self.can_copy() => {
                 mgr.set_clipboard((self.text.text()[selection.clone()]).into());
                 Action::Delete(selection.clone())
             }
-            Command::Copy if have_sel => {
+            Command::Copy if have_sel && self.can_copy() => {
                 mgr.set_clipboard((self.text.text()[selection.clone()]).into());
                 Action::None
             }
@@ -907,7 +1174,9 @@ impl<G: EditGuard> EditField<G> {
                     Action::None
                 }
             }
-            Command::Undo | Command::Redo => {
+            // Undo is not supported in password mode (only the masked text
+            // is saved)
+            Command::Undo | Command::Redo if self.editable && self.password.is_none() => {
                 // TODO: maintain full edit history (externally?)
                 if let Some((state, pos2, sel_pos)) = self.old_state.as_mut() {
                     self.text.swap_string(state);
@@ -924,6 +1193,14 @@ impl<G: EditGuard> EditField<G> {
             _ => Action::Unhandled,
         };
 
+        let action = match action {
+            // Read-only: allow navigation, selection and copy, but not edits
+            Action::Insert(..) | Action::Delete(_) | Action::Edit if !self.editable => {
+                Action::Unhandled
+            }
+            action => action,
+        };
+
         let result = match action {
             Action::None => EditAction::None,
             Action::Unhandled => EditAction::Unhandled,
@@ -931,13 +1208,14 @@ impl<G: EditGuard> EditField<G> {
             Action::Edit => EditAction::Edit,
             Action::Insert(s, edit) => {
                 let mut pos = pos;
+                let len;
                 if have_sel {
                     self.old_state =
                         Some((self.text.clone_string(), pos, self.selection.sel_pos()));
                     self.last_edit = edit;
 
-                    self.text.replace_range(selection.clone(), s);
                     pos = selection.start;
+                    len = self.replace_range(selection.clone(), s);
                 } else {
                     if self.last_edit != edit {
                         self.old_state =
@@ -945,9 +1223,9 @@ impl<G: EditGuard> EditField<G> {
                         self.last_edit = edit;
                     }
 
-                    self.text.replace_range(pos..pos, s);
+                    len = self.replace_range(pos..pos, s);
                 }
-                self.selection.set_pos(pos + s.len());
+                self.selection.set_pos(pos + len);
                 self.edit_x_coord = None;
                 EditAction::Edit
             }
@@ -958,7 +1236,7 @@ impl<G: EditGuard> EditField<G> {
                     self.last_edit = LastEdit::Delete;
                 }
 
-                self.text.replace_range(sel.clone(), "");
+                self.replace_range(sel.clone(), "");
                 self.selection.set_pos(sel.start);
                 self.edit_x_coord = None;
                 EditAction::Edit
@@ -1035,13 +1313,26 @@ impl<G: EditGuard> EditField<G> {
 
 impl<G: EditGuard> HasStr for EditField<G> {
     fn get_str(&self) -> &str {
-        self.text.text()
+        match self.password.as_ref() {
+            Some(password) => &password.value,
+            None => self.text.text(),
+        }
     }
 }
 
 impl<G: EditGuard> HasString for EditField<G> {
     fn set_string(&mut self, string: String) -> TkAction {
         let avail = self.core.rect.size;
+        let string = match self.password.as_mut() {
+            Some(password) => {
+                password.value = string;
+                match password.revealed {
+                    true => password.value.clone(),
+                    false => password.masked(),
+                }
+            }
+            None => string,
+        };
         let action = kas::text::util::set_string_and_prepare(&mut self.text, string, avail);
         let _ = G::update(self);
         action
@@ -1071,6 +1362,10 @@ impl<G: EditGuard + 'static> event::Handler for EditField<G> {
                 EditAction::Activate => G::activate(self, mgr).into(),
                 EditAction::Edit => G::edit(self, mgr).into(),
             },
+            Event::ReceivedCharacter(_) if mgr.char_focus_id() != Some(self.id()) => {
+                // Type-ahead input intended for a parent
+                Response::Unhandled(event)
+            }
             Event::ReceivedCharacter(c) => match self.received_char(mgr, c) {
                 EditAction::None => Response::None,
                 EditAction::Unhandled => Response::Unhandled(Event::ReceivedCharacter(c)),
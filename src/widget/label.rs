@@ -7,23 +7,46 @@
 
 use kas::draw::TextClass;
 use kas::text::format::{EditableText, FormattableText};
+use kas::text::RichText;
 use kas::{event, prelude::*};
 
 /// A text label
 ///
 /// This type is generic over the text type. Some aliases are available:
 /// [`StrLabel`], [`StringLabel`], [`AccelLabel`].
+///
+/// Text wraps to the available width. Size requirements are cached (per axis,
+/// keyed by font size in pixels (see [`SizeHandle::text_dpem`]) and the size
+/// of the other axis) and only re-measured when the text or one of these
+/// parameters changes.
 #[derive(Clone, Default, Debug, Widget)]
 pub struct Label<T: FormattableText + 'static> {
     #[widget_core]
     core: CoreData,
     label: Text<T>,
+    rules_cache: [Option<RulesCacheEntry>; 2],
+}
+
+#[derive(Clone, Copy, Debug)]
+struct RulesCacheEntry {
+    dpem: f32,
+    other: Option<i32>,
+    rules: SizeRules,
 }
 
 impl<T: FormattableText + 'static> Layout for Label<T> {
-    #[inline]
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
-        size_handle.text_bound(&mut self.label, TextClass::Label, axis)
+        let dpem = size_handle.text_dpem(TextClass::Label);
+        let other = axis.other();
+        let cache = &mut self.rules_cache[usize::from(axis.is_vertical())];
+        if let Some(entry) = cache {
+            if entry.dpem == dpem && entry.other == other {
+                return entry.rules;
+            }
+        }
+        let rules = size_handle.text_bound(&mut self.label, TextClass::Label, axis);
+        *cache = Some(RulesCacheEntry { dpem, other, rules });
+        rules
     }
 
     fn set_rect(&mut self, _: &mut Manager, rect: Rect, align: AlignHints) {
@@ -62,6 +85,19 @@ impl Layout for AccelLabel {
     }
 }
 
+#[cfg(feature = "min_spec")]
+impl Layout for RichLabel {
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &ManagerState, _: bool) {
+        draw_handle.text_col_spans(
+            self.core.rect.pos,
+            Offset::ZERO,
+            &self.label,
+            self.label.text().colour_effects(),
+            TextClass::Label,
+        );
+    }
+}
+
 // Str/String representations have no effects, so use simpler draw call
 #[cfg(feature = "min_spec")]
 impl<'a> Layout for Label<&'a str> {
@@ -103,6 +139,7 @@ impl<T: FormattableText + 'static> Label<T> {
         Label {
             core: Default::default(),
             label: Text::new_multi(label),
+            rules_cache: Default::default(),
         }
     }
 
@@ -111,6 +148,7 @@ impl<T: FormattableText + 'static> Label<T> {
     /// Note: this must not be called before fonts have been initialised
     /// (usually done by the theme when the main loop starts).
     pub fn set_text(&mut self, text: T) -> TkAction {
+        self.rules_cache = Default::default();
         kas::text::util::set_text_and_prepare(&mut self.label, text, self.core.rect.size)
     }
 }
@@ -123,6 +161,7 @@ impl<T: FormattableText + 'static> HasStr for Label<T> {
 
 impl<T: FormattableText + EditableText + 'static> HasString for Label<T> {
     fn set_string(&mut self, string: String) -> TkAction {
+        self.rules_cache = Default::default();
         kas::text::util::set_string_and_prepare(&mut self.label, string, self.core.rect.size)
     }
 }
@@ -153,12 +192,67 @@ impl AccelLabel {
     }
 }
 
+/// A label displaying [`RichText`]
+pub type RichLabel = Label<RichText>;
+
+impl RichLabel {
+    /// Find the span at `coord`
+    ///
+    /// Returns the index of the [`RichText`] span nearest to `coord`, or
+    /// `None` if `coord` is not within the label. This may be used to
+    /// implement clickable spans.
+    pub fn span_at_coord(&self, coord: Coord) -> Option<usize> {
+        if !self.core.rect.contains(coord) {
+            return None;
+        }
+        let index = self
+            .label
+            .text_index_nearest((coord - self.core.rect.pos).into());
+        self.label.text().span_at(index)
+    }
+}
+
 impl SetAccel for AccelLabel {
     fn set_accel_string(&mut self, string: AccelString) -> TkAction {
         let mut action = TkAction::empty();
         if self.label.text().keys() != string.keys() {
             action |= TkAction::RECONFIGURE;
         }
+        self.rules_cache = Default::default();
         action | kas::text::util::set_text_and_prepare(&mut self.label, string, self.core.rect.size)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kas::testing::{load_fonts, TestSizeHandle};
+
+    #[test]
+    fn size_cache() {
+        load_fonts();
+        let mut sh = TestSizeHandle::new();
+        let mut label = Label::new("Lorem ipsum dolor sit amet".to_string());
+
+        let width = label.size_rules(&mut sh, AxisInfo::new(false, None));
+        let width = width.ideal_size();
+        let height = label.size_rules(&mut sh, AxisInfo::new(true, Some(width)));
+        assert_eq!(sh.text_bound_calls(), 2);
+
+        // Repeated measurement uses the cache
+        let rules = label.size_rules(&mut sh, AxisInfo::new(true, Some(width)));
+        assert_eq!(rules, height);
+        assert_eq!(sh.text_bound_calls(), 2);
+
+        // A narrower width is re-measured and wraps
+        let narrow = label.size_rules(&mut sh, AxisInfo::new(true, Some(width / 2)));
+        assert!(narrow.min_size() > height.min_size());
+        assert_eq!(sh.text_bound_calls(), 3);
+
+        // A scale factor (hence font size) change invalidates the cache
+        sh.set_scale_factor(2.0);
+        let rules = label.size_rules(&mut sh, AxisInfo::new(true, Some(width / 2)));
+        assert!(rules.min_size() > narrow.min_size());
+        assert_eq!(sh.text_bound_calls(), 4);
+    }
+}
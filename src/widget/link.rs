@@ -0,0 +1,105 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Hyperlinks
+
+use std::fmt::Debug;
+
+use kas::draw::TextClass;
+use kas::event::{self, CursorIcon};
+use kas::prelude::*;
+use kas::text::{RichText, SpanStyle};
+
+/// A hyperlink
+///
+/// The label is drawn underlined in the theme's link colour (see
+/// [`TextClass::Link`]) and a pointing-hand cursor is shown on hover.
+/// The link is navigable via the keyboard. On activation (through a click,
+/// via the keyboard or by [`Event::Activate`]) a clone of `msg` (e.g. a URL
+/// or other identifier) is returned to the parent widget, which is
+/// responsible for following the link.
+#[derive(Clone, Debug, Widget)]
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+pub struct Link<M: Clone + Debug + 'static> {
+    #[widget_core]
+    core: kas::CoreData,
+    label: Text<RichText>,
+    msg: M,
+}
+
+impl<M: Clone + Debug + 'static> Link<M> {
+    /// Construct a link with the given `label` and payload `msg`
+    #[inline]
+    pub fn new<S: AsRef<str>>(label: S, msg: M) -> Self {
+        let text = RichText::new().with(label.as_ref(), SpanStyle::underline());
+        Link {
+            core: Default::default(),
+            label: Text::new_multi(text),
+            msg,
+        }
+    }
+
+    /// Get the message payload
+    pub fn msg(&self) -> &M {
+        &self.msg
+    }
+}
+
+impl<M: Clone + Debug + 'static> WidgetConfig for Link<M> {
+    fn key_nav(&self) -> bool {
+        true
+    }
+    fn hover_highlight(&self) -> bool {
+        true
+    }
+    fn cursor_icon(&self) -> CursorIcon {
+        CursorIcon::Hand
+    }
+}
+
+impl<M: Clone + Debug + 'static> Layout for Link<M> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        size_handle.text_bound(&mut self.label, TextClass::Link, axis)
+    }
+
+    fn set_rect(&mut self, _: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.label.update_env(|env| {
+            env.set_bounds(rect.size.into());
+            env.set_align(align.unwrap_or(Align::Default, Align::Centre));
+        });
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let pos = self.core.rect.pos;
+        draw_handle.text_effects(pos, Offset::ZERO, &self.label, TextClass::Link);
+        if self.input_state(mgr, disabled).nav_focus {
+            draw_handle.selection_box(self.core.rect);
+        }
+    }
+}
+
+impl<M: Clone + Debug + 'static> HasStr for Link<M> {
+    fn get_str(&self) -> &str {
+        self.label.as_str()
+    }
+}
+
+impl<M: Clone + Debug + 'static> event::Handler for Link<M> {
+    type Msg = M;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle(&mut self, _: &mut Manager, event: Event) -> Response<M> {
+        match event {
+            Event::Activate => Response::Msg(self.msg.clone()),
+            event => Response::Unhandled(event),
+        }
+    }
+}
@@ -12,14 +12,19 @@
 //! ## Dialogs
 //!
 //! -   [`MessageBox`]: a simple window with a message and an "Ok" button
+//! -   [`ConfirmBox`]: a message with "Yes" and "No" buttons
+//!
+//! See also the [`dialog`] module.
 //!
 //! ## Container widgets
 //!
 //! -   [`Frame`]: a simple frame around a single child
 //! -   [`ScrollRegion`]: may be larger on the inside than the outside
-//! -   [`Stack`]: a stack of widgets in the same rect (TODO: `TabbedStack`)
+//! -   [`Stack`]: a stack of widgets in the same rect
+//! -   [`TabView`]: a set of pages selectable via a strip of tabs
 //! -   [`List`]: a dynamic row / column of children
 //! -   [`Splitter`]: similar to [`List`] but with resizing handles
+//! -   [`SplitPane`]: two widgets separated by a draggable divider
 //! -   [`Window`] is usually the root widget and has special handling for
 //!     pop-ups and callbacks
 //!
@@ -33,8 +38,10 @@
 //! ## Controls
 //!
 //! -   [`TextButton`]: a simple button
+//! -   [`Link`]: a clickable hyperlink
 //! -   [`CheckBox`]: a checkable box
 //! -   [`RadioBox`]: a checkable box bound to a group
+//! -   [`RadioGroup`]: tracks the selected value of a group of [`RadioBox`]es
 //! -   [`EditBox`]: a text-editing box
 //! -   [`ScrollBar`]: a scrollbar
 //! -   [`Slider`]: a slider
@@ -44,6 +51,9 @@
 //! -   [`Filler`]: an empty widget, sometimes used to fill space
 //! -   [`Separator`]: a visible bar to separate things
 //! -   [`Label`]: a simple text label
+//! -   [`RichLabel`]: a label displaying styled spans of text
+//! -   [`Canvas`]: custom drawing via a closure
+//! -   [`Spinner`]: an animated busy indicator
 //!
 //! ## Components
 //!
@@ -53,14 +63,15 @@
 //! -   [`DragHandle`]: a handle (e.g. for a slider, splitter or scrollbar)
 
 mod button;
+mod canvas;
 mod checkbox;
 mod combobox;
-mod dialog;
 mod drag;
 mod editbox;
 mod filler;
 mod frame;
 mod label;
+mod link;
 mod list;
 mod menu;
 mod progress;
@@ -70,30 +81,41 @@ mod scroll;
 mod scrollbar;
 mod separator;
 mod slider;
+mod spinner;
+mod split_pane;
 mod splitter;
 mod stack;
+mod tab_view;
+mod type_ahead;
 mod window;
 
+pub mod dialog;
 pub mod view;
 
 pub use button::TextButton;
+pub use canvas::Canvas;
 pub use checkbox::{CheckBox, CheckBoxBare};
 pub use combobox::ComboBox;
-pub use dialog::MessageBox;
-pub use drag::DragHandle;
+pub use dialog::{ConfirmBox, MessageBox};
+pub use drag::{DragHandle, DragMsg};
 pub use editbox::{EditBox, EditField, EditGuard};
 pub use filler::Filler;
 pub use frame::Frame;
-pub use label::{AccelLabel, Label, StrLabel, StringLabel};
+pub use label::{AccelLabel, Label, RichLabel, StrLabel, StringLabel};
+pub use link::Link;
 pub use list::*;
 pub use menu::*;
 pub use progress::ProgressBar;
-pub use radiobox::{RadioBox, RadioBoxBare};
+pub use radiobox::{RadioBox, RadioBoxBare, RadioGroup};
 pub use reserve::{Reserve, ReserveP};
-pub use scroll::{ScrollComponent, ScrollRegion};
+pub use scroll::{OverscrollMode, ScrollComponent, ScrollRegion};
 pub use scrollbar::{ScrollBar, ScrollBarRegion, ScrollBars, ScrollWidget};
 pub use separator::Separator;
 pub use slider::{Slider, SliderType};
+pub use spinner::Spinner;
+pub use split_pane::SplitPane;
 pub use splitter::*;
 pub use stack::{BoxStack, RefStack, Stack};
+pub use tab_view::{TabMsg, TabView};
+pub use type_ahead::TypeAhead;
 pub use window::Window;
@@ -6,6 +6,7 @@
 //! Toggle widgets
 
 use log::trace;
+use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::fmt::{self, Debug};
 use std::rc::Rc;
@@ -14,6 +15,66 @@ use super::AccelLabel;
 use kas::event::{self, UpdateHandle};
 use kas::prelude::*;
 
+/// A group of radioboxes, tracking the selected value
+///
+/// This is a shared handle (it may be cloned cheaply). All radioboxes
+/// constructed over the same group (e.g. via [`RadioBox::new_in`]) are
+/// mutually exclusive: selecting one deselects the others.
+///
+/// The group stores a clone of the value associated with the selected
+/// radiobox; this is also returned as the selecting radiobox's message.
+#[derive(Clone, Debug)]
+pub struct RadioGroup<T> {
+    handle: UpdateHandle,
+    value: Rc<RefCell<Option<T>>>,
+}
+
+impl<T: Clone + PartialEq + 'static> RadioGroup<T> {
+    /// Construct a new group, optionally with an initial selection
+    ///
+    /// Radioboxes added to the group with a value equal to `value` are
+    /// initially selected.
+    pub fn new(value: Option<T>) -> Self {
+        RadioGroup {
+            handle: UpdateHandle::new(),
+            value: Rc::new(RefCell::new(value)),
+        }
+    }
+
+    /// Get the group's [`UpdateHandle`]
+    ///
+    /// This may be used to construct additional radioboxes in the group via
+    /// [`RadioBox::new`] (such radioboxes do not update the group's value).
+    #[inline]
+    pub fn handle(&self) -> UpdateHandle {
+        self.handle
+    }
+
+    /// Get the selected value, if any
+    pub fn get(&self) -> Option<T> {
+        self.value.borrow().clone()
+    }
+
+    /// True if `value` is the selected value
+    pub fn is_selected(&self, value: &T) -> bool {
+        self.value.borrow().as_ref() == Some(value)
+    }
+
+    fn selector(&self, value: T) -> impl Fn(&mut Manager) -> Option<T> {
+        let group = self.value.clone();
+        move |_| {
+            *group.borrow_mut() = Some(value.clone());
+            Some(value.clone())
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Default for RadioGroup<T> {
+    fn default() -> Self {
+        RadioGroup::new(None)
+    }
+}
+
 /// A bare radiobox (no label)
 #[derive(Clone, Widget)]
 #[handler(handle=noauto)]
@@ -159,6 +220,20 @@ impl<M: 'static> RadioBoxBare<M> {
         RadioBoxBare::new(handle).on_select(f)
     }
 
+    /// Construct a radiobox within a [`RadioGroup`]
+    ///
+    /// On selection, the group's value is set to `value` and a clone of
+    /// `value` is returned as a message.
+    ///
+    /// The radiobox is initially selected if the group's value equals `value`.
+    pub fn new_in(group: &RadioGroup<M>, value: M) -> Self
+    where
+        M: Clone + PartialEq,
+    {
+        let state = group.is_selected(&value);
+        RadioBoxBare::new_on(group.handle, group.selector(value)).with_state(state)
+    }
+
     /// Set the initial state of the radiobox.
     #[inline]
     pub fn with_state(mut self, state: bool) -> Self {
@@ -283,6 +358,28 @@ impl<M: 'static> RadioBox<M> {
     {
         Self::new_on(label, handle, move |_| Some(msg.clone()))
     }
+
+    /// Construct a radiobox with given `label` within a [`RadioGroup`]
+    ///
+    /// RadioBox labels are optional; if no label is desired, use an empty
+    /// string.
+    ///
+    /// On selection, the group's value is set to `value` and a clone of
+    /// `value` is returned as a message.
+    ///
+    /// The radiobox is initially selected if the group's value equals `value`.
+    #[inline]
+    pub fn new_in<S: Into<AccelString>>(label: S, group: &RadioGroup<M>, value: M) -> Self
+    where
+        M: Clone + PartialEq,
+    {
+        RadioBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            radiobox: RadioBoxBare::new_in(group, value),
+            label: AccelLabel::new(label.into()),
+        }
+    }
 }
 
 impl<M: 'static> RadioBox<M> {
@@ -11,6 +11,27 @@ use kas::event::ScrollDelta::{LineDelta, PixelDelta};
 use kas::event::{self, Command, PressSource};
 use kas::prelude::*;
 use std::fmt::Debug;
+use std::time::Duration;
+
+/// Interval between steps of the overscroll relaxation animation
+const OVERSCROLL_STEP: Duration = Duration::from_millis(16);
+
+/// Behaviour when content is dragged beyond the scroll bounds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverscrollMode {
+    /// Scrolling stops hard at the bounds (default)
+    None,
+    /// Content follows the drag past the bounds, springing back on release
+    Bounce,
+    /// Content stops at the bounds and the edge being pulled is highlighted
+    Glow,
+}
+
+impl Default for OverscrollMode {
+    fn default() -> Self {
+        OverscrollMode::None
+    }
+}
 
 /// Logic for a scroll region
 ///
@@ -20,6 +41,9 @@ pub struct ScrollComponent {
     max_offset: Offset,
     offset: Offset,
     scroll_rate: f32,
+    overscroll_mode: OverscrollMode,
+    overscroll: Offset,
+    dragging: bool,
 }
 
 impl Default for ScrollComponent {
@@ -29,6 +53,9 @@ impl Default for ScrollComponent {
             max_offset: Offset::ZERO,
             offset: Offset::ZERO,
             scroll_rate: 30.0,
+            overscroll_mode: OverscrollMode::None,
+            overscroll: Offset::ZERO,
+            dragging: false,
         }
     }
 }
@@ -51,6 +78,69 @@ impl ScrollComponent {
         self.offset
     }
 
+    /// Get the current overscroll
+    ///
+    /// This is the distance the content has been dragged beyond the scroll
+    /// bounds: negative components lie before the start, positive components
+    /// beyond [`Self::max_offset`]. It is always zero when the overscroll mode
+    /// is [`OverscrollMode::None`].
+    #[inline]
+    pub fn overscroll(&self) -> Offset {
+        self.overscroll
+    }
+
+    /// Get the offset to use when drawing content
+    ///
+    /// This equals [`Self::offset`] except while bouncing, where the content is
+    /// displaced by a damped fraction of the overscroll.
+    #[inline]
+    pub fn draw_offset(&self) -> Offset {
+        match self.overscroll_mode {
+            OverscrollMode::Bounce => self.offset + self.overscroll / 2,
+            _ => self.offset,
+        }
+    }
+
+    /// Get the overscroll mode
+    #[inline]
+    pub fn overscroll_mode(&self) -> OverscrollMode {
+        self.overscroll_mode
+    }
+
+    /// Set the overscroll mode
+    ///
+    /// Any current overscroll is cleared.
+    #[inline]
+    pub fn set_overscroll(&mut self, mode: OverscrollMode) -> TkAction {
+        self.overscroll_mode = mode;
+        if self.overscroll != Offset::ZERO {
+            self.overscroll = Offset::ZERO;
+            TkAction::REDRAW
+        } else {
+            TkAction::empty()
+        }
+    }
+
+    /// True when overscroll remains after the drag was released
+    ///
+    /// The owner should then schedule [`Event::TimerUpdate`] and call
+    /// [`Self::relax_overscroll`] until this returns false.
+    #[inline]
+    pub fn needs_relax(&self) -> bool {
+        !self.dragging && self.overscroll != Offset::ZERO
+    }
+
+    /// Step the overscroll back towards zero
+    ///
+    /// Does nothing while a drag is in progress.
+    pub fn relax_overscroll(&mut self) -> TkAction {
+        if !self.needs_relax() {
+            return TkAction::empty();
+        }
+        self.overscroll = self.overscroll * 2 / 3;
+        TkAction::REDRAW
+    }
+
     /// Set sizes:
     ///
     /// -   `window_size`: size of scroll region on the outside
@@ -60,7 +150,7 @@ impl ScrollComponent {
     /// change in offset. In practice the caller will likely be performing all
     /// required updates regardless and the return value can be safely ignored.
     pub fn set_sizes(&mut self, window_size: Size, content_size: Size) -> TkAction {
-        self.max_offset = Offset::from(content_size) - Offset::from(window_size);
+        self.max_offset = Offset::from(content_size) - window_size;
         self.set_offset(self.offset)
     }
 
@@ -119,7 +209,7 @@ impl ScrollComponent {
     #[inline]
     pub fn focus_rect(&mut self, rect: Rect, window_rect: Rect) -> (Rect, TkAction) {
         let v = rect.pos - window_rect.pos;
-        let off = Offset::from(rect.size) - Offset::from(window_rect.size);
+        let off = Offset::from(rect.size) - window_rect.size;
         let offset = self.offset.max(v + off).min(v);
         let action = self.set_offset(offset);
         (rect - self.offset, action)
@@ -214,9 +304,22 @@ impl ScrollComponent {
                 coord,
             } => on_press_start(source, start_id, coord),
             Event::PressMove { delta, .. } => {
-                action = self.set_offset(self.offset - delta);
+                let target = self.offset + self.overscroll - delta;
+                action = self.set_offset(target);
+                if self.overscroll_mode != OverscrollMode::None {
+                    let limit = Offset::from(window_size) / 2;
+                    let over = (target - self.offset).clamp(Offset::ZERO - limit, limit);
+                    if over != self.overscroll {
+                        self.overscroll = over;
+                        action |= TkAction::REDRAW;
+                    }
+                }
+                self.dragging = true;
+            }
+            Event::PressEnd { .. } => {
+                // consume due to request
+                self.dragging = false;
             }
-            Event::PressEnd { .. } => (), // consume due to request
             e @ _ => {
                 response = Response::Unhandled(e);
             }
@@ -260,6 +363,22 @@ impl<W: Widget> ScrollRegion<W> {
         }
     }
 
+    /// Set the overscroll mode (inline)
+    #[inline]
+    pub fn with_overscroll(mut self, mode: OverscrollMode) -> Self {
+        let _ = self.scroll.set_overscroll(mode);
+        self
+    }
+
+    /// Set the overscroll mode
+    ///
+    /// This controls what happens when content is dragged beyond its bounds.
+    /// By default, scrolling simply stops at the edge.
+    #[inline]
+    pub fn set_overscroll(&mut self, mode: OverscrollMode) -> TkAction {
+        self.scroll.set_overscroll(mode)
+    }
+
     /// Access inner widget directly
     #[inline]
     pub fn inner(&self) -> &W {
@@ -352,10 +471,27 @@ impl<W: Widget> Layout for ScrollRegion<W> {
         let disabled = disabled || self.is_disabled();
         draw_handle.clip_region(
             self.core.rect,
-            self.scroll_offset(),
+            self.scroll.draw_offset(),
             ClipRegion::Scroll,
             &mut |handle| self.inner.draw(handle, mgr, disabled),
         );
+
+        let over = self.scroll.overscroll();
+        if self.scroll.overscroll_mode() == OverscrollMode::Glow && over != Offset::ZERO {
+            let rect = self.core.rect;
+            let w = (over.0.abs() / 4).min(rect.size.0 / 8);
+            if w > 0 {
+                let x = if over.0 < 0 { 0 } else { rect.size.0 - w };
+                let size = Size(w, rect.size.1);
+                draw_handle.selection_box(Rect::new(rect.pos + Offset(x, 0), size));
+            }
+            let h = (over.1.abs() / 4).min(rect.size.1 / 8);
+            if h > 0 {
+                let y = if over.1 < 0 { 0 } else { rect.size.1 - h };
+                let size = Size(rect.size.0, h);
+                draw_handle.selection_box(Rect::new(rect.pos + Offset(0, y), size));
+            }
+        }
     }
 }
 
@@ -378,6 +514,13 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
             }
         } else {
             debug_assert!(id == self.id(), "SendEvent::send: bad WidgetId");
+            if event == Event::TimerUpdate {
+                *mgr |= self.scroll.relax_overscroll();
+                if self.scroll.needs_relax() {
+                    mgr.update_on_timer(OVERSCROLL_STEP, self.id());
+                }
+                return Response::None;
+            }
             event
         };
 
@@ -390,8 +533,11 @@ impl<W: Widget> event::SendEvent for ScrollRegion<W> {
                         mgr.request_grab(id, source, coord, event::GrabMode::Grab, icon);
                     }
                 });
-        if !action.is_empty() {
-            *mgr |= action;
+        if self.scroll.needs_relax() {
+            mgr.update_on_timer(OVERSCROLL_STEP, id);
+        }
+        *mgr |= action;
+        if action.contains(TkAction::REGION_MOVED) {
             Response::Focus(self.core.rect)
         } else {
             response.void_into()
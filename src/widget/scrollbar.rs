@@ -7,19 +7,28 @@
 
 use std::fmt::Debug;
 
-use super::{DragHandle, ScrollRegion};
-use kas::{event, prelude::*};
+use super::{DragHandle, DragMsg, ScrollRegion};
+use kas::event::{self, Command};
+use kas::prelude::*;
 
 /// A scroll bar
 ///
 /// Scroll bars allow user-input of a value between 0 and a defined maximum,
 /// and allow the size of the handle to be specified.
+///
+/// This widget may be used standalone to control custom scrollable content:
+/// configure with [`ScrollBar::set_sizes`] and handle the emitted value (the
+/// new offset). Optionally (see [`ScrollBar::with_key_nav`]) the bar may
+/// receive keyboard focus, supporting the `PageUp`, `PageDown`, `Home` and
+/// `End` keys.
 #[derive(Clone, Debug, Default, Widget)]
 #[handler(send=noauto, msg = i32)]
+#[widget(config=noauto)]
 pub struct ScrollBar<D: Directional> {
     #[widget_core]
     core: CoreData,
     direction: D,
+    key_nav: bool,
     // Terminology assumes vertical orientation:
     min_handle_len: i32,
     handle_len: i32,
@@ -48,6 +57,7 @@ impl<D: Directional> ScrollBar<D> {
         ScrollBar {
             core: Default::default(),
             direction,
+            key_nav: false,
             min_handle_len: 0,
             handle_len: 0,
             handle_value: 1,
@@ -66,6 +76,15 @@ impl<D: Directional> ScrollBar<D> {
         self
     }
 
+    /// Set the initial content and viewport sizes
+    ///
+    /// See [`ScrollBar::set_sizes`].
+    #[inline]
+    pub fn with_sizes(mut self, content: i32, viewport: i32) -> Self {
+        let _ = self.set_sizes(content, viewport);
+        self
+    }
+
     /// Set the initial value
     #[inline]
     pub fn with_value(mut self, value: i32) -> Self {
@@ -73,6 +92,30 @@ impl<D: Directional> ScrollBar<D> {
         self
     }
 
+    /// Enable keyboard navigation (inline)
+    ///
+    /// When enabled, the bar is included in the keyboard navigation order and
+    /// responds to `PageUp`/`PageDown` (moving by the viewport size) and
+    /// `Home`/`End`. Default: disabled.
+    #[inline]
+    pub fn with_key_nav(mut self, key_nav: bool) -> Self {
+        self.key_nav = key_nav;
+        self
+    }
+
+    /// Set the content and viewport sizes
+    ///
+    /// This is a convenience wrapper around [`ScrollBar::set_limits`] for
+    /// scrolling `content` (the total length) within `viewport` (the visible
+    /// length). The value is then the offset of the viewport within the
+    /// content, between 0 and `content - viewport`.
+    ///
+    /// Returns [`TkAction::REDRAW`] if a redraw is required.
+    #[inline]
+    pub fn set_sizes(&mut self, content: i32, viewport: i32) -> TkAction {
+        self.set_limits((content - viewport).max(0), viewport)
+    }
+
     /// Set the page limits
     ///
     /// The `max_value` parameter specifies the maximum possible value.
@@ -199,6 +242,12 @@ impl<D: Directional> ScrollBar<D> {
     }
 }
 
+impl<D: Directional> WidgetConfig for ScrollBar<D> {
+    fn key_nav(&self) -> bool {
+        self.key_nav
+    }
+}
+
 impl<D: Directional> Layout for ScrollBar<D> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let (size, min_len) = size_handle.scrollbar();
@@ -226,7 +275,8 @@ impl<D: Directional> Layout for ScrollBar<D> {
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         let dir = self.direction.as_direction();
-        let state = self.handle.input_state(mgr, disabled);
+        let mut state = self.handle.input_state(mgr, disabled);
+        state.nav_focus |= mgr.nav_focus(self.id());
         draw_handle.scrollbar(self.core.rect, self.handle.rect(), dir, state);
     }
 }
@@ -240,12 +290,31 @@ impl<D: Directional> event::SendEvent for ScrollBar<D> {
         let offset = if id <= self.handle.id() {
             match self.handle.send(mgr, id, event).try_into() {
                 Ok(res) => return res,
-                Err(offset) => offset,
+                Err(DragMsg::Move(offset)) => offset,
+                Err(_) => return Response::None,
             }
         } else {
             match event {
                 Event::PressStart { source, coord, .. } => {
-                    self.handle.handle_press_on_track(mgr, source, coord)
+                    match self.handle.handle_press_on_track(mgr, source, coord) {
+                        Response::Msg(DragMsg::Start(offset)) => offset,
+                        _ => return Response::None,
+                    }
+                }
+                Event::Command(cmd, shift) if self.key_nav => {
+                    let value = match cmd {
+                        Command::PageUp => self.value - self.handle_value,
+                        Command::PageDown => self.value + self.handle_value,
+                        Command::Home => 0,
+                        Command::End => self.max_value,
+                        _ => return Response::Unhandled(Event::Command(cmd, shift)),
+                    };
+                    let old_value = self.value;
+                    *mgr |= self.set_value(value.max(0));
+                    return match self.value != old_value {
+                        true => Response::Msg(self.value),
+                        false => Response::None,
+                    };
                 }
                 ev @ _ => return Response::Unhandled(ev),
             }
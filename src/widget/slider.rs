@@ -9,8 +9,10 @@ use std::fmt::Debug;
 use std::ops::{Add, Sub};
 use std::time::Duration;
 
-use super::DragHandle;
+use super::{DragHandle, DragMsg};
+use kas::draw::TextClass;
 use kas::event::{self, Command};
+use kas::geom::Vec2;
 use kas::prelude::*;
 
 /// Requirements on type used by [`Slider`]
@@ -84,6 +86,9 @@ impl SliderType for Duration {
 /// A slider
 ///
 /// Sliders allow user input of a value from a fixed range.
+///
+/// Optionally, tick marks and labels may be drawn alongside the track; see
+/// [`Slider::with_ticks`] and [`Slider::with_tick_labels`].
 #[derive(Clone, Debug, Default, Widget)]
 #[handler(send=noauto, msg = T)]
 #[widget(config(key_nav = true, hover_highlight = true))]
@@ -95,6 +100,13 @@ pub struct Slider<T: SliderType, D: Directional> {
     range: (T, T),
     step: T,
     value: T,
+    // Number of major intervals, number of minor intervals per major interval
+    ticks: (u32, u32),
+    labels: Vec<Text<String>>,
+    // Size of track (cross axis), tick length and tick width
+    track_size: i32,
+    tick_size: (i32, i32),
+    label_size: i32,
     #[widget]
     handle: DragHandle,
 }
@@ -133,10 +145,52 @@ impl<T: SliderType, D: Directional> Slider<T, D> {
             range: (min, max),
             step,
             value,
+            ticks: (0, 0),
+            labels: vec![],
+            track_size: 0,
+            tick_size: (0, 0),
+            label_size: 0,
             handle: DragHandle::new(),
         }
     }
 
+    /// Draw tick marks
+    ///
+    /// The range is divided into `major` intervals, with a major tick mark
+    /// at each end of each interval. Each major interval is subdivided into
+    /// `minor` intervals with (shorter) minor tick marks. No ticks are drawn
+    /// when `major == 0`; no minor ticks are drawn when `minor <= 1`.
+    ///
+    /// For example, `with_ticks(4, 5)` on the range `0..=100` draws major
+    /// ticks at `0, 25, 50, 75, 100` and minor ticks every 5 units.
+    #[inline]
+    pub fn with_ticks(mut self, major: u32, minor: u32) -> Self {
+        self.ticks = (major, minor.max(1));
+        self.labels.clear();
+        self
+    }
+
+    /// Label major tick marks
+    ///
+    /// The closure `f` is called with the value at each major tick mark (see
+    /// [`Slider::with_ticks`]) to produce a label. This method should be
+    /// called after [`Slider::with_ticks`].
+    pub fn with_tick_labels<F: Fn(T) -> String>(mut self, f: F) -> Self {
+        let major = self.ticks.0;
+        let b = self.range.1 - self.range.0;
+        self.labels = (0..=major)
+            .map(|i| {
+                let value = if i == major {
+                    self.range.1
+                } else {
+                    self.range.0 + b.mul_f64(i as f64 / major as f64)
+                };
+                Text::new_multi(f(value))
+            })
+            .collect();
+        self
+    }
+
     /// Set the initial value
     #[inline]
     pub fn with_value(mut self, mut value: T) -> Self {
@@ -219,24 +273,62 @@ impl<T: SliderType, D: Directional> Layout for Slider<T, D> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let (size, min_len) = size_handle.slider();
         let margins = (0, 0);
+        let mut label_size = 0;
+        for label in &mut self.labels {
+            let rules = size_handle.text_bound(label, TextClass::Label, axis);
+            label_size = label_size.max(rules.ideal_size());
+        }
         if self.direction.is_vertical() == axis.is_vertical() {
+            let min_len = min_len.max(label_size * self.labels.len().cast());
             SizeRules::new(min_len, min_len, margins, StretchPolicy::HighUtility)
         } else {
-            SizeRules::fixed(size.1, margins)
+            self.track_size = size.1;
+            self.label_size = label_size;
+            if self.ticks.0 > 0 {
+                let tick_width = (size_handle.separator().0 / 2).max(1);
+                self.tick_size = (size.1 / 3, tick_width);
+            } else {
+                self.tick_size = (0, 0);
+            }
+            SizeRules::fixed(size.1 + self.tick_size.0 + label_size, margins)
         }
     }
 
     fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
         self.core.rect = rect;
-        self.handle.set_rect(mgr, rect, align);
+        let mut track = rect;
+        if self.direction.is_horizontal() {
+            track.size.1 = self.track_size.min(rect.size.1);
+        } else {
+            track.size.0 = self.track_size.min(rect.size.0);
+        }
+        self.handle.set_rect(mgr, track, align);
         let min_handle_size = mgr.size_handle(|sh| (sh.slider().0).0);
-        let mut size = rect.size;
+        let mut size = track.size;
         if self.direction.is_horizontal() {
-            size.0 = min_handle_size.min(rect.size.0);
+            size.0 = min_handle_size.min(track.size.0);
         } else {
-            size.1 = min_handle_size.min(rect.size.1);
+            size.1 = min_handle_size.min(track.size.1);
         }
         let _ = self.handle.set_size_and_offset(size, self.offset());
+
+        if !self.labels.is_empty() {
+            let spacing = self.major_spacing();
+            let bounds = match self.direction.is_horizontal() {
+                true => Vec2(spacing as f32, self.label_size as f32),
+                false => Vec2(self.label_size as f32, spacing as f32),
+            };
+            let align = match self.direction.is_horizontal() {
+                true => (Align::Centre, Align::TL),
+                false => (Align::TL, Align::Centre),
+            };
+            for label in &mut self.labels {
+                label.update_env(|env| {
+                    env.set_bounds(bounds);
+                    env.set_align(align);
+                });
+            }
+        }
     }
 
     fn find_id(&self, coord: Coord) -> Option<WidgetId> {
@@ -249,7 +341,81 @@ impl<T: SliderType, D: Directional> Layout for Slider<T, D> {
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
         let dir = self.direction.as_direction();
         let state = self.input_state(mgr, disabled) | self.handle.input_state(mgr, disabled);
-        draw_handle.slider(self.core.rect, self.handle.rect(), dir, state);
+        draw_handle.slider(self.track_rect(), self.handle.rect(), dir, state);
+
+        if self.ticks.0 == 0 {
+            return;
+        }
+        let (major, minor) = self.ticks;
+        let n = major * minor;
+        let horiz = self.direction.is_horizontal();
+        let (tick_len, tick_width) = self.tick_size;
+        for i in 0..=n {
+            let pos = self.tick_pos(i as f64 / n as f64);
+            let len = if i % minor == 0 {
+                tick_len
+            } else {
+                tick_len / 2
+            };
+            let rect = match horiz {
+                true => Rect::new(pos - Offset(tick_width / 2, 0), Size(tick_width, len)),
+                false => Rect::new(pos - Offset(0, tick_width / 2), Size(len, tick_width)),
+            };
+            draw_handle.separator(rect);
+        }
+
+        let spacing = self.major_spacing();
+        for (i, label) in self.labels.iter().enumerate() {
+            let pos = self.tick_pos(i as f64 / major as f64);
+            let pos = match horiz {
+                true => pos + Offset(-spacing / 2, tick_len),
+                false => pos + Offset(tick_len, -spacing / 2),
+            };
+            draw_handle.text(pos, label, TextClass::Label);
+        }
+    }
+}
+
+impl<T: SliderType, D: Directional> Slider<T, D> {
+    // Rect of the track (excluding ticks and labels)
+    fn track_rect(&self) -> Rect {
+        let mut rect = self.core.rect;
+        if self.direction.is_horizontal() {
+            rect.size.1 = self.track_size.min(rect.size.1);
+        } else {
+            rect.size.0 = self.track_size.min(rect.size.0);
+        }
+        rect
+    }
+
+    // Distance between major ticks
+    fn major_spacing(&self) -> i32 {
+        let max_offset = self.handle.max_offset();
+        let len = match self.direction.is_horizontal() {
+            true => max_offset.0,
+            false => max_offset.1,
+        };
+        len / (self.ticks.0.max(1) as i32)
+    }
+
+    // Position of tick at fraction `frac` of the range, on the track's edge
+    fn tick_pos(&self, mut frac: f64) -> Coord {
+        if self.direction.is_reversed() {
+            frac = 1.0 - frac;
+        }
+        let track = self.track_rect();
+        let max_offset = self.handle.max_offset();
+        let h_size = self.handle.rect().size;
+        match self.direction.is_horizontal() {
+            true => {
+                let x = h_size.0 / 2 + (max_offset.0 as f64 * frac).cast_nearest();
+                Coord(track.pos.0 + x, track.pos.1 + track.size.1)
+            }
+            false => {
+                let y = h_size.1 / 2 + (max_offset.1 as f64 * frac).cast_nearest();
+                Coord(track.pos.0 + track.size.0, track.pos.1 + y)
+            }
+        }
     }
 }
 
@@ -262,7 +428,8 @@ impl<T: SliderType, D: Directional> event::SendEvent for Slider<T, D> {
         let offset = if id <= self.handle.id() {
             match self.handle.send(mgr, id, event).try_into() {
                 Ok(res) => return res,
-                Err(offset) => offset,
+                Err(DragMsg::Move(offset)) => offset,
+                Err(_) => return Response::None,
             }
         } else {
             match event {
@@ -301,7 +468,10 @@ impl<T: SliderType, D: Directional> event::SendEvent for Slider<T, D> {
                     };
                 }
                 Event::PressStart { source, coord, .. } => {
-                    self.handle.handle_press_on_track(mgr, source, coord)
+                    match self.handle.handle_press_on_track(mgr, source, coord) {
+                        Response::Msg(DragMsg::Start(offset)) => offset,
+                        _ => return Response::None,
+                    }
                 }
                 ev @ _ => return Response::Unhandled(ev),
             }
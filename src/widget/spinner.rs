@@ -0,0 +1,166 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Spinner (busy indicator)
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use kas::event;
+use kas::prelude::*;
+
+/// Interval at which to check whether a hidden spinner is drawn again
+const HIDDEN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A spinner (busy indicator)
+///
+/// This draws a rotating arc to indicate indeterminate progress. Use
+/// [`ProgressBar`](super::ProgressBar) when progress can be measured.
+///
+/// While active, the spinner requests updates on each frame (see
+/// [`Manager::update_on_frame`]); these stop while the window does not have
+/// focus and resume when it regains focus. If the spinner was not drawn in
+/// the previous frame (e.g. it is on an inactive page of a stack), it stops
+/// requesting frames and checks only occasionally whether it is drawn again.
+#[derive(Clone, Debug, Widget)]
+#[handler(handle=noauto)]
+#[widget(config=noauto)]
+pub struct Spinner {
+    #[widget_core]
+    core: CoreData,
+    active: bool,
+    /// Seconds per revolution
+    period: f32,
+    /// Time of last phase update
+    last: Instant,
+    phase: f32,
+    /// Whether updates are per-frame (otherwise hidden)
+    on_frame: bool,
+    /// Set when drawn, cleared each frame
+    drawn: Cell<bool>,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Spinner::new()
+    }
+}
+
+impl Spinner {
+    /// Construct an active spinner
+    #[inline]
+    pub fn new() -> Self {
+        Spinner {
+            core: Default::default(),
+            active: true,
+            period: 1.0,
+            last: Instant::now(),
+            phase: 0.0,
+            on_frame: false,
+            drawn: Cell::new(true),
+        }
+    }
+
+    /// Set whether the spinner is initially active (inline)
+    #[inline]
+    pub fn with_active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Set the duration of one revolution (inline)
+    ///
+    /// Default: 1 second.
+    #[inline]
+    pub fn with_period(mut self, period: Duration) -> Self {
+        self.period = period.as_secs_f32().max(f32::EPSILON);
+        self
+    }
+
+    /// Whether the spinner is active
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Start or stop the spinner
+    ///
+    /// An inactive spinner is not drawn. Since this requires the [`Manager`]
+    /// to (re)start animation, it does not return a [`TkAction`].
+    pub fn set_active(&mut self, mgr: &mut Manager, active: bool) {
+        if active == self.active {
+            return;
+        }
+        self.active = active;
+        self.set_on_frame(mgr, active);
+        mgr.redraw(self.id());
+    }
+
+    fn set_on_frame(&mut self, mgr: &mut Manager, on_frame: bool) {
+        if on_frame {
+            self.last = Instant::now();
+            self.drawn.set(true);
+        }
+        self.on_frame = on_frame;
+        mgr.update_on_frame(self.id(), on_frame);
+    }
+}
+
+impl WidgetConfig for Spinner {
+    fn configure(&mut self, mgr: &mut Manager) {
+        if self.active {
+            self.set_on_frame(mgr, true);
+        }
+    }
+}
+
+impl Layout for Spinner {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let size = size_handle.spinner();
+        let margins = size_handle.outer_margins();
+        SizeRules::extract_fixed(axis, size, margins)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, disabled: bool) {
+        if self.active {
+            self.drawn.set(true);
+            let state = self.input_state(mgr, disabled);
+            draw_handle.spinner(self.core.rect, state, self.phase);
+        }
+    }
+}
+
+impl event::Handler for Spinner {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, event: Event) -> Response<Self::Msg> {
+        match event {
+            Event::TimerUpdate if self.active => {
+                let drawn = self.drawn.replace(false);
+                if !self.on_frame {
+                    // Hidden: resume animation once drawn again. Frame
+                    // updates are paused anyway while the window is inactive.
+                    if drawn || !mgr.window_has_focus() {
+                        self.set_on_frame(mgr, true);
+                    } else {
+                        mgr.update_on_timer(HIDDEN_INTERVAL, self.id());
+                    }
+                } else if drawn {
+                    let now = Instant::now();
+                    let elapsed = (now - self.last).as_secs_f32();
+                    self.phase = (self.phase + elapsed / self.period).fract();
+                    self.last = now;
+                    mgr.redraw(self.id());
+                } else {
+                    self.set_on_frame(mgr, false);
+                    mgr.update_on_timer(HIDDEN_INTERVAL, self.id());
+                }
+                Response::None
+            }
+            Event::TimerUpdate => Response::None,
+            event => Response::Unhandled(event),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A pair of widgets separated by a draggable divider
+
+use super::{DragHandle, DragMsg};
+use kas::event;
+use kas::prelude::*;
+
+/// Two widgets separated by a draggable divider
+///
+/// The divider may be dragged to re-allocate space between the children,
+/// within the limits of their minimum sizes. Optionally (see
+/// [`SplitPane::with_snap`]) dragging the divider close to an edge collapses
+/// the adjacent child to zero size. Double-clicking the divider restores the
+/// default allocation.
+///
+/// The split position is view state: no messages are generated.
+#[derive(Clone, Debug, Widget)]
+#[handler(send=noauto, msg = <A as event::Handler>::Msg)]
+pub struct SplitPane<D: Directional, A: Widget, B: Widget<Msg = <A as event::Handler>::Msg>> {
+    #[widget_core]
+    core: CoreData,
+    direction: D,
+    #[widget]
+    first: A,
+    #[widget]
+    divider: DragHandle,
+    #[widget]
+    second: B,
+    divider_size: i32,
+    // Size rules of children along the split direction
+    rules: (SizeRules, SizeRules),
+    // Size of first child as a fraction of available length, if set by user
+    ratio: Option<f32>,
+    snap: f32,
+    snap_px: i32,
+}
+
+impl<D, A, B> SplitPane<D, A, B>
+where
+    D: Directional + Default,
+    A: Widget,
+    B: Widget<Msg = <A as event::Handler>::Msg>,
+{
+    /// Construct a new instance
+    ///
+    /// This constructor is available where the direction is determined by the
+    /// type: for `D: Directional + Default`. In other cases, use
+    /// [`SplitPane::new_with_direction`].
+    pub fn new(first: A, second: B) -> Self {
+        Self::new_with_direction(D::default(), first, second)
+    }
+}
+
+impl<D, A, B> SplitPane<D, A, B>
+where
+    D: Directional,
+    A: Widget,
+    B: Widget<Msg = <A as event::Handler>::Msg>,
+{
+    /// Construct a new instance with explicit direction
+    pub fn new_with_direction(direction: D, first: A, second: B) -> Self {
+        SplitPane {
+            core: Default::default(),
+            direction,
+            first,
+            divider: DragHandle::new(),
+            second,
+            divider_size: 0,
+            rules: (SizeRules::EMPTY, SizeRules::EMPTY),
+            ratio: None,
+            snap: 0.0,
+            snap_px: 0,
+        }
+    }
+
+    /// Set the snap distance (inline)
+    ///
+    /// When the divider is dragged to less than `snap` logical pixels from
+    /// the position where a child would be at its minimum size, that child is
+    /// collapsed to zero size. Default: 0 (children are never collapsed).
+    pub fn with_snap(mut self, snap: f32) -> Self {
+        self.snap = snap;
+        self
+    }
+
+    /// Access the first child
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Access the first child mutably
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    /// Access the second child
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Access the second child mutably
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    /// Get the split ratio
+    ///
+    /// This is the size of the first child relative to the space available
+    /// to both children, or `None` if the default allocation is used.
+    pub fn ratio(&self) -> Option<f32> {
+        self.ratio
+    }
+
+    /// Set the split ratio
+    ///
+    /// The ratio is clamped to `0..=1`; `None` selects the default allocation
+    /// (according to the children's size rules).
+    pub fn set_ratio(&mut self, ratio: Option<f32>) -> TkAction {
+        self.ratio = ratio.map(|r| r.max(0.0).min(1.0));
+        TkAction::SET_SIZE
+    }
+
+    fn avail_len(&self) -> i32 {
+        (self.core.rect.size.extract(self.direction) - self.divider_size).max(0)
+    }
+
+    // Adjust the length of the first child to respect minimum sizes and snap
+    fn snap_len(&self, len: i32, total: i32) -> i32 {
+        let (min0, min1) = (self.rules.0.min_size(), self.rules.1.min_size());
+        let len = if len < min0 {
+            match len < min0 - self.snap_px {
+                true => 0,
+                false => min0,
+            }
+        } else if total - len < min1 {
+            match total - len < min1 - self.snap_px {
+                true => total,
+                false => total - min1,
+            }
+        } else {
+            len
+        };
+        len.max(0).min(total)
+    }
+
+    // Set child rects given the length of the first child
+    fn layout_children(&mut self, mgr: &mut Manager, len: i32) {
+        let rect = self.core.rect;
+        let dir = self.direction;
+        let total = self.avail_len();
+        let len = len.max(0).min(total);
+
+        let (len0, len1) = match dir.is_reversed() {
+            false => (len, total - len),
+            true => (total - len, len),
+        };
+        let mut rect0 = rect;
+        rect0.size.set_component(dir, len0);
+        let mut rect1 = rect;
+        let pos1 = rect.pos.extract(dir) + len0 + self.divider_size;
+        rect1.pos.set_component(dir, pos1);
+        rect1.size.set_component(dir, len1);
+        let (rect_first, rect_second) = match dir.is_reversed() {
+            false => (rect0, rect1),
+            true => (rect1, rect0),
+        };
+
+        let align = AlignHints::default();
+        self.first.set_rect(mgr, rect_first, align);
+        self.second.set_rect(mgr, rect_second, align);
+
+        let mut size = rect.size;
+        size.set_component(dir, self.divider_size);
+        let mut offset = Offset::ZERO;
+        offset.set_component(dir, len0);
+        let _ = self.divider.set_size_and_offset(size, offset);
+    }
+
+    fn divider_moved(&mut self, mgr: &mut Manager, offset: Offset) {
+        let total = self.avail_len();
+        let pos = offset.extract(self.direction);
+        let len = match self.direction.is_reversed() {
+            false => pos,
+            true => total - pos,
+        };
+        let len = self.snap_len(len, total);
+        if total > 0 {
+            self.ratio = Some(len as f32 / total as f32);
+        }
+        self.layout_children(mgr, len);
+        mgr.update_rect_cache(&*self);
+    }
+}
+
+impl<D, A, B> Layout for SplitPane<D, A, B>
+where
+    D: Directional,
+    A: Widget,
+    B: Widget<Msg = <A as event::Handler>::Msg>,
+{
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let rules0 = self.first.size_rules(size_handle, axis);
+        let rules1 = self.second.size_rules(size_handle, axis);
+        if self.direction.is_vertical() != axis.is_vertical() {
+            return rules0.max(rules1);
+        }
+
+        self.divider_size = size_handle.separator().extract(axis);
+        self.snap_px = (self.snap * size_handle.scale_factor()).cast_nearest();
+        self.rules = (rules0, rules1);
+        let divider = SizeRules::fixed(self.divider_size, (0, 0));
+        match self.direction.is_reversed() {
+            false => rules0.appended(divider).appended(rules1),
+            true => rules1.appended(divider).appended(rules0),
+        }
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        self.divider.set_rect(mgr, rect, AlignHints::default());
+
+        let total = self.avail_len();
+        let len = match self.ratio {
+            Some(ratio) => self.snap_len((ratio * total as f32).cast_nearest(), total),
+            None => {
+                let mut widths = [0; 2];
+                let rules = [self.rules.0, self.rules.1];
+                SizeRules::solve_seq(&mut widths, &rules, total);
+                widths[0]
+            }
+        };
+        self.layout_children(mgr, len);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.first
+            .find_id(coord)
+            .or_else(|| self.second.find_id(coord))
+            .or_else(|| self.divider.find_id(coord))
+            .or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.first.draw(draw_handle, mgr, disabled);
+        self.second.draw(draw_handle, mgr, disabled);
+        draw_handle.separator(self.divider.rect());
+    }
+}
+
+impl<D, A, B> event::SendEvent for SplitPane<D, A, B>
+where
+    D: Directional,
+    A: Widget,
+    B: Widget<Msg = <A as event::Handler>::Msg>,
+{
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        if id <= self.first.id() {
+            self.first.send(mgr, id, event)
+        } else if id <= self.divider.id() {
+            if let Event::PressStart { source, .. } = &event {
+                if source.repetitions() == 2 {
+                    self.ratio = None;
+                    self.set_rect(mgr, self.core.rect, AlignHints::default());
+                    mgr.update_rect_cache(&*self);
+                    mgr.redraw(self.id());
+                    return Response::None;
+                }
+            }
+            match self.divider.send(mgr, id, event).try_into() {
+                Ok(r) => r,
+                Err(DragMsg::Move(offset)) => {
+                    self.divider_moved(mgr, offset);
+                    Response::None
+                }
+                Err(_) => Response::None,
+            }
+        } else if id <= self.second.id() {
+            self.second.send(mgr, id, event)
+        } else {
+            debug_assert!(id == self.id(), "SendEvent::send: bad WidgetId");
+            self.handle(mgr, event)
+        }
+    }
+}
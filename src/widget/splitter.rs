@@ -8,10 +8,10 @@
 use log::warn;
 use std::ops::{Index, IndexMut};
 
-use super::DragHandle;
+use super::{DragHandle, DragMsg};
 use kas::dir::{Down, Right};
 use kas::event;
-use kas::layout::{self, RulesSetter, RulesSolver};
+use kas::layout::{self, RowStorage, RulesSetter, RulesSolver};
 use kas::prelude::*;
 
 /// A generic row widget
@@ -69,6 +69,10 @@ pub type RefSplitter<'a, D, M> = Splitter<D, &'a mut dyn Widget<Msg = M>>;
 /// A resizable row/column widget
 ///
 /// Similar to [`kas::widget::List`] but with draggable handles between items.
+///
+/// Positions of handles moved by the user are remembered: when the splitter is
+/// resized, only the unadjusted parts are re-negotiated. Double-clicking a
+/// handle sizes the item before it to its ideal size.
 // TODO: better doc
 #[derive(Clone, Default, Debug, Widget)]
 #[handler(send=noauto, msg=<W as event::Handler>::Msg)]
@@ -79,6 +83,8 @@ pub struct Splitter<D: Directional, W: Widget> {
     core: CoreData,
     widgets: Vec<W>,
     handles: Vec<DragHandle>,
+    // Handle positions set by the user, relative to the start (len <= handles.len())
+    handle_pos: Vec<Option<i32>>,
     data: layout::DynRowStorage,
     direction: D,
 }
@@ -178,6 +184,11 @@ impl<D: Directional, W: Widget> Layout for Splitter<D, W> {
 
             n += 1;
         }
+
+        self.handle_pos.resize(self.handles.len(), None);
+        if self.handle_pos.iter().any(|pos| pos.is_some()) {
+            self.adjust_sizes(mgr);
+        }
     }
 
     fn find_id(&self, coord: Coord) -> Option<WidgetId> {
@@ -233,15 +244,24 @@ impl<D: Directional, W: Widget> event::SendEvent for Splitter<D, W> {
                     break;
                 }
                 if id <= self.handles[n].id() {
-                    return self.handles[n]
-                        .send(mgr, id, event)
-                        .try_into()
-                        .unwrap_or_else(|_| {
+                    if let Event::PressStart { source, .. } = &event {
+                        if source.repetitions() == 2 {
+                            self.auto_size(mgr, n);
+                            return Response::None;
+                        }
+                    }
+                    return match self.handles[n].send(mgr, id, event).try_into() {
+                        Ok(r) => r,
+                        Err(DragMsg::Move(_)) => {
                             // Message is the new offset relative to the track;
                             // the handle has already adjusted its position
-                            self.adjust_size(mgr, n);
+                            let pos = self.handles[n].rect().pos - self.core.rect.pos;
+                            self.set_handle_pos(n, pos.extract(self.direction));
+                            self.adjust_sizes(mgr);
                             Response::None
-                        });
+                        }
+                        Err(_) => Response::None,
+                    };
                 }
                 n += 1;
             }
@@ -273,25 +293,68 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
             core: Default::default(),
             widgets,
             handles,
+            handle_pos: vec![],
             data: Default::default(),
             direction,
         }
     }
 
-    fn adjust_size(&mut self, mgr: &mut Manager, n: usize) {
-        assert!(n < self.handles.len());
-        assert_eq!(self.widgets.len(), self.handles.len() + 1);
-        let index = 2 * n + 1;
+    fn set_handle_pos(&mut self, n: usize, pos: i32) {
+        if self.handle_pos.len() < self.handles.len() {
+            self.handle_pos.resize(self.handles.len(), None);
+        }
+        self.handle_pos[n] = Some(pos);
+    }
+
+    // Keep handle_pos in sync with handles after a handle is added at n
+    fn insert_handle_pos(&mut self, n: usize) {
+        if n <= self.handle_pos.len() {
+            self.handle_pos.insert(n, None);
+        }
+    }
 
-        let hrect = self.handles[n].rect();
-        let width1 = (hrect.pos - self.core.rect.pos).extract(self.direction);
-        let width2 = (self.core.rect.size - hrect.size).extract(self.direction) - width1;
+    // Remove the handle adjacent to the widget at index (which has not yet
+    // been removed), along with its position
+    fn remove_handle(&mut self, index: usize) {
+        if self.handles.is_empty() {
+            return;
+        }
+        let n = index.min(self.handles.len() - 1);
+        let _ = self.handles.remove(n);
+        if n < self.handle_pos.len() {
+            self.handle_pos.remove(n);
+        }
+    }
+
+    // Size the item before handle n to its ideal size
+    fn auto_size(&mut self, mgr: &mut Manager, n: usize) {
+        let start = (self.widgets[n].rect().pos - self.core.rect.pos).extract(self.direction);
+        let ideal = self.data.rules()[n << 1].ideal_size();
+        self.set_handle_pos(n, start + ideal);
+        self.adjust_sizes(mgr);
+        mgr.redraw(self.id());
+    }
+
+    // Solve child sizes, keeping handles at user-set positions
+    fn adjust_sizes(&mut self, mgr: &mut Manager) {
+        assert_eq!(self.widgets.len(), self.handles.len() + 1);
+        let total = self.core.rect.size.extract(self.direction);
 
         let dim = (self.direction, self.num_children());
         let mut setter =
             layout::RowSetter::<D, Vec<i32>, _>::new_unsolved(self.core.rect, dim, &mut self.data);
-        setter.solve_range(&mut self.data, 0..index, width1);
-        setter.solve_range(&mut self.data, (index + 1)..dim.1, width2);
+        let (mut start, mut pos) = (0, 0);
+        for (n, handle_pos) in self.handle_pos.iter_mut().enumerate() {
+            if let Some(p) = handle_pos.as_mut() {
+                let index = (n << 1) + 1;
+                let handle_size = self.handles[n].rect().size.extract(self.direction);
+                *p = (*p).min(total - handle_size).max(pos);
+                setter.solve_range(&mut self.data, start..index, *p - pos);
+                start = index + 1;
+                pos = *p + handle_size;
+            }
+        }
+        setter.solve_range(&mut self.data, start..dim.1, total - pos);
         setter.update_offsets(&mut self.data);
 
         let mut n = 0;
@@ -312,6 +375,18 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
 
             n += 1;
         }
+        mgr.update_rect_cache(&*self);
+    }
+
+    /// Forget handle positions set by the user
+    ///
+    /// Item sizes are re-negotiated from their size rules on the next resize.
+    pub fn reset_sizes(&mut self) -> TkAction {
+        if self.handle_pos.iter().all(|pos| pos.is_none()) {
+            return TkAction::empty();
+        }
+        self.handle_pos.clear();
+        TkAction::SET_SIZE
     }
 
     /// True if there are no child widgets
@@ -347,6 +422,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         };
         self.widgets.clear();
         self.handles.clear();
+        self.handle_pos.clear();
         action
     }
 
@@ -356,6 +432,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     pub fn push(&mut self, widget: W) -> TkAction {
         if !self.widgets.is_empty() {
             self.handles.push(DragHandle::new());
+            self.insert_handle_pos(self.handles.len() - 1);
         }
         self.widgets.push(widget);
         TkAction::RECONFIGURE
@@ -374,6 +451,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
             false => TkAction::RECONFIGURE,
         };
         let _ = self.handles.pop();
+        self.handle_pos.truncate(self.handles.len());
         (self.widgets.pop(), action)
     }
 
@@ -383,8 +461,12 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     ///
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn insert(&mut self, index: usize, widget: W) -> TkAction {
+        assert!(index <= self.widgets.len());
         if !self.widgets.is_empty() {
-            self.handles.push(DragHandle::new());
+            // The new handle follows the new widget, or precedes it if last
+            let n = index.min(self.handles.len());
+            self.handles.insert(n, DragHandle::new());
+            self.insert_handle_pos(n);
         }
         self.widgets.insert(index, widget);
         TkAction::RECONFIGURE
@@ -396,7 +478,8 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     ///
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn remove(&mut self, index: usize) -> (W, TkAction) {
-        let _ = self.handles.pop();
+        assert!(index < self.widgets.len());
+        self.remove_handle(index);
         let r = self.widgets.remove(index);
         (r, TkAction::RECONFIGURE)
     }
@@ -423,6 +506,9 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         self.widgets.extend(iter);
         self.handles
             .resize_with(self.widgets.len().saturating_sub(1), || DragHandle::new());
+        if !self.handle_pos.is_empty() {
+            self.handle_pos.resize(self.handles.len(), None);
+        }
         match len == self.widgets.len() {
             true => TkAction::empty(),
             false => TkAction::RECONFIGURE,
@@ -446,6 +532,9 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
         }
         self.handles
             .resize_with(self.widgets.len().saturating_sub(1), || DragHandle::new());
+        if !self.handle_pos.is_empty() {
+            self.handle_pos.resize(self.handles.len(), None);
+        }
         TkAction::RECONFIGURE
     }
 
@@ -455,11 +544,16 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
     ///
     /// Triggers a [reconfigure action](Manager::send_action) if any widgets
     /// are removed.
-    pub fn retain<F: FnMut(&W) -> bool>(&mut self, f: F) -> TkAction {
+    pub fn retain<F: FnMut(&W) -> bool>(&mut self, mut f: F) -> TkAction {
         let len = self.widgets.len();
-        self.widgets.retain(f);
-        self.handles
-            .resize_with(self.widgets.len().saturating_sub(1), || DragHandle::new());
+        let keep: Vec<bool> = self.widgets.iter().map(|w| f(w)).collect();
+        // Remove in reverse order so that indices remain valid
+        for index in (0..len).rev() {
+            if !keep[index] {
+                self.remove_handle(index);
+                let _ = self.widgets.remove(index);
+            }
+        }
         match len == self.widgets.len() {
             true => TkAction::empty(),
             false => TkAction::RECONFIGURE,
@@ -480,3 +574,46 @@ impl<D: Directional, W: Widget> IndexMut<usize> for Splitter<D, W> {
         &mut self.widgets[index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kas::testing::TestHarness;
+    use kas::widget::StrLabel;
+
+    #[test]
+    fn handle_pos_sync() {
+        let labels = vec!["a", "b", "c", "d"];
+        let s = Splitter::<Right, _>::new(labels.into_iter().map(StrLabel::new).collect());
+        let mut harness = TestHarness::with_size(s, Size(400, 50));
+
+        // Move each handle so that all have a user-set position
+        for n in 0..3 {
+            let rect = harness.widget().handles[n].rect();
+            let start = rect.pos + rect.size / 2;
+            assert!(harness.drag(start, start + Offset(5, 0)));
+        }
+        let p = harness.widget().handle_pos.clone();
+        assert!(p.iter().all(|pos| pos.is_some()));
+        let (p0, p1, p2) = (p[0], p[1], p[2]);
+
+        let s = harness.widget_mut();
+        let _ = s.insert(1, StrLabel::new("x"));
+        assert_eq!(s.handle_pos, vec![p0, None, p1, p2]);
+        let _ = s.remove(1);
+        assert_eq!(s.handle_pos, vec![p0, p1, p2]);
+        let _ = s.remove(3);
+        assert_eq!(s.handle_pos, vec![p0, p1]);
+        let _ = s.push(StrLabel::new("e"));
+        assert_eq!(s.handle_pos, vec![p0, p1, None]);
+        let _ = s.pop();
+        assert_eq!(s.handle_pos, vec![p0, p1]);
+        let _ = s.retain(|w| w.get_str() != "a");
+        assert_eq!(s.handle_pos, vec![p1]);
+        let _ = s.resize_with(4, |_| StrLabel::new("f"));
+        assert_eq!(s.handle_pos, vec![p1, None, None]);
+        let _ = s.resize_with(2, |_| StrLabel::new("f"));
+        assert_eq!(s.handle_pos, vec![p1]);
+        assert_eq!(s.handles.len(), s.widgets.len() - 1);
+    }
+}
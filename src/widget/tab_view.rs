@@ -0,0 +1,420 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A tabbed container
+
+use super::{Row, TextButton};
+use kas::event::{self, Command};
+use kas::prelude::*;
+
+/// Message type of [`TabView`]
+#[derive(Clone, Debug, VoidMsg)]
+pub enum TabMsg<M> {
+    /// The tab with this index was selected by the user
+    Selected(usize),
+    /// The user requested closure of the tab with this index
+    ///
+    /// The tab is not closed automatically; use [`TabView::remove`].
+    Close(usize),
+    /// A message from a page
+    Page(M),
+}
+
+impl<M> From<Response<TabMsg<M>>> for Response<M> {
+    fn from(r: Response<TabMsg<M>>) -> Self {
+        match Response::try_from(r) {
+            Ok(r) => r,
+            Err(msg) => match msg {
+                TabMsg::Page(msg) => Response::Msg(msg),
+                _ => Response::None,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, VoidMsg)]
+enum TabAction {
+    Select,
+    Close,
+}
+
+/// A set of pages, selectable via a strip of tabs
+///
+/// Only the active page is drawn and receives input; switching tabs sets the
+/// position of the newly active page without resizing other widgets. The
+/// view is sized to fit the largest page.
+///
+/// Tabs may be selected by clicking, or via the keyboard: `Ctrl+Tab` and
+/// `Ctrl+Shift+Tab` cycle through tabs, while the arrow keys move between
+/// tabs when a tab has keyboard focus. Each change is reported via
+/// [`TabMsg::Selected`].
+///
+/// If constructed with [`TabView::new_closable`], each tab has a close button
+/// which emits [`TabMsg::Close`].
+#[derive(Clone, Debug, Widget)]
+#[handler(send=noauto, msg=TabMsg<<W as event::Handler>::Msg>)]
+#[widget(children=noauto)]
+pub struct TabView<W: Widget> {
+    first_id: WidgetId,
+    #[widget_core]
+    core: CoreData,
+    strip: Row<TextButton<TabAction>>,
+    pages: Vec<W>,
+    active: usize,
+    closable: bool,
+    strip_rules: SizeRules,
+    page_rect: Rect,
+}
+
+impl<W: Widget> WidgetChildren for TabView<W> {
+    #[inline]
+    fn first_id(&self) -> WidgetId {
+        self.first_id
+    }
+    fn record_first_id(&mut self, id: WidgetId) {
+        self.first_id = id;
+    }
+    #[inline]
+    fn num_children(&self) -> usize {
+        1 + self.pages.len()
+    }
+    #[inline]
+    fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        match index {
+            0 => Some(self.strip.as_widget()),
+            i => self.pages.get(i - 1).map(|w| w.as_widget()),
+        }
+    }
+    #[inline]
+    fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        match index {
+            0 => Some(self.strip.as_widget_mut()),
+            i => self.pages.get_mut(i - 1).map(|w| w.as_widget_mut()),
+        }
+    }
+}
+
+impl<W: Widget> Layout for TabView<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let strip_rules = self.strip.size_rules(size_handle, axis);
+        let mut rules = SizeRules::EMPTY;
+        for page in &mut self.pages {
+            rules = rules.max(page.size_rules(size_handle, axis));
+        }
+        if axis.is_horizontal() {
+            rules.max(strip_rules)
+        } else {
+            self.strip_rules = strip_rules;
+            strip_rules.appended(rules)
+        }
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let strip_height = self.strip_rules.ideal_size().min(rect.size.1);
+        let strip_rect = Rect::new(rect.pos, Size(rect.size.0, strip_height));
+        let align_strip = AlignHints::new(align.horiz, None);
+        self.strip.set_rect(mgr, strip_rect, align_strip);
+
+        let pos = rect.pos + Size(0, strip_height);
+        self.page_rect = Rect::new(pos, Size(rect.size.0, rect.size.1 - strip_height));
+        if let Some(page) = self.pages.get_mut(self.active) {
+            page.set_rect(mgr, self.page_rect, AlignHints::default());
+        }
+    }
+
+    fn is_child_active(&self, index: usize) -> bool {
+        index == 0 || index - 1 == self.active
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+        self.strip
+            .find_id(coord)
+            .or_else(|| self.pages.get(self.active)?.find_id(coord))
+            .or(Some(self.id()))
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &event::ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        self.strip.draw(draw_handle, mgr, disabled);
+        if let Some(page) = self.pages.get(self.active) {
+            draw_handle.selection_box(self.strip[self.strip_index(self.active)].rect());
+            page.draw(draw_handle, mgr, disabled);
+        }
+    }
+}
+
+impl<W: Widget> event::SendEvent for TabView<W> {
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        if id <= self.strip.id() {
+            return match self.strip.send(mgr, id, event) {
+                Response::Msg((index, action)) => {
+                    let index = match self.closable {
+                        false => index,
+                        true => index / 2,
+                    };
+                    match action {
+                        TabAction::Select => {
+                            self.activate(mgr, index);
+                            Response::Msg(TabMsg::Selected(index))
+                        }
+                        TabAction::Close => Response::Msg(TabMsg::Close(index)),
+                    }
+                }
+                Response::Unhandled(Event::Command(cmd, shift)) => {
+                    self.handle_command(mgr, cmd, shift, true)
+                }
+                r => r.try_into().unwrap_or(Response::None),
+            };
+        }
+
+        for index in 0..self.pages.len() {
+            if id <= self.pages[index].id() {
+                return match self.pages[index].send(mgr, id, event) {
+                    Response::Focus(rect) => {
+                        if index != self.active {
+                            self.activate(mgr, index);
+                        }
+                        Response::Focus(rect)
+                    }
+                    Response::Unhandled(Event::Command(cmd, shift)) => {
+                        self.handle_command(mgr, cmd, shift, false)
+                    }
+                    r => match r.try_into() {
+                        Ok(r) => r,
+                        Err(msg) => Response::Msg(TabMsg::Page(msg)),
+                    },
+                };
+            }
+        }
+
+        debug_assert!(id == self.id(), "SendEvent::send: bad WidgetId");
+        match event {
+            Event::Command(cmd, shift) => self.handle_command(mgr, cmd, shift, false),
+            event => Response::Unhandled(event),
+        }
+    }
+}
+
+impl<W: Widget> TabView<W> {
+    /// Construct from a list of `(label, page)` pairs
+    ///
+    /// The first page is initially active.
+    pub fn new<S: Into<AccelString>>(pages: Vec<(S, W)>) -> Self {
+        Self::new_(pages, false)
+    }
+
+    /// Construct from a list of `(label, page)` pairs, with close buttons
+    ///
+    /// The first page is initially active.
+    pub fn new_closable<S: Into<AccelString>>(pages: Vec<(S, W)>) -> Self {
+        Self::new_(pages, true)
+    }
+
+    fn new_<S: Into<AccelString>>(pages: Vec<(S, W)>, closable: bool) -> Self {
+        let mut tab_view = TabView {
+            first_id: Default::default(),
+            core: Default::default(),
+            strip: Row::new(vec![]),
+            pages: Vec::with_capacity(pages.len()),
+            active: 0,
+            closable,
+            strip_rules: SizeRules::EMPTY,
+            page_rect: Rect::default(),
+        };
+        for (label, page) in pages {
+            let _ = tab_view.push(label, page);
+        }
+        tab_view
+    }
+
+    fn strip_index(&self, index: usize) -> usize {
+        match self.closable {
+            false => index,
+            true => 2 * index,
+        }
+    }
+
+    // Switch to tab `index`, setting the position of the new page only
+    fn activate(&mut self, mgr: &mut Manager, index: usize) {
+        if index == self.active || index >= self.pages.len() {
+            return;
+        }
+        self.active = index;
+        self.pages[index].set_rect(mgr, self.page_rect, AlignHints::default());
+        // Update the whole view, dropping the rects of the previous page
+        mgr.update_rect_cache(&*self);
+        *mgr |= TkAction::REGION_MOVED;
+    }
+
+    fn handle_command(
+        &mut self,
+        mgr: &mut Manager,
+        cmd: Command,
+        shift: bool,
+        from_strip: bool,
+    ) -> Response<<Self as event::Handler>::Msg> {
+        let len = self.pages.len();
+        if len == 0 {
+            return Response::Unhandled(Event::Command(cmd, shift));
+        }
+        let index = match cmd {
+            Command::TabNext => (self.active + 1) % len,
+            Command::TabPrev => (self.active + len - 1) % len,
+            Command::Right if from_strip => (self.active + 1).min(len - 1),
+            Command::Left if from_strip => self.active.saturating_sub(1),
+            _ => return Response::Unhandled(Event::Command(cmd, shift)),
+        };
+        if from_strip {
+            mgr.set_nav_focus(self.strip[self.strip_index(index)].id());
+        }
+        if index == self.active {
+            return Response::None;
+        }
+        self.activate(mgr, index);
+        Response::Msg(TabMsg::Selected(index))
+    }
+
+    /// Get the index of the active page
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Set the initially active page (inline)
+    ///
+    /// Does nothing if `index >= self.len()`.
+    pub fn with_active(mut self, index: usize) -> Self {
+        if index < self.pages.len() {
+            self.active = index;
+        }
+        self
+    }
+
+    /// Change the active page
+    ///
+    /// The new page is given the existing page area; this does not require a
+    /// resize. Does nothing if `index >= self.len()`.
+    pub fn set_active(&mut self, mgr: &mut Manager, index: usize) {
+        self.activate(mgr, index);
+    }
+
+    /// True if there are no pages
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Returns the number of pages
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Get a page
+    pub fn get(&self, index: usize) -> Option<&W> {
+        self.pages.get(index)
+    }
+
+    /// Get a page mutably
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut W> {
+        self.pages.get_mut(index)
+    }
+
+    /// Append a page
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push<S: Into<AccelString>>(&mut self, label: S, page: W) -> TkAction {
+        let _ = self
+            .strip
+            .push(TextButton::new_msg(label, TabAction::Select));
+        if self.closable {
+            let _ = self.strip.push(TextButton::new_msg("×", TabAction::Close));
+        }
+        self.pages.push(page);
+        TkAction::RECONFIGURE
+    }
+
+    /// Remove the page at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, index: usize) -> (W, TkAction) {
+        let strip_index = self.strip_index(index);
+        if self.closable {
+            let _ = self.strip.remove(strip_index + 1);
+        }
+        let _ = self.strip.remove(strip_index);
+        let page = self.pages.remove(index);
+        if self.active > index || self.active >= self.pages.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        (page, TkAction::RECONFIGURE)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    fn tabs() -> TestHarness<TabView<TextButton<i32>>> {
+        let pages = vec![
+            ("One", TextButton::new_msg("a", 1)),
+            ("Two", TextButton::new_msg("b", 2)),
+        ];
+        TestHarness::with_size(TabView::new(pages), Size(200, 100))
+    }
+
+    // The cached rect of each page
+    fn page_rects(harness: &TestHarness<TabView<TextButton<i32>>>) -> Vec<Option<Rect>> {
+        let tabs = harness.widget();
+        let state = harness.manager_state();
+        (0..tabs.len())
+            .map(|i| state.rect_of(tabs.get(i).unwrap().id()))
+            .collect()
+    }
+
+    #[test]
+    fn rect_of_inactive_page() {
+        let mut harness = tabs();
+        let rect = harness.widget().get(0).unwrap().rect();
+        assert_eq!(page_rects(&harness), vec![Some(rect), None]);
+
+        harness.with_manager(|mgr, w| w.activate(mgr, 1));
+        let rect = harness.widget().get(1).unwrap().rect();
+        assert_eq!(page_rects(&harness), vec![None, Some(rect)]);
+    }
+
+    #[test]
+    fn nav_skips_inactive_page() {
+        // Widgets reached by tabbing from no focus until navigation stops
+        fn nav_order(harness: &mut TestHarness<TabView<TextButton<i32>>>) -> Vec<WidgetId> {
+            harness.with_manager(|mgr, _| mgr.clear_nav_focus());
+            let mut ids = vec![];
+            while harness.with_manager(|mgr, w| mgr.next_nav_focus(w, false)) {
+                ids.extend(harness.with_manager(|mgr, _| mgr.nav_focus()));
+            }
+            ids
+        }
+
+        let mut harness = tabs();
+        let order = nav_order(&mut harness);
+        let tabs = harness.widget();
+        assert!(order.contains(&tabs.get(0).unwrap().id()));
+        assert!(!order.contains(&tabs.get(1).unwrap().id()));
+
+        harness.with_manager(|mgr, w| w.set_active(mgr, 1));
+        let order = nav_order(&mut harness);
+        let tabs = harness.widget();
+        assert!(!order.contains(&tabs.get(0).unwrap().id()));
+        assert!(order.contains(&tabs.get(1).unwrap().id()));
+    }
+}
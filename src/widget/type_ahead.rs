@@ -0,0 +1,58 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Type-ahead find
+
+use std::time::Instant;
+
+use kas::event::Manager;
+
+/// Logic for type-ahead find
+///
+/// Characters typed in quick succession are accumulated into a prefix, which
+/// widgets such as [`ComboBox`](super::ComboBox) and
+/// [`ListView`](super::view::ListView) use to find an item by its text. The
+/// prefix is reset when no character is typed within
+/// [`Manager::type_ahead_timeout`].
+///
+/// Matching is case-insensitive (using a simple lower-case conversion).
+#[derive(Clone, Debug, Default)]
+pub struct TypeAhead {
+    prefix: String,
+    last: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Handle a received character
+    ///
+    /// Returns the prefix to search for, or `None` if `c` does not start a
+    /// search (whitespace is ignored at the start, since Space is used to
+    /// activate widgets).
+    pub fn push(&mut self, mgr: &Manager, c: char) -> Option<&str> {
+        let now = Instant::now();
+        if let Some(last) = self.last {
+            if now.duration_since(last) > mgr.type_ahead_timeout() {
+                self.prefix.clear();
+            }
+        }
+        if self.prefix.is_empty() && c.is_whitespace() {
+            return None;
+        }
+        self.last = Some(now);
+        self.prefix.extend(c.to_lowercase());
+        Some(&self.prefix)
+    }
+
+    /// Reset the prefix
+    pub fn clear(&mut self) {
+        self.prefix.clear();
+        self.last = None;
+    }
+
+    /// Check whether `text` starts with `prefix` (as returned by [`TypeAhead::push`])
+    pub fn matches(prefix: &str, text: &str) -> bool {
+        text.to_lowercase().starts_with(prefix)
+    }
+}
@@ -10,6 +10,7 @@
 #[allow(unused)]
 use kas::event::Manager;
 use kas::event::UpdateHandle;
+use kas::layout::SizeRules;
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::ops::Deref;
@@ -78,6 +79,26 @@ pub trait ListData: Debug {
     fn update_handle(&self) -> Option<UpdateHandle> {
         None
     }
+
+    /// Get all update handles used
+    ///
+    /// Views subscribe to each of these. Adapters over other data (e.g.
+    /// [`FilteredList`](super::FilteredList)) may use their own handle in
+    /// addition to that of the underlying data. The default implementation
+    /// returns [`ListData::update_handle`].
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        self.update_handle().into_iter().collect()
+    }
+
+    /// Notify of an update
+    ///
+    /// Views call this on receiving an update via one of the handles returned
+    /// by [`ListData::update_handles`], before reading the data. Adapters over
+    /// other data (e.g. [`SortedList`](super::SortedList)) may use this to
+    /// refresh cached state. The default implementation does nothing.
+    fn on_update(&mut self, handle: UpdateHandle) {
+        let _ = handle;
+    }
 }
 
 /// Trait for writable data lists
@@ -86,6 +107,47 @@ pub trait ListDataMut: ListData {
     fn set(&self, key: Self::Key, item: Self::Item) -> UpdateHandle;
 }
 
+/// Trait for viewable two-dimensional (cellular) data
+///
+/// Cells are addressed by `(col, row)` coordinates.
+pub trait GridData: Debug {
+    /// Item type
+    type Item: Clone;
+
+    /// Number of columns
+    fn cols(&self) -> usize;
+
+    /// Number of rows
+    ///
+    /// Note: users may assume this and [`GridData::cols`] are `O(1)`.
+    fn rows(&self) -> usize;
+
+    // TODO(gat): add get<'a>(&self) -> Self::ItemRef<'a> and get_mut
+
+    /// Get data for a cell (clone)
+    ///
+    /// Returns `None` if `cell` is out of range or has no data.
+    fn get_cloned(&self, cell: (usize, usize)) -> Option<Self::Item>;
+
+    /// Size requirements of a column
+    ///
+    /// Views size each column to satisfy both this and the size requirements
+    /// of the view widget for the column's first cell. The default
+    /// implementation returns [`SizeRules::EMPTY`], thus columns are sized
+    /// to fit their content.
+    fn column_width(&self, col: usize) -> SizeRules {
+        let _ = col;
+        SizeRules::EMPTY
+    }
+
+    /// Get an update handle, if any is used
+    ///
+    /// Widgets may use this `handle` to call `mgr.update_on_handle(handle, self.id())`.
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        None
+    }
+}
+
 impl<T: Clone + Debug> ListData for [T] {
     type Key = usize;
     type Item = T;
@@ -112,6 +174,23 @@ impl<T: Clone + Debug> ListData for [T] {
     }
 }
 
+/// Rows of cells; the number of columns is that of the longest row
+impl<T: Clone + Debug> GridData for [Vec<T>] {
+    type Item = T;
+
+    fn cols(&self) -> usize {
+        self.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    fn rows(&self) -> usize {
+        (*self).len()
+    }
+
+    fn get_cloned(&self, cell: (usize, usize)) -> Option<Self::Item> {
+        self.get(cell.1).and_then(|row| row.get(cell.0)).cloned()
+    }
+}
+
 // TODO(spec): implement using Deref; for now can't since it "might" conflict
 // with a RefCell impl on a derived type downstream, according to the solver.
 // impl<T: Deref + Debug> SingleData for T
@@ -155,12 +234,36 @@ macro_rules! impl_via_deref {
             fn update_handle(&self) -> Option<UpdateHandle> {
                 self.deref().update_handle()
             }
+            fn update_handles(&self) -> Vec<UpdateHandle> {
+                self.deref().update_handles()
+            }
         }
         impl<$t: ListDataMut + ?Sized> ListDataMut for $derived {
             fn set(&self, key: Self::Key, item: Self::Item) -> UpdateHandle {
                 self.deref().set(key, item)
             }
         }
+
+        impl<$t: GridData + ?Sized> GridData for $derived {
+            type Item = $t::Item;
+
+            fn cols(&self) -> usize {
+                self.deref().cols()
+            }
+            fn rows(&self) -> usize {
+                self.deref().rows()
+            }
+            fn get_cloned(&self, cell: (usize, usize)) -> Option<Self::Item> {
+                self.deref().get_cloned(cell)
+            }
+            fn column_width(&self, col: usize) -> SizeRules {
+                self.deref().column_width(col)
+            }
+
+            fn update_handle(&self) -> Option<UpdateHandle> {
+                self.deref().update_handle()
+            }
+        }
     };
     ($t: ident: $derived:ty, $($dd:ty),+) => {
         impl_via_deref!($t: $derived);
@@ -206,9 +309,36 @@ impl<T: ListData> ListData for RefCell<T> {
     fn update_handle(&self) -> Option<UpdateHandle> {
         self.borrow().update_handle()
     }
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        self.borrow().update_handles()
+    }
+    fn on_update(&mut self, handle: UpdateHandle) {
+        self.get_mut().on_update(handle)
+    }
 }
 impl<T: ListDataMut> ListDataMut for RefCell<T> {
     fn set(&self, key: Self::Key, item: Self::Item) -> UpdateHandle {
         self.borrow().set(key, item)
     }
 }
+
+impl<T: GridData> GridData for RefCell<T> {
+    type Item = T::Item;
+
+    fn cols(&self) -> usize {
+        self.borrow().cols()
+    }
+    fn rows(&self) -> usize {
+        self.borrow().rows()
+    }
+    fn get_cloned(&self, cell: (usize, usize)) -> Option<Self::Item> {
+        self.borrow().get_cloned(cell)
+    }
+    fn column_width(&self, col: usize) -> SizeRules {
+        self.borrow().column_width(col)
+    }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        self.borrow().update_handle()
+    }
+}
@@ -17,6 +17,22 @@ pub trait Filter<T>: Debug + 'static {
     /// Returns true if the given item matches this filter
     // TODO: once Accessor::get returns a reference, this should take item: &T where T: ?Sized
     fn matches(&self, item: T) -> bool;
+
+    /// Returns true if this filter is a refinement of `prev`
+    ///
+    /// A refinement matches only items which are also matched by `prev`
+    /// (for example, a substring filter whose pattern contains the previous
+    /// pattern). This allows [`FilteredList`] to narrow its existing results
+    /// instead of re-scanning all data.
+    ///
+    /// The default implementation returns false, which is always correct.
+    fn is_refinement_of(&self, prev: &Self) -> bool
+    where
+        Self: Sized,
+    {
+        let _ = prev;
+        false
+    }
 }
 impl<'a, T: Clone, X> Filter<&'a T> for X
 where
@@ -25,22 +41,38 @@ where
     fn matches(&self, item: &T) -> bool {
         self.matches(item.clone())
     }
+
+    fn is_refinement_of(&self, prev: &Self) -> bool {
+        Filter::<T>::is_refinement_of(self, prev)
+    }
 }
 
 impl<'a> Filter<&'a str> for &'static str {
     fn matches(&self, item: &str) -> bool {
         item.contains(self)
     }
+
+    fn is_refinement_of(&self, prev: &Self) -> bool {
+        self.contains(prev)
+    }
 }
 impl<'a> Filter<&'a str> for String {
     fn matches(&self, item: &str) -> bool {
         item.contains(self)
     }
+
+    fn is_refinement_of(&self, prev: &Self) -> bool {
+        self.contains(prev.as_str())
+    }
 }
 impl Filter<String> for String {
     fn matches(&self, item: String) -> bool {
         item.contains(self)
     }
+
+    fn is_refinement_of(&self, prev: &Self) -> bool {
+        self.contains(prev.as_str())
+    }
 }
 
 /// Case-insensitive string matcher
@@ -61,21 +93,35 @@ impl<'a> Filter<&'a str> for SimpleCaseInsensitiveFilter {
     fn matches(&self, item: &str) -> bool {
         item.to_owned().to_uppercase().contains(&self.0)
     }
+
+    fn is_refinement_of(&self, prev: &Self) -> bool {
+        self.0.contains(&prev.0)
+    }
 }
 impl Filter<String> for SimpleCaseInsensitiveFilter {
     fn matches(&self, item: String) -> bool {
         item.to_uppercase().contains(&self.0)
     }
+
+    fn is_refinement_of(&self, prev: &Self) -> bool {
+        self.0.contains(&prev.0)
+    }
 }
 
 /// Filter accessor over another accessor
 ///
-/// Warning: the underlying data may have a separate update handle, and handles
-/// are not currently transitive. That is, `FilterList`'s update handle is not
-/// triggered by changes to the underlying data list.
+/// This has its own update handle, triggered when the filter changes. Views
+/// also subscribe to the underlying data's update handle, if any, and re-apply
+/// the filter when this is triggered (see [`ListData::update_handles`]).
+/// Otherwise, after modifying the data, call [`FilteredList::refresh`] and
+/// trigger the returned handle.
 ///
 /// Warning: this implementation is `O(n)` where `n = data.len()` and not well
-/// optimised, thus is expected to be slow on large data lists.
+/// optimised, thus is expected to be slow on large data lists. Where a new
+/// filter is a refinement of the previous one (see
+/// [`Filter::is_refinement_of`]) and the data has not changed since the last
+/// full scan, [`FilteredList::set_filter`] only re-tests items currently
+/// matched.
 ///
 /// Note: the key and item types are the same as those in the underlying list,
 /// thus one can retrieve values from the underlying list directly (without
@@ -88,6 +134,7 @@ pub struct FilteredList<T: ListData, F: Filter<T::Item>> {
     pub data: T,
     filter: F,
     view: Vec<T::Key>,
+    scanned_len: usize,
     update: UpdateHandle,
 }
 
@@ -97,14 +144,11 @@ impl<T: ListData, F: Filter<T::Item>> FilteredList<T, F> {
     pub fn new(data: T, filter: F) -> Self {
         let len = data.len().cast();
         let view = Vec::with_capacity(len);
-        // TODO: using a separate update handle allows notification of the
-        // filter view update without notifying users of the underlying list,
-        // *but* update of the list should also imply update of the view.
-        // Can we make one update handle imply another?
         let mut s = FilteredList {
             data,
             filter,
             view,
+            scanned_len: 0,
             update: UpdateHandle::new(),
         };
         let _ = s.refresh();
@@ -119,7 +163,9 @@ impl<T: ListData, F: Filter<T::Item>> FilteredList<T, F> {
     /// An update should be triggered using the returned handle.
     pub fn refresh(&mut self) -> UpdateHandle {
         self.view.clear();
-        for (key, item) in self.data.iter_vec(usize::MAX) {
+        let items = self.data.iter_vec(usize::MAX);
+        self.scanned_len = items.len();
+        for (key, item) in items {
             if self.filter.matches(item) {
                 self.view.push(key);
             }
@@ -131,9 +177,26 @@ impl<T: ListData, F: Filter<T::Item>> FilteredList<T, F> {
     ///
     /// An update should be triggered using the returned handle.
     /// See [`FilteredList::refresh`].
+    ///
+    /// If `filter` is a refinement of the current filter, only items in the
+    /// current view are re-tested. This is skipped (re-scanning all data)
+    /// where the data's length changed since the last full scan; changes
+    /// which do not affect the length must be followed by
+    /// [`FilteredList::refresh`] or an update (see [`ListData::on_update`]).
     pub fn set_filter(&mut self, filter: F) -> UpdateHandle {
+        let refine = Filter::<T::Item>::is_refinement_of(&filter, &self.filter);
         self.filter = filter;
-        self.refresh()
+        if !refine || self.data.len() != self.scanned_len {
+            return self.refresh();
+        }
+
+        let (data, filter) = (&self.data, &self.filter);
+        self.view.retain(|key| {
+            data.get_cloned(key)
+                .map(|item| filter.matches(item))
+                .unwrap_or(false)
+        });
+        self.update
     }
 }
 
@@ -168,4 +231,131 @@ impl<T: ListData, F: Filter<T::Item>> ListData for FilteredList<T, F> {
     fn update_handle(&self) -> Option<UpdateHandle> {
         Some(self.update)
     }
+
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        let mut handles = self.data.update_handles();
+        handles.push(self.update);
+        handles
+    }
+
+    fn on_update(&mut self, handle: UpdateHandle) {
+        // Our own handle is only triggered after the view has been updated
+        // (by refresh or set_filter); re-scan only on update of the data
+        if handle != self.update {
+            self.data.on_update(handle);
+            let _ = self.refresh();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dir::Down;
+    use crate::event::Event;
+    use crate::geom::Size;
+    use crate::testing::TestHarness;
+    use crate::widget::view::ListView;
+    use crate::WidgetCore;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Clone, Debug)]
+    struct Data(Vec<&'static str>, Option<UpdateHandle>);
+
+    impl ListData for Data {
+        type Key = usize;
+        type Item = &'static str;
+
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+        fn get_cloned(&self, key: &usize) -> Option<&'static str> {
+            self.0.get_cloned(key)
+        }
+        fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(usize, &'static str)> {
+            self.0.iter_vec_from(start, limit)
+        }
+        fn update_handle(&self) -> Option<UpdateHandle> {
+            self.1
+        }
+    }
+
+    fn keys<T: ListData<Key = usize>>(list: &T) -> Vec<usize> {
+        list.iter_vec(usize::MAX)
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect()
+    }
+
+    #[test]
+    fn refine() {
+        let mut list = FilteredList::new(Data(vec!["ab", "abc", "b"], None), "a");
+        assert_eq!(keys(&list), vec![0, 1]);
+        let _ = list.set_filter("abc");
+        assert_eq!(keys(&list), vec![1]);
+    }
+
+    #[test]
+    fn refine_after_insertion() {
+        let mut list = FilteredList::new(Data(vec!["ab", "b"], None), "a");
+        list.data.0.push("abc");
+        let _ = list.set_filter("ab");
+        assert_eq!(keys(&list), vec![0, 2]);
+    }
+
+    #[test]
+    fn refine_after_update() {
+        let handle = UpdateHandle::new();
+        let mut list = FilteredList::new(Data(vec!["ab", "b"], Some(handle)), "a");
+        assert!(list.update_handles().contains(&handle));
+        assert_ne!(list.update_handle(), Some(handle));
+
+        list.data.0[1] = "abc";
+        list.on_update(handle);
+        let _ = list.set_filter("ab");
+        assert_eq!(keys(&list), vec![0, 1]);
+    }
+
+    // Records the items tested
+    #[derive(Debug)]
+    struct Recorded(&'static str, Rc<RefCell<Vec<&'static str>>>);
+
+    impl Filter<&'static str> for Recorded {
+        fn matches(&self, item: &'static str) -> bool {
+            self.1.borrow_mut().push(item);
+            item.contains(self.0)
+        }
+
+        fn is_refinement_of(&self, prev: &Self) -> bool {
+            self.0.contains(prev.0)
+        }
+    }
+
+    #[test]
+    fn refine_through_view() {
+        let tested = Rc::new(RefCell::new(vec![]));
+        let data_handle = UpdateHandle::new();
+        let data = Data(vec!["ab", "abc", "b", "c"], Some(data_handle));
+        let list = FilteredList::new(data, Recorded("a", tested.clone()));
+        let view: ListView<Down, _> = ListView::new(list);
+        let mut harness = TestHarness::with_size(view, Size(100, 100));
+        let id = harness.widget().id();
+
+        // Refining the filter only re-tests items previously matched
+        tested.borrow_mut().clear();
+        let filter = Recorded("ab", tested.clone());
+        let handle = harness.widget_mut().data_mut().set_filter(filter);
+        assert_ne!(handle, data_handle);
+        let _ = harness.send(id, Event::HandleUpdate { handle, payload: 0 });
+        assert!(!tested.borrow().contains(&"b"));
+        assert!(!tested.borrow().contains(&"c"));
+        assert_eq!(keys(harness.widget().data()), vec![0, 1]);
+
+        // Update of the data re-applies the filter
+        harness.widget_mut().data_mut().data.0.push("abd");
+        let handle = data_handle;
+        let _ = harness.send(id, Event::HandleUpdate { handle, payload: 0 });
+        assert_eq!(keys(harness.widget().data()), vec![0, 1, 4]);
+    }
 }
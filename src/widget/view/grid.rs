@@ -0,0 +1,760 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Grid view widget
+
+use super::{DefaultView, GridData, ListMsg, SelectionMode, ViewWidget};
+use kas::event::{Command, CursorIcon, GrabMode, PressSource};
+use kas::layout::solve_size_rules;
+use kas::prelude::*;
+use kas::widget::{ScrollComponent, ScrollWidget};
+use linear_map::set::LinearSet;
+use log::{debug, trace};
+use std::convert::TryFrom;
+use std::time::Instant;
+
+#[derive(Clone, Debug, Default)]
+struct WidgetData<W> {
+    cell: Option<(usize, usize)>,
+    widget: W,
+}
+
+/// Grid view widget
+///
+/// This is a scrollable view over [`GridData`]. Columns are sized
+/// independently (see [`GridData::column_width`]); all rows have the same
+/// height. Only enough child widgets to cover the visible region are
+/// allocated; these are re-assigned to cells on scrolling.
+///
+/// Messages use [`ListMsg`] with keys of the form `(col, row)`.
+#[derive(Clone, Debug, Widget)]
+#[handler(send=noauto, msg=ListMsg<(usize, usize), <W as Handler>::Msg>)]
+#[widget(children=noauto, config=noauto)]
+pub struct GridView<
+    T: GridData + 'static,
+    W: ViewWidget<T::Item> = <<T as GridData>::Item as DefaultView>::Widget,
+> where
+    T::Item: Default,
+{
+    first_id: WidgetId,
+    #[widget_core]
+    core: CoreData,
+    offset: Offset,
+    frame_size: Size,
+    data: T,
+    // Widgets for the visible region; index is row_slot * pool.0 + col_slot
+    widgets: Vec<WidgetData<W>>,
+    pool: (usize, usize),
+    align_hints: AlignHints,
+    ideal_visible: (i32, i32),
+    inter_margin: Size,
+    col_rules: Vec<SizeRules>,
+    // Column offsets (len = cols + 1; includes margins)
+    col_offsets: Vec<i32>,
+    row_size_min: i32,
+    row_size_ideal: i32,
+    row_height: i32,
+    // Data dimensions and column widths at the last size_rules call
+    data_dims: (usize, usize),
+    data_col_widths: Vec<SizeRules>,
+    scroll: ScrollComponent,
+    sel_mode: SelectionMode,
+    selection: LinearSet<(usize, usize)>,
+    selection_msgs: bool,
+    press_event: Option<PressSource>,
+    press_target: Option<(usize, usize)>,
+}
+
+impl<T: GridData, W: ViewWidget<T::Item>> GridView<T, W>
+where
+    T::Item: Default,
+{
+    /// Construct a new instance
+    pub fn new(data: T) -> Self {
+        GridView {
+            first_id: Default::default(),
+            core: Default::default(),
+            offset: Default::default(),
+            frame_size: Default::default(),
+            data,
+            widgets: Default::default(),
+            pool: (0, 0),
+            align_hints: Default::default(),
+            ideal_visible: (3, 5),
+            inter_margin: Size::ZERO,
+            col_rules: vec![],
+            col_offsets: vec![0],
+            row_size_min: 0,
+            row_size_ideal: 0,
+            row_height: 0,
+            data_dims: (0, 0),
+            data_col_widths: vec![],
+            scroll: Default::default(),
+            sel_mode: SelectionMode::None,
+            selection: Default::default(),
+            selection_msgs: false,
+            press_event: None,
+            press_target: None,
+        }
+    }
+
+    /// Access the stored data
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Mutably access the stored data
+    ///
+    /// It may be necessary to use [`GridView::update_view`] to update the view of this data.
+    pub fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+
+    /// Get the current selection mode
+    pub fn selection_mode(&self) -> SelectionMode {
+        self.sel_mode
+    }
+    /// Set the current selection mode
+    pub fn set_selection_mode(&mut self, mode: SelectionMode) -> TkAction {
+        self.sel_mode = mode;
+        match mode {
+            SelectionMode::None if !self.selection.is_empty() => {
+                self.selection.clear();
+                TkAction::REDRAW
+            }
+            SelectionMode::Single if self.selection.len() > 1 => {
+                if let Some(first) = self.selection.iter().next().cloned() {
+                    self.selection.retain(|item| *item == first);
+                }
+                TkAction::REDRAW
+            }
+            _ => TkAction::empty(),
+        }
+    }
+    /// Set the selection mode (inline)
+    pub fn with_selection_mode(mut self, mode: SelectionMode) -> Self {
+        let _ = self.set_selection_mode(mode);
+        self
+    }
+
+    /// Enable or disable reporting of the complete selection
+    ///
+    /// See [`ListView::with_selection_msgs`](super::ListView::with_selection_msgs).
+    pub fn with_selection_msgs(mut self, enable: bool) -> Self {
+        self.selection_msgs = enable;
+        self
+    }
+
+    /// Get the set of selected cells
+    pub fn selection(&self) -> &LinearSet<(usize, usize)> {
+        &self.selection
+    }
+
+    /// Check whether a cell is selected
+    pub fn is_selected(&self, cell: (usize, usize)) -> bool {
+        self.selection.contains(&cell)
+    }
+
+    /// Clear all selected cells
+    ///
+    /// Does not send [`ListMsg`] responses.
+    pub fn clear_selected(&mut self) {
+        self.selection.clear();
+    }
+
+    /// Set the preferred number of columns and rows visible (inline)
+    ///
+    /// This affects the (ideal) size request but not the minimum size.
+    pub fn with_num_visible(mut self, cols: i32, rows: i32) -> Self {
+        self.ideal_visible = (cols, rows);
+        self
+    }
+
+    /// Manually trigger an update to handle changed data
+    pub fn update_view(&mut self, mgr: &mut Manager) {
+        for w in &mut self.widgets {
+            w.cell = None;
+        }
+        let cols = self.data.cols();
+        let resize = (cols, self.data.rows()) != self.data_dims
+            || (0..cols).any(|col| self.data.column_width(col) != self.data_col_widths[col]);
+        if resize {
+            trace!("update_view triggers RESIZE");
+            *mgr |= TkAction::RESIZE;
+        } else {
+            self.update_widgets(mgr);
+            mgr.redraw(self.id());
+        }
+    }
+
+    // Row height used when the view (excluding frame) has the given height
+    fn row_height_for(&self, view_height: i32) -> i32 {
+        if view_height >= self.ideal_visible.1 * self.row_size_ideal {
+            self.row_size_ideal
+        } else {
+            self.row_size_min
+        }
+    }
+
+    fn row_skip(&self) -> i32 {
+        (self.row_height + self.inter_margin.1).max(1)
+    }
+
+    // Index of the column at data-space position x
+    fn col_index_at(&self, x: i32) -> usize {
+        let len = self.col_offsets.len().saturating_sub(1);
+        let i = match self.col_offsets.binary_search(&x) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+        i.min(len.saturating_sub(1))
+    }
+
+    // Measure column size rules (horizontal axis)
+    fn measure_cols(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        // We measure the first cell of each column using the first pooled
+        // widget; clearing its cell causes update_widgets to re-assign it.
+        let w = &mut self.widgets[0];
+        w.cell = None;
+        let mut rules = w.widget.size_rules(size_handle, axis);
+        let cols = self.data.cols();
+        self.data_dims = (cols, self.data.rows());
+        self.col_rules.clear();
+        self.col_rules.reserve(cols);
+        self.data_col_widths.clear();
+        self.data_col_widths.reserve(cols);
+        for col in 0..cols {
+            let item = self.data.get_cloned((col, 0)).unwrap_or_default();
+            let _ = w.widget.set(item);
+            let r = w.widget.size_rules(size_handle, axis);
+            let width = self.data.column_width(col);
+            self.data_col_widths.push(width);
+            self.col_rules.push(r.max(width));
+        }
+        if let Some(first) = self.col_rules.first() {
+            rules = *first;
+        }
+        rules
+    }
+
+    fn update_widgets(&mut self, mgr: &mut Manager) {
+        let time = Instant::now();
+        let (cols, rows) = (self.data.cols(), self.data.rows());
+        let pool = self.pool;
+        let offset = self.scroll_offset();
+        let first_col = self.col_index_at(offset.0);
+        let first_row = usize::conv(offset.1.max(0) / self.row_skip());
+        let last_col = cols.min(first_col + pool.0);
+        let last_row = rows.min(first_row + pool.1);
+
+        let pos_start = self.core.rect.pos + self.offset;
+        let row_skip = self.row_skip();
+        let mut used = vec![false; self.widgets.len()];
+        let mut action = TkAction::empty();
+        for row in first_row..last_row {
+            for col in first_col..last_col {
+                let slot = (row % pool.1) * pool.0 + (col % pool.0);
+                used[slot] = true;
+                let cell = Some((col, row));
+                let w = &mut self.widgets[slot];
+                if cell != w.cell {
+                    w.cell = cell;
+                    let item = self.data.get_cloned((col, row)).unwrap_or_default();
+                    action |= w.widget.set(item);
+                }
+                let x = self.col_offsets[col];
+                let width = self.col_offsets[col + 1] - x - self.inter_margin.0;
+                let y = row_skip * i32::conv(row);
+                let rect = Rect::new(pos_start + Offset(x, y), Size(width, self.row_height));
+                w.widget.set_rect(mgr, rect, self.align_hints);
+            }
+        }
+        for (w, used) in self.widgets.iter_mut().zip(used) {
+            if !used {
+                w.cell = None;
+            }
+        }
+        mgr.update_rect_cache(&*self);
+        *mgr |= action;
+        let dur = (Instant::now() - time).as_micros();
+        trace!("GridView::update_widgets completed in {}μs", dur);
+    }
+
+    fn active_widgets<'a>(&'a self) -> impl Iterator<Item = &'a WidgetData<W>> + 'a {
+        self.widgets.iter().filter(|w| w.cell.is_some())
+    }
+}
+
+impl<T: GridData + 'static, W: ViewWidget<T::Item>> ScrollWidget for GridView<T, W>
+where
+    T::Item: Default,
+{
+    fn scroll_axes(&self, size: Size) -> (bool, bool) {
+        let view_size = size - self.frame_size;
+        let margin = self.inter_margin;
+        let width: i32 = (self.col_rules.iter())
+            .map(|rules| rules.ideal_size() + margin.0)
+            .sum();
+        let rows = i32::conv(self.data.rows());
+        let height = (self.row_height_for(view_size.1) + margin.1) * rows;
+        (
+            width - margin.0 > view_size.0,
+            height - margin.1 > view_size.1,
+        )
+    }
+
+    #[inline]
+    fn max_scroll_offset(&self) -> Offset {
+        self.scroll.max_offset()
+    }
+
+    #[inline]
+    fn scroll_offset(&self) -> Offset {
+        self.scroll.offset()
+    }
+
+    #[inline]
+    fn set_scroll_offset(&mut self, mgr: &mut Manager, offset: Offset) -> Offset {
+        *mgr |= self.scroll.set_offset(offset);
+        self.update_widgets(mgr);
+        self.scroll.offset()
+    }
+}
+
+impl<T: GridData + 'static, W: ViewWidget<T::Item>> WidgetChildren for GridView<T, W>
+where
+    T::Item: Default,
+{
+    #[inline]
+    fn first_id(&self) -> WidgetId {
+        self.first_id
+    }
+    fn record_first_id(&mut self, id: WidgetId) {
+        self.first_id = id;
+    }
+    #[inline]
+    fn num_children(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+        self.widgets.get(index).map(|w| w.widget.as_widget())
+    }
+    #[inline]
+    fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+        self.widgets
+            .get_mut(index)
+            .map(|w| w.widget.as_widget_mut())
+    }
+}
+
+impl<T: GridData + 'static, W: ViewWidget<T::Item>> WidgetConfig for GridView<T, W>
+where
+    T::Item: Default,
+{
+    fn configure(&mut self, mgr: &mut Manager) {
+        if let Some(handle) = self.data.update_handle() {
+            mgr.update_on_handle(handle, self.id());
+        }
+        mgr.register_nav_fallback(self.id());
+    }
+}
+
+impl<T: GridData + 'static, W: ViewWidget<T::Item>> Layout for GridView<T, W>
+where
+    T::Item: Default,
+{
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        // We use an invisible frame for highlighting selections, drawing into the margin
+        let inner_margin = size_handle.inner_margin().extract(axis);
+        let frame = FrameRules::new_sym(0, inner_margin, (0, 0));
+
+        if self.widgets.is_empty() {
+            let item = self.data.get_cloned((0, 0)).unwrap_or_default();
+            let cell = None;
+            let widget = W::new(item);
+            self.widgets.push(WidgetData { cell, widget });
+            self.pool = (1, 1);
+        }
+
+        let mut rules = if axis.is_horizontal() {
+            self.measure_cols(size_handle, axis)
+        } else {
+            self.widgets[0].widget.size_rules(size_handle, axis)
+        };
+        let m = rules.margins_i32();
+        let margin = (m.0 + m.1).max(inner_margin);
+        self.inter_margin.set_component(axis, margin);
+
+        if axis.is_horizontal() {
+            let n = self.col_rules.len().min(usize::conv(self.ideal_visible.0));
+            let ideal = (self.col_rules[..n].iter())
+                .map(|rules| rules.ideal_size() + margin)
+                .sum::<i32>();
+            let ideal = (ideal - margin).max(rules.min_size());
+            rules = SizeRules::new(rules.min_size(), ideal, rules.margins(), rules.stretch());
+        } else {
+            self.row_size_min = rules.min_size();
+            self.row_size_ideal = rules.ideal_size();
+            rules.multiply_with_margin(2, self.ideal_visible.1);
+        }
+        rules.set_stretch(rules.stretch().max(StretchPolicy::HighUtility));
+
+        let (rules, offset, size) = frame.surround(rules);
+        self.offset.set_component(axis, offset);
+        self.frame_size.set_component(axis, size);
+        rules
+    }
+
+    fn set_rect(&mut self, mgr: &mut Manager, rect: Rect, mut align: AlignHints) {
+        self.core.rect = rect;
+        let view_size = rect.size - self.frame_size;
+        let margin = self.inter_margin;
+
+        // Columns are given their ideal width
+        self.col_offsets.clear();
+        self.col_offsets.push(0);
+        let mut pos = 0;
+        let mut min_skip = i32::MAX;
+        for rules in &self.col_rules {
+            let skip = rules.ideal_size() + margin.0;
+            min_skip = min_skip.min(skip);
+            pos += skip;
+            self.col_offsets.push(pos);
+        }
+        let min_skip = min_skip.max(1);
+
+        self.row_height = self.row_height_for(view_size.1);
+        let row_skip = self.row_skip();
+        let rows = self.data.rows();
+        let rows32 = i32::try_from(rows).unwrap();
+        let content_size = Size(
+            (pos - margin.0).max(0),
+            (row_skip * rows32 - margin.1).max(0),
+        );
+
+        align.horiz = None;
+        align.vert = None;
+        self.align_hints = align;
+
+        // Number of columns and rows which may be partially visible at once
+        let cols = self.data.cols();
+        let num_cols = usize::conv((view_size.0 + min_skip - 1) / min_skip + 1).min(cols);
+        let num_rows = usize::conv((view_size.1 + row_skip - 1) / row_skip + 1).min(rows);
+        let pool = (num_cols.max(self.pool.0), num_rows.max(self.pool.1));
+        if pool != self.pool {
+            debug!(
+                "allocating widgets (old = {:?}, new = {:?})",
+                self.pool, pool
+            );
+            *mgr |= TkAction::RECONFIGURE;
+            let num = pool.0 * pool.1;
+            let row_height = self.row_height;
+            self.widgets.clear();
+            self.widgets.reserve(num);
+            mgr.size_handle(|size_handle| {
+                for _ in 0..num {
+                    let mut widget = W::default();
+                    solve_size_rules(&mut widget, size_handle, None, Some(row_height));
+                    self.widgets.push(WidgetData { cell: None, widget });
+                }
+            });
+            self.pool = pool;
+        }
+
+        *mgr |= self.scroll.set_sizes(rect.size, content_size);
+        self.update_widgets(mgr);
+    }
+
+    fn spatial_range(&self) -> (usize, usize) {
+        let last = self.num_children().wrapping_sub(1);
+        (0, last)
+    }
+
+    fn is_child_active(&self, index: usize) -> bool {
+        self.widgets
+            .get(index)
+            .map(|w| w.cell.is_some())
+            .unwrap_or(false)
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.rect().contains(coord) {
+            return None;
+        }
+
+        let coord = coord + self.scroll.offset();
+        for child in self.active_widgets() {
+            if let Some(id) = child.widget.find_id(coord) {
+                return Some(id);
+            }
+        }
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, disabled: bool) {
+        let disabled = disabled || self.is_disabled();
+        let offset = self.scroll_offset();
+        use kas::draw::ClipRegion::Scroll;
+        draw_handle.clip_region(self.core.rect, offset, Scroll, &mut |draw_handle| {
+            for child in self.active_widgets() {
+                child.widget.draw(draw_handle, mgr, disabled);
+                if let Some(cell) = child.cell {
+                    if self.is_selected(cell) {
+                        draw_handle.selection_box(child.widget.rect());
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl<T: GridData + 'static, W: ViewWidget<T::Item>> SendEvent for GridView<T, W>
+where
+    T::Item: Default,
+{
+    fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+
+        let event = if id < self.id() {
+            let response = 'outer: loop {
+                for child in &mut self.widgets {
+                    if id <= child.widget.id() {
+                        let event = self.scroll.offset_event(event);
+                        break 'outer (child.cell, child.widget.send(mgr, id, event));
+                    }
+                }
+                debug_assert!(false, "SendEvent::send: bad WidgetId");
+                return Response::Unhandled(event);
+            };
+            match response {
+                (cell, Response::Unhandled(event)) => {
+                    if let Event::PressStart { source, coord, .. } = event {
+                        if source.is_primary() {
+                            // We request a grab with our ID, hence the
+                            // PressMove/PressEnd events are matched below.
+                            if mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None) {
+                                self.press_event = Some(source);
+                                self.press_target = cell;
+                            }
+                            return Response::None;
+                        }
+                    }
+                    event
+                }
+                (_, Response::Focus(rect)) => {
+                    let (rect, action) = self.scroll.focus_rect(rect, self.core.rect);
+                    *mgr |= action;
+                    self.update_widgets(mgr);
+                    return Response::Focus(rect);
+                }
+                (cell, r) => {
+                    return match Response::try_from(r) {
+                        Ok(r) => r,
+                        Err(msg) => {
+                            if let Some(cell) = cell {
+                                Response::Msg(ListMsg::Child(cell, msg))
+                            } else {
+                                log::warn!("GridView: response from widget with no cell");
+                                Response::None
+                            }
+                        }
+                    };
+                }
+            }
+        } else {
+            debug_assert!(id == self.id(), "SendEvent::send: bad WidgetId");
+            match event {
+                Event::HandleUpdate { .. } => {
+                    self.update_view(mgr);
+                    return Response::None;
+                }
+                Event::PressMove { source, .. } if self.press_event == Some(source) => {
+                    self.press_event = None;
+                    mgr.update_grab_cursor(self.id(), CursorIcon::Grabbing);
+                    event // fall through to scroll handler
+                }
+                Event::PressEnd { source, .. } if self.press_event == Some(source) => {
+                    self.press_event = None;
+                    let msg = match (self.sel_mode, self.press_target) {
+                        (SelectionMode::None, _) | (_, None) => None,
+                        (SelectionMode::Single, Some(cell)) => {
+                            self.selection.clear();
+                            self.selection.insert(cell);
+                            Some(ListMsg::Select(cell))
+                        }
+                        (SelectionMode::Multiple, Some(cell)) => {
+                            if self.selection.remove(&cell) {
+                                Some(ListMsg::Deselect(cell))
+                            } else {
+                                self.selection.insert(cell);
+                                Some(ListMsg::Select(cell))
+                            }
+                        }
+                    };
+                    if msg.is_some() {
+                        mgr.redraw(self.id());
+                    }
+                    return match msg {
+                        Some(_) if self.selection_msgs => {
+                            ListMsg::Selection(self.selection.clone()).into()
+                        }
+                        Some(msg) => msg.into(),
+                        None => Response::None,
+                    };
+                }
+                event => event,
+            }
+        };
+
+        if let Event::Command(Command::SelectAll, _) = event {
+            if let SelectionMode::Multiple = self.sel_mode {
+                let (cols, rows) = (self.data.cols(), self.data.rows());
+                self.selection.clear();
+                for row in 0..rows {
+                    self.selection.extend((0..cols).map(|col| (col, row)));
+                }
+                mgr.redraw(self.id());
+                return ListMsg::Selection(self.selection.clone()).into();
+            }
+        }
+
+        let id = self.id();
+        let (action, response) =
+            self.scroll
+                .scroll_by_event(event, self.core.rect.size, |source, _, coord| {
+                    if source.is_primary() {
+                        let icon = Some(CursorIcon::Grabbing);
+                        mgr.request_grab(id, source, coord, GrabMode::Grab, icon);
+                    }
+                });
+        if !action.is_empty() {
+            *mgr |= action;
+            self.update_widgets(mgr);
+            Response::Focus(self.rect())
+        } else {
+            response.void_into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::class::HasStr;
+    use crate::testing::TestHarness;
+
+    #[derive(Debug)]
+    struct Data(Vec<Vec<&'static str>>, Vec<SizeRules>);
+
+    impl GridData for Data {
+        type Item = &'static str;
+
+        fn cols(&self) -> usize {
+            self.0[..].cols()
+        }
+        fn rows(&self) -> usize {
+            self.0[..].rows()
+        }
+        fn get_cloned(&self, cell: (usize, usize)) -> Option<&'static str> {
+            self.0[..].get_cloned(cell)
+        }
+        fn column_width(&self, col: usize) -> SizeRules {
+            self.1.get(col).cloned().unwrap_or(SizeRules::EMPTY)
+        }
+    }
+
+    // Width of each column, excluding margins
+    fn col_widths(view: &GridView<Data>) -> Vec<i32> {
+        (view.col_offsets.windows(2))
+            .map(|w| w[1] - w[0] - view.inter_margin.0)
+            .collect()
+    }
+
+    // Text of each assigned widget, by cell
+    fn cells(view: &GridView<Data>) -> Vec<((usize, usize), String)> {
+        let mut cells: Vec<_> = (view.widgets.iter())
+            .filter_map(|w| w.cell.map(|cell| (cell, w.widget.get_string())))
+            .collect();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn column_widths() {
+        let rows = vec![vec!["a", "a much wider column", ""], vec!["b", "c", ""]];
+        let widths = vec![
+            SizeRules::EMPTY,
+            SizeRules::EMPTY,
+            SizeRules::fixed(100, (0, 0)),
+        ];
+        let harness = TestHarness::with_size(GridView::new(Data(rows, widths)), Size(400, 200));
+        let widths = col_widths(harness.widget());
+        assert_eq!(widths.len(), 3);
+        assert!(widths[0] < widths[1]);
+        assert_eq!(widths[2], 100);
+    }
+
+    #[test]
+    fn data_mapping() {
+        const DIGITS: [&str; 5] = ["0", "1", "2", "3", "4"];
+        let rows = (0..20).map(|i| vec![DIGITS[i % 5], "y"]).collect();
+        let mut harness = TestHarness::with_size(GridView::new(Data(rows, vec![])), Size(200, 100));
+
+        // Checks that each assigned widget shows its cell's data
+        fn check(visible: Vec<((usize, usize), String)>) {
+            assert!(!visible.is_empty());
+            for ((col, row), text) in visible {
+                let expected = if col == 0 { DIGITS[row % 5] } else { "y" };
+                assert_eq!(text, expected);
+            }
+        }
+
+        check(cells(harness.widget()));
+
+        harness.with_manager(|mgr, w| {
+            let offset = w.max_scroll_offset();
+            let _ = w.set_scroll_offset(mgr, offset);
+        });
+        let visible = cells(harness.widget());
+        assert!(visible.iter().any(|((_, row), _)| *row == 19));
+        check(visible);
+    }
+
+    #[test]
+    fn update_view_resize() {
+        let rows = vec![vec!["a", "b"], vec!["c", "d"]];
+        let view = GridView::new(Data(rows, vec![]));
+        let mut harness = TestHarness::with_size(view, Size(200, 100));
+        harness.take_action();
+
+        // Changed cell content: redraw only
+        harness.with_manager(|mgr, w| {
+            w.data.0[1][0] = "e";
+            w.update_view(mgr);
+        });
+        let action = harness.take_action();
+        assert!(action.contains(TkAction::REDRAW));
+        assert!(!action.contains(TkAction::RESIZE));
+        assert!(cells(harness.widget()).contains(&((0, 1), "e".to_string())));
+
+        // Changed column width: resize
+        harness.with_manager(|mgr, w| {
+            w.data.1 = vec![SizeRules::fixed(100, (0, 0))];
+            w.update_view(mgr);
+        });
+        assert!(harness.take_action().contains(TkAction::RESIZE));
+        harness.resize(Size(200, 100));
+
+        // Changed dimensions: resize
+        harness.with_manager(|mgr, w| {
+            w.data.0.push(vec!["f", "g"]);
+            w.update_view(mgr);
+        });
+        assert!(harness.take_action().contains(TkAction::RESIZE));
+    }
+}
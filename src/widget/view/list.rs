@@ -6,13 +6,15 @@
 //! List view widget
 
 use super::{DefaultView, ListData, ViewWidget};
-use kas::event::{CursorIcon, GrabMode, PressSource};
+use kas::draw::TextClass;
+use kas::event::{Command, CursorIcon, GrabMode, MouseButton, PressSource};
 use kas::layout::solve_size_rules;
 use kas::prelude::*;
-use kas::widget::{ScrollComponent, ScrollWidget};
+use kas::widget::{ScrollComponent, ScrollWidget, TypeAhead};
 use linear_map::set::LinearSet;
 use log::{debug, trace};
 use std::convert::TryFrom;
+use std::ops::RangeInclusive;
 use std::time::Instant;
 
 /// Selection mode used by [`ListView`]
@@ -41,6 +43,22 @@ struct WidgetData<K, W> {
 pub enum ListMsg<K, M> {
     Select(K),
     Deselect(K),
+    /// The complete selection, after a change
+    ///
+    /// This replaces `Select` and `Deselect` messages when enabled via
+    /// [`ListView::with_selection_msgs`], and is always used after
+    /// select-all (Ctrl+A).
+    Selection(LinearSet<K>),
+    /// Context action requested on an item
+    ///
+    /// This is sent on a secondary-button (right) click or touch long-press
+    /// over an item, e.g. to open a context menu at `coord` (in window
+    /// coordinates). If the item was not selected, the selection is first
+    /// changed to this item (no other message is sent for this change).
+    Context {
+        key: K,
+        coord: Coord,
+    },
     Child(K, M),
 }
 
@@ -74,6 +92,7 @@ pub struct ListView<
     frame_size: Size,
     data: T,
     widgets: Vec<WidgetData<T::Key, W>>,
+    data_len: u32,
     cur_len: u32,
     direction: D,
     align_hints: AlignHints,
@@ -83,12 +102,31 @@ pub struct ListView<
     child_inter_margin: i32,
     child_skip: i32,
     child_size: Size,
+    var_size: bool,
+    // Item offsets in variable-size mode (len = data_len + 1; includes margins)
+    var_offsets: Vec<i32>,
+    // Cross size and child size rules for which var_offsets is valid
+    var_basis: (i32, i32, i32),
+    // Widget used to measure items in variable-size mode
+    var_probe: Option<W>,
+    empty_text: Option<Text<String>>,
+    // Loading overlay: progress value if shown (None if indeterminate),
+    // progress-bar size and spinner size and phase
+    loading: Option<Option<f32>>,
+    loading_size: Size,
+    spinner_size: Size,
+    spinner_phase: f32,
     scroll: ScrollComponent,
     sel_mode: SelectionMode,
     // TODO(opt): replace selection list with RangeOrSet type?
     selection: LinearSet<T::Key>,
+    selection_msgs: bool,
     press_event: Option<PressSource>,
     press_target: Option<T::Key>,
+    press_coord: Coord,
+    long_press: Option<Instant>,
+    item_text: Option<fn(T::Item) -> String>,
+    type_ahead: TypeAhead,
 }
 
 impl<D: Directional + Default, T: ListData, W: ViewWidget<T::Item>> ListView<D, T, W>
@@ -108,6 +146,7 @@ where
             frame_size: Default::default(),
             data,
             widgets: Default::default(),
+            data_len: 0,
             cur_len: 0,
             direction: Default::default(),
             align_hints: Default::default(),
@@ -117,11 +156,25 @@ where
             child_inter_margin: 0,
             child_skip: 0,
             child_size: Size::ZERO,
+            var_size: false,
+            var_offsets: vec![],
+            var_basis: (0, 0, 0),
+            var_probe: None,
+            empty_text: None,
+            loading: None,
+            loading_size: Size::ZERO,
+            spinner_size: Size::ZERO,
+            spinner_phase: 0.0,
             scroll: Default::default(),
             sel_mode: SelectionMode::None,
             selection: Default::default(),
+            selection_msgs: false,
             press_event: None,
             press_target: None,
+            press_coord: Coord::ZERO,
+            long_press: None,
+            item_text: None,
+            type_ahead: Default::default(),
         }
     }
 }
@@ -138,6 +191,7 @@ where
             frame_size: Default::default(),
             data,
             widgets: Default::default(),
+            data_len: 0,
             cur_len: 0,
             direction,
             align_hints: Default::default(),
@@ -147,11 +201,25 @@ where
             child_inter_margin: 0,
             child_skip: 0,
             child_size: Size::ZERO,
+            var_size: false,
+            var_offsets: vec![],
+            var_basis: (0, 0, 0),
+            var_probe: None,
+            empty_text: None,
+            loading: None,
+            loading_size: Size::ZERO,
+            spinner_size: Size::ZERO,
+            spinner_phase: 0.0,
             scroll: Default::default(),
             sel_mode: SelectionMode::None,
             selection: Default::default(),
+            selection_msgs: false,
             press_event: None,
             press_target: None,
+            press_coord: Coord::ZERO,
+            long_press: None,
+            item_text: None,
+            type_ahead: Default::default(),
         }
     }
 
@@ -194,6 +262,55 @@ where
         self
     }
 
+    /// Get the set of selected keys
+    pub fn selection(&self) -> &LinearSet<T::Key> {
+        &self.selection
+    }
+
+    /// Replace the selection
+    ///
+    /// The selection mode is respected: with [`SelectionMode::None`] the
+    /// selection is cleared and with [`SelectionMode::Single`] only the first
+    /// key is used.
+    ///
+    /// Does not send [`ListMsg`] responses.
+    pub fn set_selection<I: IntoIterator<Item = T::Key>>(&mut self, keys: I) -> TkAction {
+        self.selection.clear();
+        let mut keys = keys.into_iter();
+        match self.sel_mode {
+            SelectionMode::None => (),
+            SelectionMode::Single => self.selection.extend(keys.next()),
+            SelectionMode::Multiple => self.selection.extend(keys),
+        }
+        TkAction::REDRAW
+    }
+
+    /// Select all entries
+    ///
+    /// This does nothing unless the mode is [`SelectionMode::Multiple`].
+    ///
+    /// Does not send [`ListMsg`] responses.
+    pub fn select_all(&mut self) -> TkAction {
+        match self.sel_mode {
+            SelectionMode::Multiple => {
+                let len = self.data.len();
+                let keys = self.data.iter_vec(len).into_iter().map(|(key, _)| key);
+                self.set_selection(keys)
+            }
+            _ => TkAction::empty(),
+        }
+    }
+
+    /// Enable or disable reporting of the complete selection
+    ///
+    /// If enabled, selection changes made by the user are reported via
+    /// [`ListMsg::Selection`] instead of [`ListMsg::Select`] and
+    /// [`ListMsg::Deselect`]. Disabled by default.
+    pub fn with_selection_msgs(mut self, enable: bool) -> Self {
+        self.selection_msgs = enable;
+        self
+    }
+
     /// Read the list of selected entries
     ///
     /// With mode [`SelectionMode::Single`] this may contain zero or one entry;
@@ -233,14 +350,44 @@ where
     }
 
     /// Manually trigger an update to handle changed data
+    ///
+    /// The scroll offset is preserved where possible (unless the data length
+    /// changes such that the current offset is out of range).
     pub fn update_view(&mut self, mgr: &mut Manager) {
         for w in &mut self.widgets {
             w.key = None;
         }
+        let mut resize = self.data.len() != usize::conv(self.data_len);
+        if self.var_size && !resize && self.var_offsets.len() == self.data.len() + 1 {
+            // Item content may have changed: re-measure
+            let old_len = self.var_offsets[self.var_offsets.len() - 1];
+            let view_len = (self.core.rect.size - self.frame_size).extract(self.direction);
+            let (num, _) = self.measure_items(mgr, self.child_size, view_len);
+            resize = old_len != self.var_offsets[self.var_offsets.len() - 1]
+                || usize::conv(num) > self.widgets.len();
+        }
         self.update_widgets(mgr);
-        // Force SET_SIZE so that scroll-bar wrappers get updated
-        trace!("update_view triggers SET_SIZE");
-        *mgr |= TkAction::SET_SIZE;
+        if self.loading.is_some() && self.data.len() > 0 {
+            self.loading = None;
+            mgr.update_on_frame(self.id(), false);
+        }
+        if resize {
+            // Force SET_SIZE so that scroll-bar wrappers get updated
+            trace!("update_view triggers SET_SIZE");
+            *mgr |= TkAction::SET_SIZE;
+        } else {
+            mgr.redraw(self.id());
+        }
+    }
+
+    /// Get the scrollable range
+    ///
+    /// The scroll offset (see [`ScrollWidget::scroll_offset`]) along the
+    /// list's axis is always within this range; the other component is zero.
+    /// Together with [`ScrollWidget::set_scroll_offset`] this may be used to
+    /// implement an external scroll bar or to synchronise multiple views.
+    pub fn scroll_range(&self) -> RangeInclusive<i32> {
+        0..=self.scroll.max_offset().extract(self.direction).max(0)
     }
 
     /// Get the direction of contents
@@ -257,11 +404,206 @@ where
         self
     }
 
+    /// Enable variable-size items (inline)
+    ///
+    /// By default, all items are assumed to have the same size along the
+    /// list's axis (the size of the first item). When enabled, the ideal size
+    /// of each item is measured when the data is updated (see
+    /// [`ListView::update_view`]) and when the view's width (for a vertical
+    /// list) or child size rules change, but not on other resizes. Measuring
+    /// has `O(n)` cost in the number of items. Mapping a scroll position to an
+    /// item uses a cumulative offset table and has `O(log n)` cost.
+    ///
+    /// This is useful for items with wrapped text.
+    pub fn with_variable_size(mut self, variable: bool) -> Self {
+        self.var_size = variable;
+        self
+    }
+
+    /// Enable type-ahead find (inline)
+    ///
+    /// When the list or one of its items has keyboard focus, typing selects
+    /// (according to the [`SelectionMode`]) and scrolls to the first item
+    /// whose text starts with the characters typed, ignoring case. The `text`
+    /// function gets the text of an item, for example `|item| item.to_string()`.
+    ///
+    /// Searching has `O(n)` cost in the number of items. Items are requested
+    /// a view-full at a time and the search stops at the first match, thus a
+    /// lazily-loaded source such as [`super::PagedListData`] only fetches
+    /// pages up to the matching item.
+    pub fn with_type_ahead(mut self, text: fn(T::Item) -> String) -> Self {
+        self.item_text = Some(text);
+        self
+    }
+
+    /// Set a placeholder message shown when the list is empty
+    ///
+    /// When the data has no items (for example, no items match a filter) this
+    /// text is drawn centred within the view instead of a blank area. Pass
+    /// `None` to remove the placeholder. The message may be changed at any
+    /// time, for example to distinguish "no data" from "no matching results".
+    pub fn set_empty_placeholder(&mut self, text: Option<String>) -> TkAction {
+        if let (Some(t), Some(text)) = (self.empty_text.as_mut(), text.as_ref()) {
+            let avail = self.core.rect.size.clamped_sub(self.frame_size);
+            return kas::text::util::set_text_and_prepare(t, text.clone(), avail);
+        }
+        if self.empty_text.is_none() && text.is_none() {
+            return TkAction::empty();
+        }
+        self.empty_text = text.map(Text::new_multi);
+        TkAction::RESIZE
+    }
+
+    /// Show or hide the loading overlay
+    ///
+    /// This is intended for models which fetch data asynchronously: call
+    /// `set_loading(mgr, true)` when a fetch is started. While loading, an
+    /// animated busy indicator (as drawn by [`Spinner`](kas::widget::Spinner))
+    /// is shown over the centre of the view (any rows already present are
+    /// still drawn and updated underneath). The overlay is hidden by
+    /// `set_loading(mgr, false)` or automatically by [`ListView::update_view`]
+    /// once the data is non-empty.
+    ///
+    /// Since this requires the [`Manager`] to (re)start animation, it does not
+    /// return a [`TkAction`].
+    pub fn set_loading(&mut self, mgr: &mut Manager, loading: bool) {
+        if loading == self.loading.is_some() {
+            return;
+        }
+        self.loading = if loading { Some(None) } else { None };
+        mgr.update_on_frame(self.id(), loading);
+        mgr.redraw(self.id());
+    }
+
+    /// Set progress of the loading overlay
+    ///
+    /// If the fraction of data loaded is known, this may be used to replace
+    /// the busy indicator with a progress bar; `value` is clamped to the
+    /// range `0..=1`. Has no effect unless loading (see
+    /// [`ListView::set_loading`]).
+    pub fn set_loading_progress(&mut self, value: f32) -> TkAction {
+        let value = value.max(0.0).min(1.0);
+        match self.loading.as_mut() {
+            Some(v) if *v != Some(value) => {
+                *v = Some(value);
+                TkAction::REDRAW
+            }
+            _ => TkAction::empty(),
+        }
+    }
+
+    /// Whether the loading overlay is shown
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// Set a placeholder message shown when the list is empty (inline)
+    ///
+    /// See [`ListView::set_empty_placeholder`].
+    pub fn with_empty_placeholder<S: ToString>(mut self, text: S) -> Self {
+        self.empty_text = Some(Text::new_multi(text.to_string()));
+        self
+    }
+
+    // Measure all items (variable-size mode).
+    // Returns (number of widgets required, content length).
+    fn measure_items(&mut self, mgr: &mut Manager, child_size: Size, view_len: i32) -> (i32, i32) {
+        let vertical = self.direction.is_vertical();
+        let cross = match vertical {
+            false => child_size.1,
+            true => child_size.0,
+        };
+        let margin = self.child_inter_margin;
+        let data = &self.data;
+        let probe = &mut self.var_probe;
+        let mut offsets = std::mem::take(&mut self.var_offsets);
+        offsets.clear();
+        offsets.push(0);
+        mgr.size_handle(|size_handle| {
+            let mut pos = 0;
+            for (_, item) in data.iter_vec(data.len()) {
+                // Re-use a single widget: this avoids allocating per item
+                if let Some(w) = probe.as_mut() {
+                    let _ = w.set(item);
+                } else {
+                    *probe = Some(W::new(item));
+                }
+                let widget = probe.as_mut().unwrap();
+                let _ = widget.size_rules(size_handle, AxisInfo::new(!vertical, None));
+                let rules = widget.size_rules(size_handle, AxisInfo::new(vertical, Some(cross)));
+                pos += rules.ideal_size() + margin;
+                offsets.push(pos);
+            }
+        });
+        self.var_offsets = offsets;
+        self.var_basis = (cross, self.child_size_min, self.child_size_ideal);
+        self.var_num_and_len(view_len)
+    }
+
+    // Number of widgets required and content length, from var_offsets
+    fn var_num_and_len(&self, view_len: i32) -> (i32, i32) {
+        let margin = self.child_inter_margin;
+        let offsets = &self.var_offsets;
+        let content_len = (offsets[offsets.len() - 1] - margin).max(0);
+        let min_size = offsets
+            .windows(2)
+            .map(|w| w[1] - w[0] - margin)
+            .min()
+            .unwrap_or(i32::MAX);
+        let skip = (min_size.min(self.child_size_ideal) + margin).max(1);
+        ((view_len + skip - 1) / skip + 1, content_len)
+    }
+
+    // Index of the item at data-space position x (variable-size mode)
+    fn var_index_at(&self, x: i32) -> usize {
+        let len = self.var_offsets.len().saturating_sub(1);
+        let i = match self.var_offsets.binary_search(&x) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+        i.min(len.saturating_sub(1))
+    }
+
+    // Rect of the item at data index i (as positioned by update_widgets)
+    fn item_rect(&self, i: usize) -> Rect {
+        let pos_start = self.core.rect.pos + self.offset;
+        let reversed = self.direction.is_reversed();
+        if self.var_size && self.var_offsets.len() == self.data.len() + 1 {
+            let offsets = &self.var_offsets;
+            let total = offsets[offsets.len() - 1];
+            let size = offsets[i + 1] - offsets[i] - self.child_inter_margin;
+            let pos = match reversed {
+                false => offsets[i],
+                true => total - offsets[i + 1],
+            };
+            let mut rect = Rect::new(pos_start, self.child_size);
+            rect.pos
+                .set_component(self.direction, pos_start.extract(self.direction) + pos);
+            rect.size.set_component(self.direction, size);
+            rect
+        } else {
+            let mut skip = match self.direction.is_vertical() {
+                false => Offset(self.child_skip, 0),
+                true => Offset(0, self.child_skip),
+            };
+            let mut pos = pos_start;
+            if reversed {
+                pos += skip * (i32::conv(self.cur_len) - 1);
+                skip = skip * -1;
+            }
+            Rect::new(pos + skip * i32::conv(i), self.child_size)
+        }
+    }
+
     fn update_widgets(&mut self, mgr: &mut Manager) {
         let time = Instant::now();
         // set_rect allocates enough widgets to view a page; we update widget-data allocations
         let len = self.widgets.len().min(self.data.len());
         self.cur_len = len.cast();
+        let var_size = self.var_size && self.var_offsets.len() == self.data.len() + 1;
+        if var_size {
+            return self.update_widgets_var(mgr, len);
+        }
         let offset = u64::conv(self.scroll_offset().extract(self.direction));
         let first_data = usize::conv(offset / u64::conv(self.child_skip));
         let mut skip = match self.direction.is_vertical() {
@@ -292,10 +634,56 @@ where
             rect.pos = pos_start + skip * i32::conv(i);
             w.widget.set_rect(mgr, rect, self.align_hints);
         }
+        mgr.update_rect_cache(&*self);
         *mgr |= action;
         let dur = (Instant::now() - time).as_micros();
         trace!("ListView::update_widgets completed in {}μs", dur);
     }
+
+    // Variant of update_widgets for variable-size mode
+    fn update_widgets_var(&mut self, mgr: &mut Manager, len: usize) {
+        let offsets = &self.var_offsets;
+        let total = offsets[offsets.len() - 1];
+        let margin = self.child_inter_margin;
+        let reversed = self.direction.is_reversed();
+        let view_len = (self.core.rect.size - self.frame_size).extract(self.direction);
+        let offset = self.scroll_offset().extract(self.direction);
+        let start = match reversed {
+            false => offset,
+            true => total - margin - offset - view_len,
+        };
+        let first_data = self.var_index_at(start);
+
+        let pos_start = self.core.rect.pos + self.offset;
+        let mut action = TkAction::empty();
+        for (i, item) in self
+            .data
+            .iter_vec_from(first_data, len)
+            .into_iter()
+            .enumerate()
+        {
+            let i = first_data + i;
+            let key = Some(item.0);
+            let w = &mut self.widgets[i % len];
+            if key != w.key {
+                w.key = key;
+                action |= w.widget.set(item.1);
+            }
+            let offsets = &self.var_offsets;
+            let size = offsets[i + 1] - offsets[i] - margin;
+            let pos = match reversed {
+                false => offsets[i],
+                true => total - offsets[i + 1],
+            };
+            let mut rect = Rect::new(pos_start, self.child_size);
+            rect.pos
+                .set_component(self.direction, pos_start.extract(self.direction) + pos);
+            rect.size.set_component(self.direction, size);
+            w.widget.set_rect(mgr, rect, self.align_hints);
+        }
+        mgr.update_rect_cache(&*self);
+        *mgr |= action;
+    }
 }
 
 impl<D: Directional, T: ListData + 'static, W: ViewWidget<T::Item>> ScrollWidget
@@ -306,9 +694,14 @@ where
     fn scroll_axes(&self, size: Size) -> (bool, bool) {
         // TODO: maybe we should support a scrollbar on the other axis?
         // We would need to report a fake min-child-size to enable scrolling.
-        let item_min = self.child_size_min + self.child_inter_margin;
-        let num = i32::conv(self.data.len());
-        let min_size = (item_min * num - self.child_inter_margin).max(0);
+        let min_size = if self.var_size && self.var_offsets.len() == self.data.len() + 1 {
+            let total = self.var_offsets[self.var_offsets.len() - 1];
+            (total - self.child_inter_margin).max(0)
+        } else {
+            let item_min = self.child_size_min + self.child_inter_margin;
+            let num = i32::conv(self.data.len());
+            (item_min * num - self.child_inter_margin).max(0)
+        };
         (
             self.direction.is_horizontal() && min_size > size.0,
             self.direction.is_vertical() && min_size > size.1,
@@ -367,10 +760,13 @@ where
     T::Item: Default,
 {
     fn configure(&mut self, mgr: &mut Manager) {
-        if let Some(handle) = self.data.update_handle() {
+        for handle in self.data.update_handles() {
             mgr.update_on_handle(handle, self.id());
         }
         mgr.register_nav_fallback(self.id());
+        if self.loading == Some(None) {
+            mgr.update_on_frame(self.id(), true);
+        }
     }
 }
 
@@ -402,6 +798,11 @@ where
             rules.multiply_with_margin(2, self.ideal_visible);
             rules.set_stretch(rules.stretch().max(StretchPolicy::HighUtility));
         }
+        if let Some(text) = self.empty_text.as_mut() {
+            rules.max_with(size_handle.text_bound(text, TextClass::Label, axis));
+        }
+        self.loading_size = size_handle.progress_bar();
+        self.spinner_size = size_handle.spinner();
         let (rules, offset, size) = frame.surround(rules);
         self.offset.set_component(axis, offset);
         self.frame_size.set_component(axis, size);
@@ -413,10 +814,11 @@ where
 
         let data_len = self.data.len();
         let data_len32 = i32::try_from(data_len).unwrap();
+        self.data_len = data_len.cast();
         let mut child_size = rect.size - self.frame_size;
         let mut content_size = rect.size;
         let skip;
-        let num;
+        let mut num;
         if self.direction.is_horizontal() {
             if child_size.0 >= self.ideal_visible * self.child_size_ideal {
                 child_size.0 = self.child_size_ideal;
@@ -443,8 +845,31 @@ where
             content_size.1 = (skip.1 * data_len32 - self.child_inter_margin).max(0);
         }
 
+        if self.var_size {
+            let view_len = (rect.size - self.frame_size).extract(self.direction);
+            let cross = match self.direction.is_vertical() {
+                false => child_size.1,
+                true => child_size.0,
+            };
+            let basis = (cross, self.child_size_min, self.child_size_ideal);
+            let valid = basis == self.var_basis && self.var_offsets.len() == data_len + 1;
+            let (n, content_len) = match valid {
+                true => self.var_num_and_len(view_len),
+                false => self.measure_items(mgr, child_size, view_len),
+            };
+            num = n;
+            content_size.set_component(self.direction, content_len);
+        }
+
         self.child_size = child_size;
         self.align_hints = align;
+        if let Some(text) = self.empty_text.as_mut() {
+            let bounds = rect.size.clamped_sub(self.frame_size).into();
+            text.update_env(|env| {
+                env.set_bounds(bounds);
+                env.set_align((Align::Centre, Align::Centre));
+            });
+        }
 
         let old_num = self.widgets.len();
         let num = usize::conv(num);
@@ -492,6 +917,10 @@ where
         }
     }
 
+    fn is_child_active(&self, index: usize) -> bool {
+        index < usize::conv(self.cur_len) && self.widgets[index].key.is_some()
+    }
+
     fn find_id(&self, coord: Coord) -> Option<WidgetId> {
         if !self.rect().contains(coord) {
             return None;
@@ -508,6 +937,41 @@ where
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, disabled: bool) {
         let disabled = disabled || self.is_disabled();
+        if let Some(value) = self.loading {
+            self.draw_rows(draw_handle, mgr, disabled);
+            let rect = self.core.rect;
+            let state = self.input_state(mgr, disabled);
+            if let Some(value) = value {
+                let size = Size(
+                    (rect.size.0 / 2).max(self.loading_size.0.min(rect.size.0)),
+                    self.loading_size.1.min(rect.size.1),
+                );
+                let pos = rect.pos + (rect.size - size) / 2;
+                let dir = Direction::Right;
+                draw_handle.progress_bar(Rect::new(pos, size), dir, state, value);
+            } else {
+                let size = self.spinner_size.min(rect.size);
+                let pos = rect.pos + (rect.size - size) / 2;
+                draw_handle.spinner(Rect::new(pos, size), state, self.spinner_phase);
+            }
+            return;
+        }
+        if let Some(text) = self.empty_text.as_ref() {
+            if self.data.len() == 0 {
+                let pos = self.core.rect.pos + self.offset;
+                draw_handle.text(pos, text, TextClass::Label);
+                return;
+            }
+        }
+        self.draw_rows(draw_handle, mgr, disabled);
+    }
+}
+
+impl<D: Directional, T: ListData + 'static, W: ViewWidget<T::Item>> ListView<D, T, W>
+where
+    T::Item: Default,
+{
+    fn draw_rows(&self, draw_handle: &mut dyn DrawHandle, mgr: &ManagerState, disabled: bool) {
         let offset = self.scroll_offset();
         use kas::draw::ClipRegion::Scroll;
         draw_handle.clip_region(self.core.rect, offset, Scroll, &mut |draw_handle| {
@@ -523,6 +987,71 @@ where
     }
 }
 
+impl<D: Directional, T: ListData + 'static, W: ViewWidget<T::Item>> ListView<D, T, W>
+where
+    T::Item: Default,
+{
+    fn context_msg(
+        &mut self,
+        mgr: &mut Manager,
+        key: T::Key,
+        coord: Coord,
+    ) -> Response<<Self as Handler>::Msg> {
+        match self.sel_mode {
+            SelectionMode::None => (),
+            _ if self.selection.contains(&key) => (),
+            _ => {
+                self.selection.clear();
+                self.selection.insert(key.clone());
+                mgr.redraw(self.id());
+            }
+        }
+        ListMsg::Context { key, coord }.into()
+    }
+
+    fn type_ahead_find(
+        &mut self,
+        mgr: &mut Manager,
+        c: char,
+    ) -> Option<Response<<Self as Handler>::Msg>> {
+        let text = self.item_text?;
+        let prefix = self.type_ahead.push(mgr, c)?;
+        // Search a view-full of items at a time, stopping at the first match,
+        // to avoid loading all of a lazily-loaded data source
+        let chunk = self.widgets.len().max(1);
+        let mut start = 0;
+        let (index, key) = loop {
+            let mut items = self.data.iter_vec_from(start, chunk);
+            if items.is_empty() {
+                return None;
+            }
+            let pos = items
+                .iter()
+                .position(|(_, item)| TypeAhead::matches(prefix, &text(item.clone())));
+            if let Some(i) = pos {
+                break (start + i, items.swap_remove(i).0);
+            }
+            start += items.len();
+        };
+
+        let rect = self.item_rect(index);
+        let (rect, action) = self.scroll.focus_rect(rect, self.core.rect);
+        *mgr |= action;
+        self.update_widgets(mgr);
+
+        if let SelectionMode::None = self.sel_mode {
+            return Some(Response::Focus(rect));
+        }
+        self.selection.clear();
+        self.selection.insert(key.clone());
+        mgr.redraw(self.id());
+        Some(match self.selection_msgs {
+            true => ListMsg::Selection(self.selection.clone()).into(),
+            false => ListMsg::Select(key).into(),
+        })
+    }
+}
+
 impl<D: Directional, T: ListData + 'static, W: ViewWidget<T::Item>> SendEvent for ListView<D, T, W>
 where
     T::Item: Default,
@@ -552,8 +1081,19 @@ where
                             if mgr.request_grab(self.id(), source, coord, GrabMode::Grab, None) {
                                 self.press_event = Some(source);
                                 self.press_target = key;
+                                self.press_coord = coord - self.scroll_offset();
+                                if source.is_touch() {
+                                    let delay = mgr.long_press_delay();
+                                    self.long_press = Some(Instant::now() + delay);
+                                    mgr.update_on_timer(delay, self.id());
+                                }
                             }
                             return Response::None;
+                        } else if matches!(source, PressSource::Mouse(MouseButton::Right, _)) {
+                            if let Some(key) = key {
+                                let coord = coord - self.scroll_offset();
+                                return self.context_msg(mgr, key, coord);
+                            }
                         }
                     }
                     event
@@ -581,46 +1121,88 @@ where
         } else {
             debug_assert!(id == self.id(), "SendEvent::send: bad WidgetId");
             match event {
-                Event::HandleUpdate { .. } => {
+                Event::HandleUpdate { handle, .. } => {
+                    self.data.on_update(handle);
                     self.update_view(mgr);
                     return Response::None;
                 }
+                Event::TimerUpdate => {
+                    if let (Some(time), Some(key)) = (self.long_press, self.press_target.clone()) {
+                        if self.press_event.is_some() && time <= Instant::now() {
+                            self.press_event = None;
+                            self.long_press = None;
+                            return self.context_msg(mgr, key, self.press_coord);
+                        }
+                    }
+                    if self.loading == Some(None) {
+                        // One revolution per second
+                        let delta = mgr.frame_delta().as_secs_f32();
+                        self.spinner_phase = (self.spinner_phase + delta).fract();
+                        mgr.redraw(self.id());
+                    } else {
+                        mgr.update_on_frame(self.id(), false);
+                    }
+                    return Response::None;
+                }
                 Event::PressMove { source, .. } if self.press_event == Some(source) => {
                     self.press_event = None;
+                    self.long_press = None;
                     mgr.update_grab_cursor(self.id(), CursorIcon::Grabbing);
                     event // fall through to scroll handler
                 }
                 Event::PressEnd { source, .. } if self.press_event == Some(source) => {
                     self.press_event = None;
-                    return match self.sel_mode {
-                        SelectionMode::None => Response::None,
+                    self.long_press = None;
+                    let msg = match self.sel_mode {
+                        SelectionMode::None => None,
                         SelectionMode::Single => {
                             self.selection.clear();
                             if let Some(ref key) = self.press_target {
                                 self.selection.insert(key.clone());
-                                ListMsg::Select(key.clone()).into()
+                                Some(ListMsg::Select(key.clone()))
                             } else {
-                                Response::None
+                                None
                             }
                         }
                         SelectionMode::Multiple => {
                             if let Some(ref key) = self.press_target {
                                 if self.selection.remove(key) {
-                                    ListMsg::Deselect(key.clone()).into()
+                                    Some(ListMsg::Deselect(key.clone()))
                                 } else {
                                     self.selection.insert(key.clone());
-                                    ListMsg::Select(key.clone()).into()
+                                    Some(ListMsg::Select(key.clone()))
                                 }
                             } else {
-                                Response::None
+                                None
                             }
                         }
                     };
+                    return match msg {
+                        Some(_) if self.selection_msgs => {
+                            ListMsg::Selection(self.selection.clone()).into()
+                        }
+                        Some(msg) => msg.into(),
+                        None => Response::None,
+                    };
                 }
                 event => event,
             }
         };
 
+        if let Event::ReceivedCharacter(c) = event {
+            return match self.type_ahead_find(mgr, c) {
+                Some(response) => response,
+                None => Response::Unhandled(event),
+            };
+        }
+
+        if let Event::Command(Command::SelectAll, _) = event {
+            if let SelectionMode::Multiple = self.sel_mode {
+                *mgr |= self.select_all();
+                return ListMsg::Selection(self.selection.clone()).into();
+            }
+        }
+
         let id = self.id();
         let (action, response) =
             self.scroll
@@ -12,15 +12,21 @@ use kas::prelude::*;
 
 mod data_traits;
 mod filter;
+mod grid;
 mod list;
+mod paged;
 mod shared;
 mod single;
+mod sorted;
 
-pub use data_traits::{ListData, SingleData, SingleDataMut};
+pub use data_traits::{GridData, ListData, SingleData, SingleDataMut};
 pub use filter::{Filter, FilteredList, SimpleCaseInsensitiveFilter};
+pub use grid::GridView;
 pub use list::{ListMsg, ListView, SelectionMode};
-pub use shared::{SharedConst, SharedRc};
+pub use paged::PagedListData;
+pub use shared::{MappedSharedRc, SharedConst, SharedRc};
 pub use single::SingleView;
+pub use sorted::SortedList;
 
 /// View widgets
 ///
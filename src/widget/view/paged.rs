@@ -0,0 +1,220 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Lazily-loaded (paged) list data
+
+use super::ListData;
+#[allow(unused)]
+use kas::event::Manager;
+use kas::event::UpdateHandle;
+use linear_map::set::LinearSet;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+
+#[derive(Debug)]
+struct PageCache<T> {
+    /// Loaded pages as `(page_index, rows)`, most-recently-used first
+    pages: VecDeque<(usize, Vec<T>)>,
+    /// Pages requested but not yet loaded
+    pending: LinearSet<usize>,
+}
+
+/// List data loaded on demand, one page at a time
+///
+/// This adapter allows a [`super::ListView`] to view a large or remote data
+/// source (e.g. a database or REST API). Keys are row indices.
+///
+/// Rows are fetched a page at a time using the function `fetch`, which is
+/// called with `(start, limit)` whenever a page not in the cache is accessed.
+/// The function may either:
+///
+/// -   return `Some(rows)`, loading the page immediately, or
+/// -   start an asynchronous request and return `None`; when the rows arrive,
+///     pass them to [`PagedListData::insert_page`] then trigger the returned
+///     [`UpdateHandle`] via [`Manager::trigger_update`] or the toolkit proxy.
+///
+/// Rows which are not yet loaded are represented by the `placeholder` item.
+///
+/// At most `capacity` pages are cached; when this is exceeded the
+/// least-recently-used page is evicted. The capacity should be large enough to
+/// hold all pages visible at once.
+pub struct PagedListData<T: Clone + Debug, F: Fn(usize, usize) -> Option<Vec<T>>> {
+    handle: UpdateHandle,
+    len: Cell<usize>,
+    page_size: usize,
+    capacity: usize,
+    placeholder: T,
+    fetch: F,
+    cache: RefCell<PageCache<T>>,
+}
+
+impl<T: Clone + Debug, F: Fn(usize, usize) -> Option<Vec<T>>> Debug for PagedListData<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PagedListData")
+            .field("handle", &self.handle)
+            .field("len", &self.len)
+            .field("page_size", &self.page_size)
+            .field("capacity", &self.capacity)
+            .field("placeholder", &self.placeholder)
+            .field("fetch", &"<omitted>")
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+impl<T: Clone + Debug, F: Fn(usize, usize) -> Option<Vec<T>>> PagedListData<T, F> {
+    /// Construct
+    ///
+    /// The data source is assumed to have `len` rows. The default page size
+    /// is 64 rows and the default capacity is 16 pages.
+    pub fn new(len: usize, placeholder: T, fetch: F) -> Self {
+        PagedListData {
+            handle: UpdateHandle::new(),
+            len: Cell::new(len),
+            page_size: 64,
+            capacity: 16,
+            placeholder,
+            fetch,
+            cache: RefCell::new(PageCache {
+                pages: VecDeque::new(),
+                pending: Default::default(),
+            }),
+        }
+    }
+
+    /// Set the number of rows per page
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        assert!(page_size > 0);
+        self.page_size = page_size;
+        self
+    }
+
+    /// Set the maximum number of cached pages
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0);
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the number of rows
+    ///
+    /// Returns the [`UpdateHandle`] to trigger.
+    pub fn set_len(&self, len: usize) -> UpdateHandle {
+        self.len.set(len);
+        self.handle
+    }
+
+    /// Insert the page starting at row `start`
+    ///
+    /// This should be called when rows requested asynchronously arrive.
+    /// `start` must be a multiple of the page size.
+    ///
+    /// Returns the [`UpdateHandle`] to trigger.
+    pub fn insert_page(&self, start: usize, rows: Vec<T>) -> UpdateHandle {
+        debug_assert_eq!(start % self.page_size, 0);
+        let page = start / self.page_size;
+        let mut cache = self.cache.borrow_mut();
+        cache.pending.remove(&page);
+        cache.pages.retain(|(p, _)| *p != page);
+        cache.pages.push_front((page, rows));
+        cache.pages.truncate(self.capacity);
+        self.handle
+    }
+
+    /// Drop all cached pages
+    ///
+    /// Pages will be fetched again when next accessed.
+    ///
+    /// Returns the [`UpdateHandle`] to trigger.
+    pub fn invalidate(&self) -> UpdateHandle {
+        let mut cache = self.cache.borrow_mut();
+        cache.pages.clear();
+        cache.pending.clear();
+        self.handle
+    }
+
+    /// Call `f` on the rows of `page`, fetching if necessary
+    ///
+    /// If the page is not available, `f` is not called.
+    fn with_page<R, G: FnOnce(&[T]) -> R>(&self, page: usize, f: G) -> Option<R> {
+        let mut cache = self.cache.borrow_mut();
+        if let Some(i) = cache.pages.iter().position(|(p, _)| *p == page) {
+            let entry = cache.pages.remove(i).unwrap();
+            cache.pages.push_front(entry);
+        } else if cache.pending.contains(&page) {
+            return None;
+        } else {
+            let start = page * self.page_size;
+            let limit = self.page_size.min(self.len.get().saturating_sub(start));
+            // The fetch function may call back into this object (e.g. to
+            // insert a page), thus we must not hold the borrow.
+            drop(cache);
+            let rows = (self.fetch)(start, limit);
+            cache = self.cache.borrow_mut();
+            match rows {
+                Some(rows) => {
+                    cache.pending.remove(&page);
+                    cache.pages.retain(|(p, _)| *p != page);
+                    cache.pages.push_front((page, rows));
+                    cache.pages.truncate(self.capacity);
+                }
+                None => {
+                    if !cache.pages.iter().any(|(p, _)| *p == page) {
+                        cache.pending.insert(page);
+                        return None;
+                    }
+                }
+            }
+        }
+        let (p, rows) = cache.pages.front().unwrap();
+        debug_assert_eq!(*p, page);
+        Some(f(rows))
+    }
+}
+
+impl<T: Clone + Debug, F: Fn(usize, usize) -> Option<Vec<T>>> ListData for PagedListData<T, F> {
+    type Key = usize;
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    fn get_cloned(&self, key: &usize) -> Option<Self::Item> {
+        if *key >= self.len.get() {
+            return None;
+        }
+        let (page, index) = (*key / self.page_size, *key % self.page_size);
+        let item = self.with_page(page, |rows| rows.get(index).cloned());
+        Some(item.flatten().unwrap_or_else(|| self.placeholder.clone()))
+    }
+
+    fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
+        let end = self.len.get().min(start.saturating_add(limit));
+        let mut v = Vec::with_capacity(end.saturating_sub(start));
+        let mut key = start;
+        while key < end {
+            let page = key / self.page_size;
+            let page_end = end.min((page + 1) * self.page_size);
+            let offset = page * self.page_size;
+            self.with_page(page, |rows| {
+                for k in key..page_end {
+                    let item = rows.get(k - offset);
+                    v.push((k, item.cloned().unwrap_or_else(|| self.placeholder.clone())));
+                }
+            });
+            for k in v.len() + start..page_end {
+                v.push((k, self.placeholder.clone()));
+            }
+            key = page_end;
+        }
+        v
+    }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some(self.handle)
+    }
+}
@@ -15,7 +15,22 @@ use std::rc::Rc;
 
 /// Wrapper for shared constant data
 ///
-/// This may be useful with static data, e.g. `[&'static str]`.
+/// This may be useful with static data, e.g. `[&'static str]`. Since the data
+/// never changes, [`SingleData::update_handle`] and [`ListData::update_handle`]
+/// return `None`, thus views do not subscribe to updates.
+///
+/// Static data may be wrapped via [`SharedConst::new_single`] and
+/// [`SharedConst::new_list`] or via `From` conversions:
+/// ```
+/// use kas::widget::view::{ListView, SharedConst};
+/// use kas::dir::Down;
+///
+/// const OPTIONS: &[&str] = &["Red", "Green", "Blue"];
+/// let list: ListView<Down, _> = ListView::new(SharedConst::new_list(OPTIONS));
+///
+/// let data: &'static SharedConst<[&str]> = OPTIONS.into();
+/// let list2: ListView<Down, _> = ListView::new(data);
+/// ```
 #[derive(Clone, Debug, Default)]
 pub struct SharedConst<T: Debug + 'static + ?Sized>(T);
 
@@ -24,6 +39,34 @@ impl<T: Debug + 'static> SharedConst<T> {
     pub fn new(data: T) -> Self {
         SharedConst(data)
     }
+
+    /// Wrap a static value as single-item data
+    ///
+    /// ```
+    /// use kas::widget::view::{SharedConst, SingleView};
+    ///
+    /// static GREETING: &str = "Hello, world!";
+    /// let view = SingleView::new(SharedConst::new_single(&GREETING));
+    /// ```
+    pub fn new_single(data: &'static T) -> &'static Self {
+        data.into()
+    }
+}
+
+impl<T: Debug + 'static> SharedConst<[T]> {
+    /// Wrap a static slice (or array) as list data
+    ///
+    /// ```
+    /// use kas::widget::view::{ListData, SharedConst};
+    ///
+    /// static DAYS: [&str; 3] = ["Mon", "Tue", "Wed"];
+    /// let data = SharedConst::new_list(&DAYS);
+    /// assert_eq!(data.len(), 3);
+    /// assert_eq!(data.get_cloned(&1), Some("Tue"));
+    /// ```
+    pub fn new_list(data: &'static [T]) -> &'static Self {
+        data.into()
+    }
 }
 
 impl<T: Debug + 'static> From<T> for SharedConst<T> {
@@ -45,6 +88,10 @@ impl<T: Clone + Debug + 'static + ?Sized> SingleData for SharedConst<T> {
     fn get_cloned(&self) -> Self::Item {
         self.0.clone()
     }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        None
+    }
 }
 
 impl<T: ListData + 'static + ?Sized> ListData for SharedConst<T> {
@@ -66,6 +113,11 @@ impl<T: ListData + 'static + ?Sized> ListData for SharedConst<T> {
     fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
         self.0.iter_vec_from(start, limit)
     }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        // Constant data: ignore any handle of the wrapped value
+        None
+    }
 }
 
 /// Wrapper for single-thread shared data
@@ -92,6 +144,34 @@ impl<T: Debug> SharedRc<T> {
             data: Rc::new(RefCell::new(data)),
         }
     }
+
+    /// Project a field of the shared data
+    ///
+    /// The result shares both data and update handle with `self`: setting a
+    /// value through the projection updates all views of this data.
+    ///
+    /// ```
+    /// use kas::widget::view::{SharedRc, SingleData, SingleDataMut};
+    ///
+    /// #[derive(Clone, Debug, Default)]
+    /// struct Model {
+    ///     name: String,
+    ///     count: i32,
+    /// }
+    ///
+    /// let model = SharedRc::new(Model::default());
+    /// let count = model.map(|m| &m.count, |m| &mut m.count);
+    /// let handle = count.set(3);
+    /// assert_eq!(model.get_cloned().count, 3);
+    /// assert_eq!(Some(handle), model.update_handle());
+    /// ```
+    pub fn map<U>(&self, map: fn(&T) -> &U, map_mut: fn(&mut T) -> &mut U) -> MappedSharedRc<T, U> {
+        MappedSharedRc {
+            inner: self.clone(),
+            map,
+            map_mut,
+        }
+    }
 }
 
 impl<T: Clone + Debug> SingleData for SharedRc<T> {
@@ -136,3 +216,48 @@ impl<T: ListData> ListData for SharedRc<T> {
         Some(self.handle)
     }
 }
+
+/// A projection of [`SharedRc`] data
+///
+/// This is constructed via [`SharedRc::map`].
+pub struct MappedSharedRc<T: Debug, U> {
+    inner: SharedRc<T>,
+    map: fn(&T) -> &U,
+    map_mut: fn(&mut T) -> &mut U,
+}
+
+impl<T: Debug, U> Clone for MappedSharedRc<T, U> {
+    fn clone(&self) -> Self {
+        MappedSharedRc {
+            inner: self.inner.clone(),
+            map: self.map,
+            map_mut: self.map_mut,
+        }
+    }
+}
+
+impl<T: Debug, U> Debug for MappedSharedRc<T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MappedSharedRc")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T: Debug, U: Clone> SingleData for MappedSharedRc<T, U> {
+    type Item = U;
+
+    fn get_cloned(&self) -> Self::Item {
+        (self.map)(&self.inner.data.borrow()).clone()
+    }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some(self.inner.handle)
+    }
+}
+impl<T: Debug, U: Clone> SingleDataMut for MappedSharedRc<T, U> {
+    fn set(&self, value: Self::Item) -> UpdateHandle {
+        *(self.map_mut)(&mut self.inner.data.borrow_mut()) = value;
+        self.inner.handle
+    }
+}
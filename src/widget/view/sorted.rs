@@ -0,0 +1,214 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Sorting accessor
+
+use super::ListData;
+use kas::conv::Cast;
+#[allow(unused)]
+use kas::event::Manager;
+use kas::event::UpdateHandle;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+/// Sorted accessor over another accessor
+///
+/// Items are presented in the order given by a comparator. Keys and item
+/// types are those of the underlying list, thus [`ListData::get_cloned`]
+/// works with keys of the underlying data; [`SortedList::position`] maps a key
+/// to its position in the sorted order.
+///
+/// The sort order and an index of positions by key are cached (thus keys
+/// must implement `Hash`). This has its own update handle, triggered when the
+/// comparator changes. Views also subscribe to the underlying data's update
+/// handle, if any, and re-sort the data when this is triggered (see
+/// [`ListData::update_handles`]). Otherwise, after modifying the data, call
+/// [`SortedList::refresh`] and trigger the returned handle.
+pub struct SortedList<T: ListData, F: Fn(&T::Item, &T::Item) -> Ordering + 'static> {
+    /// Direct access to unsorted data
+    ///
+    /// If adjusting this, one should call [`SortedList::refresh`] after.
+    pub data: T,
+    compare: F,
+    view: Vec<T::Key>,
+    positions: HashMap<T::Key, usize>,
+    update: UpdateHandle,
+}
+
+impl<T: ListData, F: Fn(&T::Item, &T::Item) -> Ordering> Debug for SortedList<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SortedList")
+            .field("data", &self.data)
+            .field("view", &self.view)
+            .field("update", &self.update)
+            .finish()
+    }
+}
+
+impl<T: ListData, F: Fn(&T::Item, &T::Item) -> Ordering> SortedList<T, F>
+where
+    T::Key: Hash,
+{
+    /// Construct and sort
+    pub fn new(data: T, compare: F) -> Self {
+        let len = data.len().cast();
+        let mut s = SortedList {
+            data,
+            compare,
+            view: Vec::with_capacity(len),
+            positions: HashMap::with_capacity(len),
+            update: UpdateHandle::new(),
+        };
+        let _ = s.refresh();
+        s
+    }
+
+    /// Refresh the view
+    ///
+    /// Re-sorts all data (`O(n log n)` where `n` is the number of data
+    /// elements). This should be called after modifying the data.
+    ///
+    /// An update should be triggered using the returned handle.
+    pub fn refresh(&mut self) -> UpdateHandle {
+        let mut items = self.data.iter_vec(usize::MAX);
+        let compare = &self.compare;
+        items.sort_by(|a, b| compare(&a.1, &b.1));
+
+        self.view.clear();
+        self.positions.clear();
+        for (i, (key, _)) in items.into_iter().enumerate() {
+            self.positions.insert(key.clone(), i);
+            self.view.push(key);
+        }
+        self.update
+    }
+
+    /// Replace the comparator and re-sort
+    ///
+    /// An update should be triggered using the returned handle.
+    pub fn set_compare(&mut self, compare: F) -> UpdateHandle {
+        self.compare = compare;
+        self.refresh()
+    }
+
+    /// Get the position of `key` in the sorted order
+    pub fn position(&self, key: &T::Key) -> Option<usize> {
+        self.positions.get(key).cloned()
+    }
+}
+
+impl<T: ListData, F: Fn(&T::Item, &T::Item) -> Ordering> ListData for SortedList<T, F>
+where
+    T::Key: Hash,
+{
+    type Key = T::Key;
+    type Item = T::Item;
+
+    fn len(&self) -> usize {
+        self.view.len()
+    }
+
+    fn get_cloned(&self, key: &Self::Key) -> Option<Self::Item> {
+        self.data.get_cloned(key)
+    }
+
+    fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
+        let end = self.len().min(start + limit);
+        if start >= end {
+            return Vec::new();
+        }
+        let mut v = Vec::with_capacity(end - start);
+        for k in &self.view[start..end] {
+            if let Some(item) = self.data.get_cloned(k) {
+                v.push((k.clone(), item));
+            }
+        }
+        v
+    }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some(self.update)
+    }
+
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        let mut handles = self.data.update_handles();
+        handles.push(self.update);
+        handles
+    }
+
+    fn on_update(&mut self, handle: UpdateHandle) {
+        // Our own handle is only triggered after re-sorting
+        if handle != self.update {
+            self.data.on_update(handle);
+            let _ = self.refresh();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Data(Vec<i32>, Option<UpdateHandle>);
+
+    impl ListData for Data {
+        type Key = usize;
+        type Item = i32;
+
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+        fn get_cloned(&self, key: &usize) -> Option<i32> {
+            self.0.get_cloned(key)
+        }
+        fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(usize, i32)> {
+            self.0.iter_vec_from(start, limit)
+        }
+        fn update_handle(&self) -> Option<UpdateHandle> {
+            self.1
+        }
+    }
+
+    fn sorted(data: Data) -> SortedList<Data, fn(&i32, &i32) -> Ordering> {
+        SortedList::new(data, i32::cmp)
+    }
+
+    #[test]
+    fn ordering() {
+        let list = sorted(Data(vec![3, 1, 2], None));
+        assert_eq!(list.iter_vec(usize::MAX), vec![(1, 1), (2, 2), (0, 3)]);
+        assert_eq!(list.iter_vec_from(1, 1), vec![(2, 2)]);
+        assert_eq!(list.position(&0), Some(2));
+        assert_eq!(list.position(&3), None);
+        assert_eq!(list.get_cloned(&0), Some(3));
+    }
+
+    #[test]
+    fn insertion() {
+        let mut list = sorted(Data(vec![3, 1, 2], None));
+        list.data.0.push(0);
+        let handle = list.refresh();
+        assert_eq!(list.update_handle(), Some(handle));
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.position(&3), Some(0));
+        assert_eq!(list.iter_vec(2), vec![(3, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn update() {
+        let handle = UpdateHandle::new();
+        let mut list = sorted(Data(vec![3, 1, 2], Some(handle)));
+        assert!(list.update_handles().contains(&handle));
+
+        list.data.0[1] = 4;
+        list.on_update(handle);
+        assert_eq!(list.iter_vec(usize::MAX), vec![(2, 2), (0, 3), (1, 4)]);
+        assert_eq!(list.position(&1), Some(2));
+        assert_eq!(list.position(&2), Some(0));
+    }
+}
@@ -9,10 +9,10 @@ use smallvec::SmallVec;
 use std::fmt::{self, Debug};
 
 use kas::draw::ClipRegion;
-use kas::event::{self, UpdateHandle};
+use kas::event::{self, Command, UpdateHandle};
 use kas::layout;
 use kas::prelude::*;
-use kas::{Future, WindowId};
+use kas::{Future, WindowId, WindowPosition};
 
 /// The main instantiation of the [`Window`] trait.
 #[derive(Widget)]
@@ -21,26 +21,44 @@ pub struct Window<W: Widget + 'static> {
     #[widget_core]
     core: CoreData,
     restrict_dimensions: (bool, bool),
+    size_to_content: bool,
+    position: WindowPosition,
+    continuous_redraw: bool,
     title: String,
     #[widget]
     w: W,
     popups: SmallVec<[(WindowId, kas::Popup); 16]>,
     drop: Option<(Box<dyn FnMut(&mut W)>, UpdateHandle)>,
+    default_action: Option<Box<dyn Fn(&mut Manager)>>,
+    cancel_action: Option<Box<dyn Fn(&mut Manager)>>,
 }
 
 impl<W: Widget> Debug for Window<W> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Window {{ core: {:?}, restrict_dimensions: {:?}, title: {:?}, w: {:?}, popups: {:?}, drop: ",
-            self.core, self.restrict_dimensions, self.title, self.w, self.popups,
+            "Window {{ core: {:?}, restrict_dimensions: {:?}, size_to_content: {:?}, position: {:?}, continuous_redraw: {:?}, title: {:?}, w: {:?}, popups: {:?}, drop: ",
+            self.core, self.restrict_dimensions, self.size_to_content, self.position, self.continuous_redraw, self.title, self.w, self.popups,
         )?;
         if let Some(ref d) = self.drop {
             write!(f, "Some(<closure>, {:?})", d.1)?;
         } else {
             write!(f, "None")?;
         }
-        write!(f, " }}")
+        write!(
+            f,
+            ", default_action: {}, cancel_action: {} }}",
+            if self.default_action.is_some() {
+                "Some(<closure>)"
+            } else {
+                "None"
+            },
+            if self.cancel_action.is_some() {
+                "Some(<closure>)"
+            } else {
+                "None"
+            },
+        )
     }
 }
 
@@ -49,10 +67,15 @@ impl<W: Widget + Clone> Clone for Window<W> {
         Window {
             core: self.core.clone(),
             restrict_dimensions: self.restrict_dimensions.clone(),
+            size_to_content: self.size_to_content,
+            position: self.position,
+            continuous_redraw: self.continuous_redraw,
             title: self.title.clone(),
             w: self.w.clone(),
             popups: Default::default(), // these are temporary; don't clone
             drop: None,                 // we cannot clone this!
+            default_action: None,
+            cancel_action: None,
         }
     }
 }
@@ -63,10 +86,15 @@ impl<W: Widget> Window<W> {
         Window {
             core: Default::default(),
             restrict_dimensions: (true, false),
+            size_to_content: false,
+            position: WindowPosition::Default,
+            continuous_redraw: false,
             title: title.to_string(),
             w,
             popups: Default::default(),
             drop: None,
+            default_action: None,
+            cancel_action: None,
         }
     }
 
@@ -77,6 +105,67 @@ impl<W: Widget> Window<W> {
         self.restrict_dimensions = (min, max);
     }
 
+    /// Size the window to its content before first show
+    ///
+    /// See [`kas::Window::size_to_content`].
+    pub fn with_size_to_content(mut self) -> Self {
+        self.size_to_content = true;
+        self
+    }
+
+    /// Set the initial position of the window
+    ///
+    /// See [`kas::Window::position`].
+    pub fn with_position(mut self, position: WindowPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Enable or disable continuous redrawing
+    ///
+    /// See [`kas::Window::continuous_redraw`]. The returned action wakes the
+    /// shell, which then starts (or stops) redrawing.
+    pub fn set_continuous_redraw(&mut self, enable: bool) -> TkAction {
+        if enable == self.continuous_redraw {
+            return TkAction::empty();
+        }
+        self.continuous_redraw = enable;
+        TkAction::REDRAW
+    }
+
+    /// Enable continuous redrawing
+    ///
+    /// See [`kas::Window::continuous_redraw`].
+    pub fn with_continuous_redraw(mut self) -> Self {
+        self.continuous_redraw = true;
+        self
+    }
+
+    /// Set the default action, triggered by the `Return` key
+    ///
+    /// The action takes priority over the default widget (see
+    /// [`Manager::set_default_widget`]) and over keyboard navigation focus
+    /// (e.g. a focussed button or a single-line edit box), but not over a
+    /// widget with character focus which uses the key (e.g. a multi-line edit
+    /// box; see [`WidgetConfig::uses_return_key`]). It is not triggered while
+    /// a pop-up is open.
+    ///
+    /// In case the window is cloned, this action is *not* inherited.
+    pub fn set_default_action<F: Fn(&mut Manager) + 'static>(&mut self, f: F) {
+        self.default_action = Some(Box::new(f));
+    }
+
+    /// Set the cancel action, triggered by the `Escape` key
+    ///
+    /// Priority is as for [`Window::set_default_action`], except that a widget
+    /// with character focus never takes priority. A typical action closes the
+    /// window: `|mgr| *mgr |= TkAction::CLOSE`.
+    ///
+    /// In case the window is cloned, this action is *not* inherited.
+    pub fn set_cancel_action<F: Fn(&mut Manager) + 'static>(&mut self, f: F) {
+        self.cancel_action = Some(Box::new(f));
+    }
+
     /// Set a closure to be called on destruction, and return a future
     ///
     /// This is a convenience wrapper around [`Window::on_drop_boxed`].
@@ -159,9 +248,26 @@ impl<W: Widget> Layout for Window<W> {
 
 impl<M: Into<VoidMsg>, W: Widget<Msg = M> + 'static> event::SendEvent for Window<W> {
     fn send(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
-        if !self.is_disabled() && id <= self.w.id() {
+        if self.is_disabled() {
+            return Response::Unhandled(event);
+        }
+        if id <= self.w.id() {
             return self.w.send(mgr, id, event).into();
         }
+        let action = match event {
+            Event::Message(_) | Event::AppearanceChanged { .. } if id == self.id() => {
+                // Messages sent to the window are for its content
+                let id = self.w.id();
+                return self.w.send(mgr, id, event).into();
+            }
+            Event::Command(Command::Return, _) if id == self.id() => self.default_action.as_ref(),
+            Event::Command(Command::Escape, _) if id == self.id() => self.cancel_action.as_ref(),
+            _ => None,
+        };
+        if let Some(action) = action {
+            action(mgr);
+            return Response::None;
+        }
         Response::Unhandled(event)
     }
 }
@@ -175,6 +281,18 @@ impl<M: Into<VoidMsg>, W: Widget<Msg = M> + 'static> kas::Window for Window<W> {
         self.restrict_dimensions
     }
 
+    fn size_to_content(&self) -> bool {
+        self.size_to_content
+    }
+
+    fn position(&self) -> WindowPosition {
+        self.position
+    }
+
+    fn continuous_redraw(&self) -> bool {
+        self.continuous_redraw
+    }
+
     fn add_popup(&mut self, mgr: &mut Manager, id: WindowId, popup: kas::Popup) {
         let index = self.popups.len();
         self.popups.push((id, popup));
@@ -206,10 +324,10 @@ impl<M: Into<VoidMsg>, W: Widget<Msg = M> + 'static> kas::Window for Window<W> {
     }
 }
 
-// This is like WidgetChildren::find, but returns a translated Rect.
-fn find_rect(widget: &dyn WidgetConfig, id: WidgetId) -> Option<Rect> {
+// Sum of translations applied by ancestors of `id` (see Layout::translation)
+fn find_translation(widget: &dyn WidgetConfig, id: WidgetId) -> Option<Offset> {
     if id == widget.id() {
-        return Some(widget.rect());
+        return Some(Offset::ZERO);
     } else if id > widget.id() {
         return None;
     }
@@ -219,7 +337,7 @@ fn find_rect(widget: &dyn WidgetConfig, id: WidgetId) -> Option<Rect> {
             if id > w.id() {
                 continue;
             }
-            return find_rect(w, id).map(|rect| rect - widget.translation(i));
+            return find_translation(w, id).map(|offset| offset + widget.translation(i));
         }
         break;
     }
@@ -233,7 +351,13 @@ impl<W: Widget> Window<W> {
         let r = self.core.rect;
         let popup = &mut self.popups[index].1;
 
-        let c = find_rect(self.w.as_widget(), popup.parent).unwrap();
+        let c = match mgr.rect_of(popup.parent) {
+            Some(rect) => rect - find_translation(self.w.as_widget(), popup.parent).unwrap(),
+            None => {
+                log::warn!("Window: pop-up parent {} is not laid out", popup.parent);
+                return;
+            }
+        };
         let widget = self.w.find_leaf_mut(popup.id).unwrap();
         let mut cache = mgr.size_handle(|sh| layout::SolveCache::find_constraints(widget, sh));
         let ideal = cache.ideal(false);
@@ -277,3 +401,195 @@ impl<W: Widget> Window<W> {
         cache.apply_rect(widget, mgr, rect, false);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event::VirtualKeyCode;
+    use crate::testing::TestHarness;
+    use crate::widget::{EditField, TextButton};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[derive(Debug, Widget)]
+    #[layout(column)]
+    struct Dialog {
+        #[widget_core]
+        core: CoreData,
+        #[layout_data]
+        layout_data: <Self as kas::LayoutData>::Data,
+        #[widget]
+        edit: EditField<()>,
+        #[widget]
+        button: TextButton<VoidMsg>,
+    }
+
+    // Returns the number of default and cancel actions triggered
+    fn dialog(multi_line: bool) -> (TestHarness<Window<Dialog>>, Rc<Cell<(u32, u32)>>) {
+        let dialog = Dialog {
+            core: Default::default(),
+            layout_data: Default::default(),
+            edit: EditField::new("").multi_line(multi_line),
+            button: TextButton::new("Button"),
+        };
+        let mut window = Window::new("Dialog", dialog);
+        let count = Rc::new(Cell::new((0, 0)));
+        let c = count.clone();
+        window.set_default_action(move |_| c.set((c.get().0 + 1, c.get().1)));
+        let c = count.clone();
+        window.set_cancel_action(move |_| c.set((c.get().0, c.get().1 + 1)));
+        (TestHarness::with_size(window, Size(200, 100)), count)
+    }
+
+    #[test]
+    fn dialog_keys_with_nav_focus() {
+        let (mut harness, count) = dialog(false);
+        let id = harness.widget().w.button.id();
+        harness.with_manager(|mgr, _| mgr.set_nav_focus(id));
+        harness.press_key(VirtualKeyCode::Return);
+        assert_eq!(count.get(), (1, 0));
+        harness.press_key(VirtualKeyCode::Escape);
+        assert_eq!(count.get(), (1, 1));
+        assert!(harness.manager_state().nav_focus(id));
+    }
+
+    #[test]
+    fn dialog_keys_with_char_focus() {
+        let (mut harness, count) = dialog(false);
+        let id = harness.widget().w.edit.id();
+        harness.with_manager(|mgr, _| mgr.request_char_focus(id));
+        harness.press_key(VirtualKeyCode::Return);
+        assert_eq!(count.get(), (1, 0));
+
+        // A multi-line edit uses the Return key itself
+        let (mut harness, count) = dialog(true);
+        let id = harness.widget().w.edit.id();
+        harness.with_manager(|mgr, _| mgr.request_char_focus(id));
+        harness.press_key(VirtualKeyCode::Return);
+        assert_eq!(count.get(), (0, 0));
+        assert_eq!(harness.widget().w.edit.get_string(), "\n");
+        harness.press_key(VirtualKeyCode::Escape);
+        assert_eq!(count.get(), (0, 1));
+    }
+
+    #[derive(Debug, Widget)]
+    #[handler(handle = noauto)]
+    struct Receiver {
+        #[widget_core]
+        core: CoreData,
+        received: Option<i32>,
+    }
+
+    impl Layout for Receiver {
+        fn size_rules(&mut self, _: &mut dyn SizeHandle, _: AxisInfo) -> SizeRules {
+            SizeRules::EMPTY
+        }
+
+        fn draw(&self, _: &mut dyn DrawHandle, _: &event::ManagerState, _: bool) {}
+    }
+
+    impl event::Handler for Receiver {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, _: &mut Manager, event: Event) -> Response<VoidMsg> {
+            match event {
+                Event::Message(payload) => {
+                    self.received = payload.take();
+                    Response::None
+                }
+                event => Response::Unhandled(event),
+            }
+        }
+    }
+
+    #[test]
+    fn message_reaches_content() {
+        let receiver = Receiver {
+            core: Default::default(),
+            received: None,
+        };
+        let mut harness = TestHarness::new(Window::new("Receiver", receiver));
+        harness.with_manager(|mgr, window| mgr.send_message(window, Box::new(7i32)));
+        assert_eq!(harness.widget().w.received, Some(7));
+    }
+
+    // Uses accelerator key A without Alt; counts events received
+    #[derive(Debug, Default, Widget)]
+    #[widget(config = noauto)]
+    #[handler(handle = noauto)]
+    struct AccelTarget {
+        #[widget_core]
+        core: CoreData,
+        nav_focus: u32,
+        activated: u32,
+    }
+
+    impl WidgetConfig for AccelTarget {
+        fn configure(&mut self, mgr: &mut Manager) {
+            mgr.enable_alt_bypass(true);
+            mgr.add_accel_keys(self.id(), &[VirtualKeyCode::A]);
+        }
+
+        fn key_nav(&self) -> bool {
+            true
+        }
+    }
+
+    impl Layout for AccelTarget {
+        fn size_rules(&mut self, _: &mut dyn SizeHandle, _: AxisInfo) -> SizeRules {
+            SizeRules::EMPTY
+        }
+
+        fn draw(&self, _: &mut dyn DrawHandle, _: &event::ManagerState, _: bool) {}
+    }
+
+    impl event::Handler for AccelTarget {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, _: &mut Manager, event: Event) -> Response<VoidMsg> {
+            match event {
+                Event::NavFocus => self.nav_focus += 1,
+                Event::Activate => self.activated += 1,
+                event => return Response::Unhandled(event),
+            }
+            Response::None
+        }
+    }
+
+    #[derive(Debug, Widget)]
+    #[layout(row)]
+    struct AccelPair {
+        #[widget_core]
+        core: CoreData,
+        #[layout_data]
+        layout_data: <Self as kas::LayoutData>::Data,
+        #[widget]
+        a: AccelTarget,
+        #[widget]
+        b: AccelTarget,
+    }
+
+    #[test]
+    fn shared_accel_key_sends_nav_focus() {
+        let pair = AccelPair {
+            core: Default::default(),
+            layout_data: Default::default(),
+            a: Default::default(),
+            b: Default::default(),
+        };
+        let mut harness = TestHarness::with_size(Window::new("Accel", pair), Size(100, 50));
+
+        harness.press_key(VirtualKeyCode::A);
+        harness.update();
+        let pair = &harness.widget().w;
+        assert!(harness.manager_state().nav_focus(pair.a.id()));
+        assert_eq!((pair.a.nav_focus, pair.b.nav_focus), (1, 0));
+
+        harness.press_key(VirtualKeyCode::A);
+        harness.update();
+        let pair = &harness.widget().w;
+        assert!(harness.manager_state().nav_focus(pair.b.id()));
+        assert_eq!((pair.a.nav_focus, pair.b.nav_focus), (1, 1));
+        assert_eq!((pair.a.activated, pair.b.activated), (0, 0));
+    }
+}
@@ -0,0 +1,14 @@
+use kas::prelude::*;
+use kas::widget::TextButton;
+
+#[derive(Debug, Widget)]
+#[layout(single)]
+#[handler(msg = VoidMsg)]
+struct Parent {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    button: TextButton<i32>,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use kas::prelude::*;
+use kas::widget::Label;
+
+#[derive(Clone, Debug, Widget)]
+#[layout(row)]
+struct Pair {
+    #[widget_core]
+    core: CoreData,
+    #[widget]
+    first: Label<&'static str>,
+    #[widget]
+    second: Label<&'static str>,
+}
+
+fn main() {}